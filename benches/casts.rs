@@ -0,0 +1,111 @@
+//! The checked casts compared to `as`, `TryFrom` and `num-traits`
+//!
+//! Run with `cargo bench`. Each group is one pair of types, and casts a value
+//! that is in range, i.e. it measures the overhead of the checks on the path
+//! that is taken in practice.
+//!
+//! Every variant runs in a function that is never inlined and hides its input
+//! and output from the optimizer: the ones of `cast::bench_support`, and the
+//! ones below for `as` and `num-traits`, which can't be written generically.
+
+use std::convert::TryFrom;
+use std::hint::black_box;
+use std::num::TryFromIntError;
+
+use cast::bench_support;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// Benchmarks the ways to cast `$x: $src` into `$dst`; `TryFrom` only exists
+/// between integers, so those pairs are marked with `try_from`
+macro_rules! pairs {
+    ($($src:ident => $dst:ident: $x:expr $(, $try_from:ident)?;)+) => {
+        fn pairs(c: &mut Criterion) {
+            $({
+                #[inline(never)]
+                fn as_(x: $src) -> $dst {
+                    black_box(black_box(x) as $dst)
+                }
+
+                #[inline(never)]
+                fn num_traits(x: $src) -> Option<$dst> {
+                    black_box(num_traits::cast::<$src, $dst>(black_box(x)))
+                }
+
+                let mut group = c.benchmark_group(concat!(stringify!($src), " -> ", stringify!($dst)));
+                let x: $src = $x;
+
+                group.bench_function("as", |b| b.iter(|| as_(x)));
+                group.bench_function("cast", |b| {
+                    b.iter(|| bench_support::checked::<$dst, $src>(x))
+                });
+                group.bench_function("cast::saturating", |b| {
+                    b.iter(|| bench_support::saturating::<$dst, $src>(x))
+                });
+                $(
+                    group.bench_function(stringify!($try_from), |b| {
+                        b.iter(|| bench_support::try_from::<$dst, $src>(x))
+                    });
+                )?
+                group.bench_function("num-traits", |b| b.iter(|| num_traits(x)));
+
+                group.finish();
+            })+
+        }
+    };
+}
+
+pairs! {
+    u8 => u16: 200;
+    u32 => u8: 200, try_from;
+    i32 => u32: 1 << 30, try_from;
+    i64 => u32: 1 << 31, try_from;
+    u64 => i16: 1 << 14, try_from;
+    f32 => u8: 200.5;
+    f64 => i32: -1e9;
+    f64 => f32: 1e30;
+    u64 => f32: 1 << 40;
+}
+
+/// `as` of every element of `src` into `dst`; see `bench_support::checked_slice`
+#[inline(never)]
+fn as_slice(src: &[u32], dst: &mut [u8]) {
+    for (y, &x) in dst.iter_mut().zip(black_box(src)) {
+        *y = x as u8;
+    }
+
+    black_box(dst);
+}
+
+/// `u8::try_from` of every element of `src` into `dst`; see
+/// `bench_support::checked_slice`
+#[inline(never)]
+fn try_from_slice(src: &[u32], dst: &mut [u8]) -> Result<(), TryFromIntError> {
+    for (y, &x) in dst.iter_mut().zip(black_box(src)) {
+        *y = u8::try_from(x)?;
+    }
+
+    black_box(dst);
+    Ok(())
+}
+
+/// Casts of whole buffers, where the checks add up
+fn slices(c: &mut Criterion) {
+    const LEN: usize = 4096;
+
+    let src: Vec<u32> = (0..LEN as u32).map(|i| i % 256).collect();
+    let mut dst = vec![0u8; LEN];
+
+    let mut group = c.benchmark_group("[u32] -> [u8]");
+    group.throughput(Throughput::Elements(LEN as u64));
+
+    group.bench_function("as", |b| b.iter(|| as_slice(&src, &mut dst)));
+    group.bench_function("cast", |b| {
+        b.iter(|| bench_support::checked_slice(&src, &mut dst))
+    });
+    group.bench_function("try_from", |b| b.iter(|| try_from_slice(&src, &mut dst)));
+
+    group.finish();
+}
+
+criterion_group!(benches, pairs, slices);
+criterion_main!(benches);
@@ -0,0 +1,166 @@
+//! `#[derive(cast::From)]` for fieldless enums
+//!
+//! Use it through the `cast` crate, with its `derive` Cargo feature enabled,
+//! rather than directly: the expansion refers to `::cast`.
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Meta, Token};
+
+/// The types an enum can be `#[repr]`
+const INTEGERS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Implements `cast::From` between a fieldless enum and the primitives
+///
+/// The enum must have a `#[repr]` with an integer type. A cast from a
+/// primitive first casts it into that integer, and then looks for the variant
+/// with that discriminant; it fails with `cast::EnumError`, which tells apart
+/// values that don't fit in the integer from unknown discriminants. A cast
+/// into a primitive casts the discriminant, so it can't fail into the
+/// `#[repr]` type, nor into any type that can hold all of its values.
+///
+/// ```
+/// use cast::{EnumError, Error, From as _, OverflowError};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, cast::From)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop = 0x00,
+///     Load = 0x10,
+///     Store,
+///     Halt = 0xff,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Opcode::cast(0x11u32), Ok(Opcode::Store));
+/// assert_eq!(Opcode::cast(0x12u32), Err(EnumError::UnknownDiscriminant(0x12)));
+/// assert_eq!(Opcode::cast(256u32), Err(EnumError::Cast(Error::Overflow)));
+///
+/// let byte: u8 = cast::u8(Opcode::Halt);
+/// assert_eq!(byte, 0xff);
+/// assert_eq!(cast::i8(Opcode::Halt), Err(OverflowError));
+/// # }
+/// ```
+#[proc_macro_derive(From)]
+pub fn derive_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`#[derive(cast::From)]` only supports enums",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`#[derive(cast::From)]` doesn't support generic enums",
+        ));
+    }
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(Error::new_spanned(
+            variant,
+            "`#[derive(cast::From)]` only supports fieldless variants",
+        ));
+    }
+
+    let ty = &input.ident;
+    let repr = repr(input)?;
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    // the discriminants, as constants that can be matched against
+    let consts = (0..data.variants.len())
+        .map(|i| format_ident!("__CAST_DISCRIMINANT_{}", i))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl<__Src> ::cast::From<__Src> for #ty
+        where
+            #repr: ::cast::From<__Src>,
+            <#repr as ::cast::From<__Src>>::Output: ::cast::IntoResult<#repr>,
+        {
+            type Output = ::core::result::Result<#ty, ::cast::EnumError<#repr>>;
+            type Error = ::cast::EnumError<#repr>;
+            const FALLIBLE: bool = true;
+
+            #[inline]
+            fn cast(src: __Src) -> Self::Output {
+                #(const #consts: #repr = #ty::#variants as #repr;)*
+
+                let discriminant =
+                    ::cast::IntoResult::into_result(<#repr as ::cast::From<__Src>>::cast(src))
+                        .map_err(::cast::EnumError::Cast)?;
+
+                match discriminant {
+                    #(#consts => ::core::result::Result::Ok(#ty::#variants),)*
+                    _ => ::core::result::Result::Err(
+                        ::cast::derive::unknown::<__Src, #ty, #repr>(discriminant),
+                    ),
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::cast::Repr for #ty {
+            type Primitive = #repr;
+
+            #[inline]
+            fn repr(self) -> #repr {
+                self as #repr
+            }
+        }
+    })
+}
+
+/// The integer type in the `#[repr]` of the enum
+fn repr(input: &DeriveInput) -> syn::Result<Ident> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::Path(path) = meta {
+                if let Some(ident) = path.get_ident() {
+                    if INTEGERS.contains(&ident.to_string().as_str()) {
+                        return Ok(ident.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    Err(Error::new_spanned(
+        &input.ident,
+        "`#[derive(cast::From)]` requires a `#[repr]` with an integer type, e.g. `#[repr(u8)]`",
+    ))
+}
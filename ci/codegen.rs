@@ -0,0 +1,106 @@
+//! Monomorphic instances of the casts, to check their codegen
+//!
+//! `ci/codegen.sh` compiles this, against the `cast` library built for the
+//! target, into assembly and checks that:
+//!
+//! - every `promote_*` function compiles to the same instructions as its
+//!   `as_*` twin, which casts with `as`, i.e. that promotions are free,
+//! - so does every `saturate_*` function, i.e. that the saturating casts from
+//!   floats into integers compile to the target's saturating conversion, and
+//! - the range check of every `cast_*` function is a single comparison and at
+//!   most one conditional branch.
+
+#![no_std]
+
+use cast::Error;
+
+macro_rules! promotions {
+    ($($promote:ident, $as:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            #[no_mangle]
+            pub fn $promote(x: $src) -> $dst {
+                cast::$dst(x)
+            }
+
+            #[no_mangle]
+            pub fn $as(x: $src) -> $dst {
+                x as $dst
+            }
+        )+
+    };
+}
+
+macro_rules! saturations {
+    ($($saturate:ident, $as:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            #[no_mangle]
+            pub fn $saturate(x: $src) -> $dst {
+                cast::saturating(x)
+            }
+
+            #[no_mangle]
+            pub fn $as(x: $src) -> $dst {
+                x as $dst
+            }
+        )+
+    };
+}
+
+promotions! {
+    promote_u8_u16, as_u8_u16: u8 => u16;
+    promote_u8_i32, as_u8_i32: u8 => i32;
+    promote_u16_u64, as_u16_u64: u16 => u64;
+    promote_u32_u64, as_u32_u64: u32 => u64;
+    promote_u64_u128, as_u64_u128: u64 => u128;
+    promote_i8_i16, as_i8_i16: i8 => i16;
+    promote_i16_i64, as_i16_i64: i16 => i64;
+    promote_i32_i64, as_i32_i64: i32 => i64;
+    promote_i64_i128, as_i64_i128: i64 => i128;
+    promote_u16_f32, as_u16_f32: u16 => f32;
+    promote_i32_f64, as_i32_f64: i32 => f64;
+    promote_f32_f64, as_f32_f64: f32 => f64;
+}
+
+saturations! {
+    saturate_f32_u8, as_f32_u8: f32 => u8;
+    saturate_f32_i16, as_f32_i16: f32 => i16;
+    saturate_f32_i32, as_f32_i32: f32 => i32;
+    saturate_f32_u32, as_f32_u32: f32 => u32;
+    saturate_f64_i64, as_f64_i64: f64 => i64;
+    saturate_f64_usize, as_f64_usize: f64 => usize;
+}
+
+macro_rules! casts {
+    ($($name:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            #[no_mangle]
+            pub fn $name(x: $src) -> Result<$dst, Error> {
+                cast::$dst(x).map_err(Error::from)
+            }
+        )+
+    };
+}
+
+casts! {
+    cast_i16_i8: i16 => i8;
+    cast_i32_i8: i32 => i8;
+    cast_i32_i16: i32 => i16;
+    cast_i32_u8: i32 => u8;
+    cast_i32_u16: i32 => u16;
+    cast_i64_i32: i64 => i32;
+    cast_i64_u32: i64 => u32;
+    cast_i128_i64: i128 => i64;
+    cast_u32_u8: u32 => u8;
+    cast_u32_i8: u32 => i8;
+    cast_u64_u32: u64 => u32;
+    cast_u64_i32: u64 => i32;
+    cast_i32_u32: i32 => u32;
+    cast_f32_u8: f32 => u8;
+    cast_f32_i32: f32 => i32;
+    cast_f32_u64: f32 => u64;
+    cast_f64_i32: f64 => i32;
+    cast_f64_u32: f64 => u32;
+    cast_f64_i64: f64 => i64;
+    cast_f64_u64: f64 => u64;
+    cast_f32_u128: f32 => u128;
+}
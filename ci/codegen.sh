@@ -0,0 +1,145 @@
+#!/usr/bin/env bash
+
+# Checks the codegen of the casts of `ci/codegen.rs` on the target given as
+# the first argument, x86_64 by default; thumbv7em is also supported.
+#
+# Every promotion, `promote_*`, and every saturating cast from a float,
+# `saturate_*`, must compile to the same instructions as its `as_*` twin.
+#
+# The happy path of every checked cast, `cast_*`, must take at most one
+# conditional branch and, on x86_64, do at most one integer comparison. The
+# happy path of a cast from an integer is the whole function on x86_64, and
+# the path from the entry to the first return, falling through the branches,
+# on thumbv7em, where the code is mostly branchless. That of a cast from a
+# float is the path from the entry to the conversion (`cvtt*` or `vcvt*`, or a
+# call to the runtime); the comparisons of the saturating `as` that follow it
+# aren't counted. Prints the number of instructions of each function, and what
+# its happy path does.
+
+set -euo pipefail
+
+target=${1:-x86_64-unknown-linux-gnu}
+
+case $target in
+    x86_64-*) arch=x86; flags=(-C llvm-args=-x86-asm-syntax=intel) ;;
+    thumbv7*) arch=thumb; flags=() ;;
+    *) echo "error: unsupported target $target" >&2; exit 1 ;;
+esac
+
+cargo build --release --lib --target "$target"
+
+deps=target/$target/release
+asm=$deps/codegen.s
+rustc --edition 2018 --crate-type lib -C opt-level=3 --target "$target" \
+    "${flags[@]}" --emit asm -o "$asm" \
+    -L "dependency=$deps/deps" --extern "cast=$deps/libcast.rlib" ci/codegen.rs
+
+awk -v arch="$arch" '
+    function is_conversion(op) {
+        if (arch == "x86") { return op ~ /^cvtt/ || op == "call" }
+        return op ~ /^vcvt/ || op == "bl"
+    }
+    function is_return(op, line) {
+        if (arch == "x86") { return op == "ret" }
+        return op == "bx" || (op ~ /^pop/ && line ~ /pc}/)
+    }
+    function is_comparison(op) {
+        if (arch == "x86") { return op == "cmp" || op == "test" || op ~ /^u?comis/ }
+        return op ~ /^(cmp|cmn|tst|teq|vcmp)/
+    }
+    function is_jump(op) {
+        if (arch == "x86") { return op == "jmp" }
+        return op == "b" || op == "b.w"
+    }
+    function is_branch(op) {
+        if (arch == "x86") { return op ~ /^j/ && op != "jmp" }
+        return op ~ /^(b(eq|ne|hs|cs|lo|cc|mi|pl|vs|vc|hi|ls|ge|lt|gt|le)(\.w)?|cbn?z)$/
+    }
+
+    # the checks on the way from instruction `i` to the conversion, or to a
+    # return if `to_return`, or -1 if it is not reachable; `cmps` and `jccs`
+    # of the first such path, falling through the branches, are left in `pc`
+    # and `pj`
+    function walk(i, cmps, jccs,    op, target) {
+        for (; i <= n; i++) {
+            if (i in seen) { return -1 }
+            seen[i] = 1
+
+            op = ops[i]
+            if (is_conversion(op)) { pc = cmps; pj = jccs; return 0 }
+            if (is_return(op, lines[i])) {
+                if (to_return) { pc = cmps; pj = jccs; return 0 }
+                return -1
+            }
+            if (is_comparison(op)) { cmps++ }
+            if (is_jump(op)) { i = labels[args[i]] - 1; continue }
+            if (is_branch(op)) {
+                jccs++
+                target = labels[args[i]]
+                if (walk(i + 1, cmps, jccs) == 0) { return 0 }
+                delete seen
+                return walk(target, cmps, jccs)
+            }
+        }
+        return -1
+    }
+
+    /^(cast|promote|saturate|as)_[a-z0-9_]+:$/ {
+        name = substr($1, 1, length($1) - 1)
+        n = 0; cmps = 0; jccs = 0; body = ""
+        delete ops; delete args; delete lines; delete labels; delete seen
+        next
+    }
+    # identical functions are merged into aliases
+    /^(promote|saturate)_[a-z0-9_]+ = as_[a-z0-9_]+$/ { aliases[$1] = $3; twins[$1] = 1; next }
+    name == "" { next }
+    /^\.Lfunc_end/ {
+        if (name !~ /^cast_/) {
+            bodies[name] = body
+            sizes[name] = n
+            if (name !~ /^as_/) { twins[name] = 1 }
+        } else {
+            if (name ~ /^cast_f/ || arch == "thumb") {
+                pc = -1; pj = -1
+                to_return = name !~ /^cast_f/
+                walk(1, 0, 0)
+                cmps = pc; jccs = pj
+            }
+            printf "%-16s %3d instructions, happy path: %d comparisons, %d conditional branches\n", name, n, cmps, jccs
+            if (jccs < 0 || jccs > 1 || (arch == "x86" && cmps > 1)) {
+                failed = 1
+                print "error: " name " does more than one check"
+            }
+        }
+        name = ""
+        next
+    }
+    /^\.L[A-Za-z0-9_]+:/ { labels[substr($1, 1, length($1) - 1)] = n + 1; next }
+    $1 ~ /^[.#@]/ { next }
+    {
+        n++
+        ops[n] = $1
+        args[n] = $2
+        lines[n] = $0
+        if (is_comparison($1)) { cmps++ }
+        if (is_branch($1)) { jccs++ }
+
+        # local labels are numbered per function
+        line = $0
+        gsub(/\.L[A-Za-z0-9_]+/, ".L", line)
+        body = body line "\n"
+    }
+    END {
+        for (name in twins) {
+            twin = name
+            sub(/^[a-z]+_/, "as_", twin)
+            same = aliases[name] == twin || (name in bodies && bodies[name] == bodies[twin])
+            printf "%-18s %3d instructions, same as %s: %s\n", name, sizes[twin], twin, same ? "yes" : "no"
+            if (!same) {
+                failed = 1
+                print "error: " name " is not the same as " twin
+            }
+        }
+        exit failed
+    }
+' "$asm"
@@ -0,0 +1,165 @@
+//! Scaling of the codes of N-bit ADCs and DACs
+//!
+//! An N-bit converter maps the range `0..reference` onto the codes
+//! `0..2^N`: a reading of `code` stands for `code * reference / 2^N`, and the
+//! largest code, `2^N - 1`, for one step below `reference`. The functions of
+//! this module do that scaling, in floating point or in fixed point, and check
+//! that the codes are in range on the way in and on the way out.
+//!
+//! ```
+//! use cast::adc;
+//! use cast::Error;
+//!
+//! # fn main() {
+//! // a 12-bit ADC with a 3.3 V reference
+//! assert_eq!(adc::from_code::<12, _>(2048u16, 3.3), Ok(1.65));
+//! assert_eq!(adc::from_code_fixed::<12, _, u16>(2048u16, 3300), Ok(1650));
+//! // a 12-bit ADC doesn't produce this code
+//! assert_eq!(adc::from_code::<12, _>(4096u16, 3.3), Err(Error::Overflow));
+//!
+//! // a 10-bit DAC with the same reference
+//! assert_eq!(adc::to_code::<10, u16>(1.65, 3.3), Ok(512));
+//! assert_eq!(adc::to_code_fixed::<10, u16>(1650, 3300), Ok(512));
+//! assert_eq!(adc::to_code::<10, u16>(3.3, 3.3), Err(Error::Overflow));
+//! assert_eq!(adc::to_code::<10, u16>(-0.1, 3.3), Err(Error::NegOverflow));
+//! # }
+//! ```
+//!
+//! These are for unipolar converters; the codes of a bipolar one can be
+//! offset by `2^(N - 1)` first. The resolution, `N`, can be 1 to 32 bits.
+
+use crate::{fail, Error, From, IntoResult};
+
+/// Fails to compile unless `BITS` is a supported resolution
+const fn assert_bits<const BITS: u32>() {
+    assert!(
+        BITS >= 1 && BITS <= 32,
+        "the resolution must be between 1 and 32 bits"
+    );
+}
+
+/// The largest code of a `BITS`-bit converter
+#[inline]
+fn max<const BITS: u32>() -> u64 {
+    (1 << BITS) - 1
+}
+
+dst! {
+    u32,
+
+    /// Checked cast of `code`, a reading of a `BITS`-bit ADC, into a `u32`
+    #[inline]
+    fn code<const BITS: u32, Code>(code: Code) -> Result<u32, Error>
+    where
+        u32: From<Code>,
+        <u32 as From<Code>>::Output: IntoResult<u32>,
+    {
+        let code = u32::cast(code).into_result()?;
+
+        if u64::from(code) > max::<BITS>() {
+            Err(fail::<Code, u32, _>(Error::Overflow))
+        } else {
+            Ok(code)
+        }
+    }
+
+    /// Scales `code`, a reading of a `BITS`-bit ADC, into the range
+    /// `0..reference`
+    ///
+    /// This fails if `code` doesn't fit in `BITS` bits.
+    #[inline]
+    pub fn from_code<const BITS: u32, Code>(code: Code, reference: f32) -> Result<f32, Error>
+    where
+        u32: From<Code>,
+        <u32 as From<Code>>::Output: IntoResult<u32>,
+    {
+        const { assert_bits::<BITS>() };
+
+        let code = self::code::<BITS, Code>(code)?;
+
+        Ok(code as f32 * (reference / (1u64 << BITS) as f32))
+    }
+
+    /// Scales `code`, a reading of a `BITS`-bit ADC, into the range
+    /// `0..full_scale`, rounding to the nearest integer, and casts the result
+    /// into `Dst`
+    ///
+    /// With `full_scale` in some fixed-point unit, like millivolts, this is
+    /// `from_code` for targets without floating point.
+    #[inline]
+    pub fn from_code_fixed<const BITS: u32, Code, Dst>(
+        code: Code,
+        full_scale: u32,
+    ) -> Result<Dst, Error>
+    where
+        u32: From<Code>,
+        <u32 as From<Code>>::Output: IntoResult<u32>,
+        Dst: From<u64>,
+        <Dst as From<u64>>::Output: IntoResult<Dst>,
+    {
+        const { assert_bits::<BITS>() };
+
+        let code = self::code::<BITS, Code>(code)?;
+        let x = (u64::from(code) * u64::from(full_scale) + (1 << (BITS - 1))) >> BITS;
+
+        Dst::cast(x).into_result()
+    }
+}
+
+/// Scales `x`, in the range `0..reference`, into the nearest code of a
+/// `BITS`-bit DAC, and casts it into `Code`
+///
+/// This fails if `x` is not a number, or if it rounds to a code that a
+/// `BITS`-bit DAC doesn't accept: a negative one, or `2^BITS` or larger.
+#[inline]
+pub fn to_code<const BITS: u32, Code>(x: f32, reference: f32) -> Result<Code, Error>
+where
+    Code: From<u32>,
+    <Code as From<u32>>::Output: IntoResult<Code>,
+{
+    const { assert_bits::<BITS>() };
+
+    let scaled = x * ((1u64 << BITS) as f32 / reference);
+
+    let error = if scaled.is_nan() {
+        Error::NaN
+    } else if scaled.is_infinite() {
+        Error::Infinite
+    } else if scaled < -0.5 {
+        Error::NegOverflow
+    } else if scaled >= max::<BITS>() as f32 + 0.5 {
+        Error::Overflow
+    } else {
+        // `scaled` is not negative, so this rounds to the nearest code
+        return Code::cast((scaled + 0.5) as u32).into_result();
+    };
+
+    Err(fail::<f32, Code, _>(error))
+}
+
+/// Scales `x`, in the range `0..full_scale`, into the nearest code of a
+/// `BITS`-bit DAC, and casts it into `Code`
+///
+/// This is `to_code` in fixed point; it fails if `x` rounds to `2^BITS` or
+/// larger.
+///
+/// # Panics
+///
+/// If `full_scale` is zero
+#[inline]
+pub fn to_code_fixed<const BITS: u32, Code>(x: u32, full_scale: u32) -> Result<Code, Error>
+where
+    Code: From<u32>,
+    <Code as From<u32>>::Output: IntoResult<Code>,
+{
+    const { assert_bits::<BITS>() };
+
+    let full_scale = u64::from(full_scale);
+    let code = ((u64::from(x) << BITS) + full_scale / 2) / full_scale;
+
+    if code > max::<BITS>() {
+        Err(fail::<u32, Code, _>(Error::Overflow))
+    } else {
+        Code::cast(code as u32).into_result()
+    }
+}
@@ -0,0 +1,43 @@
+//! Element-wise casts between arrays
+
+use crate::outcome::{Outcome, Wrap};
+use crate::{Error, Fallibility, From, IntoResult};
+
+impl<Src, Dst, const N: usize> From<[Src; N]> for [Dst; N]
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: Outcome<Value = Dst>,
+    <<Dst as From<Src>>::Output as Outcome>::Fallibility: Wrap<[Dst; N]>,
+{
+    type Output = <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<[Dst; N]>>::Output;
+    type Error = Error;
+    const FALLIBLE: bool = <Dst as From<Src>>::FALLIBLE;
+    const FALLIBILITY: Fallibility = <Dst as From<Src>>::FALLIBILITY;
+
+    #[inline]
+    fn cast(src: [Src; N]) -> Self::Output {
+        // like with tuples, the elements after the first one that fails are
+        // neither casted nor reported
+        let mut error = None;
+        let dst = src.map(|x| match error {
+            Some(_) => None,
+            None => Dst::cast(x).into_result().map_err(|e| error = Some(e)).ok(),
+        });
+
+        let result = match error {
+            Some(e) => Err(e),
+            // every element was casted
+            None => Ok(dst.map(Option::unwrap)),
+        };
+
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<[Dst; N]>>::wrap(result)
+    }
+}
+
+// the output of the infallible casts
+impl<T, const N: usize> IntoResult<[T; N]> for [T; N] {
+    #[inline]
+    fn into_result(self) -> Result<[T; N], Error> {
+        Ok(self)
+    }
+}
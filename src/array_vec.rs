@@ -0,0 +1,42 @@
+//! Element-wise casts of `arrayvec::ArrayVec`s
+
+use arrayvec::ArrayVec;
+
+use crate::{ElementError, From, IntoResult};
+
+/// Checked cast of every element of an `ArrayVec`
+///
+/// The output has the same capacity, `CAP`, as the input.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+///
+/// # fn main() {
+/// let readings: ArrayVec<i16, 4> = [12, -3, 40].iter().copied().collect();
+///
+/// let e = cast::array_vec::<u8, _, 4>(readings).unwrap_err();
+/// assert_eq!((e.index(), e.value()), (1, -3));
+///
+/// let pixels: ArrayVec<f32, 8> = cast::array_vec([0u8, 255].iter().copied().collect()).unwrap();
+/// assert_eq!(&pixels[..], [0., 255.]);
+/// # }
+/// ```
+///
+/// *This API requires the `arrayvec` Cargo feature*
+#[inline]
+pub fn array_vec<Dst, Src, const CAP: usize>(
+    v: ArrayVec<Src, CAP>,
+) -> Result<ArrayVec<Dst, CAP>, ElementError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    v.into_iter()
+        .enumerate()
+        .map(|(index, x)| match Dst::cast(x).into_result() {
+            Ok(y) => Ok(y),
+            Err(error) => Err(ElementError::new(index, x, error)),
+        })
+        .collect()
+}
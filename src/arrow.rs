@@ -0,0 +1,85 @@
+//! Element-wise casts of Arrow primitive arrays
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, PrimitiveArray};
+
+use crate::{ElementError, From, IntoResult};
+
+mod sealed {
+    use arrow_array::ArrowPrimitiveType;
+
+    /// The Arrow types whose values are plain numbers
+    pub trait Numeric: ArrowPrimitiveType {}
+}
+
+use self::sealed::Numeric;
+
+impl Numeric for Float32Type {}
+impl Numeric for Float64Type {}
+impl Numeric for Int8Type {}
+impl Numeric for Int16Type {}
+impl Numeric for Int32Type {}
+impl Numeric for Int64Type {}
+impl Numeric for UInt8Type {}
+impl Numeric for UInt16Type {}
+impl Numeric for UInt32Type {}
+impl Numeric for UInt64Type {}
+
+/// Checked cast of every element of an Arrow primitive array
+///
+/// Null slots stay null, and the values behind them are not casted, so they
+/// can't fail. Only the numeric types are supported; e.g. dates, timestamps
+/// and decimals are not, because a cast of their raw representation doesn't
+/// preserve what it means.
+///
+/// ```
+/// use arrow_array::types::{Float64Type, Int32Type, UInt8Type};
+/// use arrow_array::{Array, Float64Array, Int32Array};
+///
+/// # fn main() {
+/// let column = Int32Array::from(vec![Some(1), None, Some(255)]);
+///
+/// let bytes = cast::arrow::<UInt8Type, Int32Type>(&column).unwrap();
+/// assert_eq!(bytes.values()[..], [1, 0, 255]);
+/// assert!(bytes.is_null(1));
+///
+/// let column = Float64Array::from(vec![Some(0.5), None, Some(-1.)]);
+/// let e = cast::arrow::<UInt8Type, Float64Type>(&column).unwrap_err();
+/// assert_eq!((e.index(), e.value()), (2, -1.));
+/// # }
+/// ```
+///
+/// *This API requires the `arrow` Cargo feature*
+pub fn arrow<Dst, Src>(
+    array: &PrimitiveArray<Src>,
+) -> Result<PrimitiveArray<Dst>, ElementError<Src::Native>>
+where
+    Src: Numeric,
+    Dst: Numeric,
+    Dst::Native: From<Src::Native>,
+    <Dst::Native as From<Src::Native>>::Output: IntoResult<Dst::Native>,
+{
+    let ys = array
+        .values()
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            if array.is_null(i) {
+                return Ok(Dst::Native::default());
+            }
+
+            Dst::Native::cast(x)
+                .into_result()
+                .map_err(|error| ElementError::new(i, x, error))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PrimitiveArray::new(ys.into(), array.nulls().cloned()))
+}
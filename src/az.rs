@@ -0,0 +1,119 @@
+//! Interoperability with the `az` crate
+//!
+//! The crate's own numeric newtypes implement `az`'s `Cast`, `CheckedCast`,
+//! `SaturatingCast` and `UnwrappedCast`, like the primitive they wrap, so
+//! they can be passed to code written against `az`.
+//!
+//! ```
+//! use az::SaturatingAs;
+//! use cast::Finite;
+//!
+//! # fn main() {
+//! let x = Finite::new(300.5f32).unwrap();
+//! assert_eq!(x.saturating_as::<u8>(), 255);
+//! # }
+//! ```
+//!
+//! Going the other way, `checked` casts anything that implements
+//! `az::CheckedCast`, e.g. the fixed-point numbers of the `fixed` crate, and
+//! reports why the cast failed with this crate's `Error`, so it can go through
+//! the same error handling as the rest of the crate's casts.
+//!
+//! ```
+//! use cast::Error;
+//!
+//! # fn main() {
+//! assert_eq!(cast::az::checked::<u8, _>(200i32), Ok(200));
+//! assert_eq!(cast::az::checked::<u8, _>(-1i32), Err(Error::NegOverflow));
+//! assert_eq!(cast::az::checked::<i16, _>(f32::INFINITY), Err(Error::Infinite));
+//! # }
+//! ```
+//!
+//! The coherence rules don't allow a blanket bridge between `az`'s traits and
+//! `cast::From` in either direction, for types defined elsewhere; for
+//! primitive newtypes, `Transparent` does that job.
+//!
+//! *This module requires the `az` Cargo feature*
+
+use az::{Cast, CheckedCast, SaturatingCast, UnwrappedCast};
+
+use crate::finite::Float;
+use crate::proof::Integer;
+use crate::{fail, Error, Finite, InRange, NonNegative, Signedness};
+
+/// Checked cast of anything that `az` can cast; see the module documentation
+///
+/// The error is found by casting `src` into `f64` when the cast into `Dst`
+/// fails: NaN and infinities are reported as such, negative values as
+/// `NegOverflow` and the rest as `Overflow`.
+#[inline]
+pub fn checked<Dst, Src>(src: Src) -> Result<Dst, Error>
+where
+    Src: Copy + CheckedCast<Dst> + CheckedCast<f64>,
+{
+    match CheckedCast::<Dst>::checked_cast(src) {
+        Some(dst) => Ok(dst),
+        None => {
+            let error = match CheckedCast::<f64>::checked_cast(src) {
+                Some(x) if x.is_nan() => Error::NaN,
+                Some(x) if x.is_infinite() => Error::Infinite,
+                Some(x) if x < 0. => Error::NegOverflow,
+                _ => Error::Overflow,
+            };
+
+            Err(fail::<Src, Dst, _>(error))
+        }
+    }
+}
+
+macro_rules! wrapper {
+    ($($ty:ty where $bound:path, [$($param:tt)*];)+) => {
+        $(
+            impl<T, Dst, $($param)*> Cast<Dst> for $ty
+            where
+                T: $bound + Cast<Dst>,
+            {
+                #[inline]
+                fn cast(self) -> Dst {
+                    self.get().cast()
+                }
+            }
+
+            impl<T, Dst, $($param)*> CheckedCast<Dst> for $ty
+            where
+                T: $bound + CheckedCast<Dst>,
+            {
+                #[inline]
+                fn checked_cast(self) -> Option<Dst> {
+                    self.get().checked_cast()
+                }
+            }
+
+            impl<T, Dst, $($param)*> SaturatingCast<Dst> for $ty
+            where
+                T: $bound + SaturatingCast<Dst>,
+            {
+                #[inline]
+                fn saturating_cast(self) -> Dst {
+                    self.get().saturating_cast()
+                }
+            }
+
+            impl<T, Dst, $($param)*> UnwrappedCast<Dst> for $ty
+            where
+                T: $bound + UnwrappedCast<Dst>,
+            {
+                #[inline]
+                fn unwrapped_cast(self) -> Dst {
+                    self.get().unwrapped_cast()
+                }
+            }
+        )+
+    }
+}
+
+wrapper! {
+    Finite<T> where Float, [];
+    NonNegative<T> where Signedness, [];
+    InRange<T, MIN, MAX> where Integer, [const MIN: i128, const MAX: i128];
+}
@@ -0,0 +1,89 @@
+//! Wrappers of the casts for benchmarks
+//!
+//! Benchmarking a cast of a constant measures nothing: the compiler folds the
+//! check away. Every function of this module hides its input and its output
+//! from the optimizer with `core::hint::black_box`, and is never inlined, so
+//! each call does one complete cast, and so that the wrappers of this crate's
+//! casts and of the alternatives to them are measured the same way. The
+//! benchmarks of this crate, in `benches/`, are built on them.
+//!
+//! ```
+//! use cast::bench_support;
+//! use cast::Error;
+//!
+//! # fn main() {
+//! assert_eq!(bench_support::checked::<u8, _>(256u32), Err(Error::Overflow));
+//! assert_eq!(bench_support::saturating::<u8, _>(256u32), 255);
+//! assert!(bench_support::try_from::<u8, _>(256u32).is_err());
+//!
+//! let mut dst = [0; 3];
+//! assert_eq!(bench_support::checked_slice(&[1u32, 2, 3], &mut dst), Ok(()));
+//! # }
+//! ```
+//!
+//! With `cargo asm`, or in a disassembler, these are also the functions to
+//! look at to see what the checks of a cast compile to.
+
+use core::convert::TryFrom;
+use core::hint::black_box;
+
+use crate::{Compare, Error, From, IntoResult, Primitive};
+
+/// The checked cast, `Dst::cast`, of `x`
+#[inline(never)]
+pub fn checked<Dst, Src>(x: Src) -> Result<Dst, Error>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    black_box(Dst::cast(black_box(x)).into_result())
+}
+
+/// The saturating cast, `cast::saturating`, of `x`
+#[inline(never)]
+pub fn saturating<Dst, Src>(x: Src) -> Dst
+where
+    Src: Compare,
+    Dst: From<Src> + Primitive + Default,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    black_box(crate::saturating(black_box(x)))
+}
+
+/// The standard library's checked conversion, `Dst::try_from`, of `x`
+///
+/// This is only implemented between integers, so it's the baseline for the
+/// checked casts between them.
+#[inline(never)]
+pub fn try_from<Dst, Src>(x: Src) -> Result<Dst, Dst::Error>
+where
+    Dst: TryFrom<Src>,
+{
+    black_box(Dst::try_from(black_box(x)))
+}
+
+/// The checked cast of every element of `src` into the element of `dst` at
+/// the same position
+///
+/// This stops at the first element that fails to be casted. It measures the
+/// throughput of the casts, which is what matters when converting buffers.
+///
+/// # Panics
+///
+/// If the slices have different lengths
+#[inline(never)]
+pub fn checked_slice<Dst, Src>(src: &[Src], dst: &mut [Dst]) -> Result<(), Error>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    assert_eq!(src.len(), dst.len());
+
+    for (y, &x) in dst.iter_mut().zip(black_box(src)) {
+        *y = Dst::cast(x).into_result()?;
+    }
+
+    black_box(dst);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Casts between `js_sys::BigInt` and the 64- and 128-bit integers
+
+use js_sys::BigInt;
+
+use crate::{Error, From};
+
+macro_rules! into_bigint {
+    ($($src:ident),+) => {
+        $(
+            /// A `BigInt` can hold any integer
+            ///
+            /// *This impl requires the `wasm-bindgen` Cargo feature*
+            impl From<$src> for BigInt {
+                type Output = BigInt;
+                type Error = Error;
+                const FALLIBLE: bool = false;
+
+                #[inline]
+                fn cast(src: $src) -> BigInt {
+                    BigInt::from(src)
+                }
+            }
+        )+
+    }
+}
+
+into_bigint!(i64, u64, i128, u128);
+
+macro_rules! from_bigint {
+    ($($dst:ident),+) => {
+        $(
+            dst! {
+                $dst,
+
+                /// Values outside the range of the integer are reported as
+                /// `Error::Overflow` or `Error::NegOverflow`
+                ///
+                /// *This impl requires the `wasm-bindgen` Cargo feature*
+                impl From<BigInt> for $dst {
+                    type Output = Result<$dst, Error>;
+                    type Error = Error;
+                    const FALLIBLE: bool = true;
+
+                    // `BigInt` can only be compared with other `BigInt`s
+                    #[allow(clippy::cmp_owned)]
+                    #[inline]
+                    fn cast(src: BigInt) -> Result<$dst, Error> {
+                        match <$dst as core::convert::TryFrom<BigInt>>::try_from(src) {
+                            Ok(x) => Ok(x),
+                            Err(src) if src < BigInt::from(0) => {
+                                Err(crate::fail::<BigInt, $dst, _>(Error::NegOverflow))
+                            }
+                            Err(_) => Err(crate::fail::<BigInt, $dst, _>(Error::Overflow)),
+                        }
+                    }
+                }
+            }
+        )+
+    }
+}
+
+from_bigint!(i64, u64, i128, u128);
@@ -0,0 +1,130 @@
+//! Checked casts into the types `bindgen` generates for C enums
+
+use core::ops::{BitAnd, Not};
+
+use crate::{fail, EnumError, Error, FlagsError};
+
+/// Implements `From` for the newtypes `bindgen` generates for C enums
+///
+/// With `newtype_enum` (or `bitfield_enum`), `bindgen` turns a C enum into a
+/// tuple struct around its integer type, plus one associated constant per
+/// enumerator. Nothing stops `foo_mode(42)` from being constructed, so a raw
+/// value that comes from C, a register or the wire has to be validated before
+/// it is trusted to be one of the enumerators. This macro implements the casts
+/// that do that, from every type the integer can be casted from.
+///
+/// For `enum` newtypes, the value has to be one of the listed constants. For
+/// `flags` newtypes, the value can be any combination of them. Like the other
+/// casts into enums and flags, they fail with `EnumError` and `FlagsError`.
+///
+/// ```
+/// use cast::{EnumError, Error, FlagsError, From as _0};
+///
+/// // generated by `bindgen`
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// pub struct foo_mode(pub u32);
+/// impl foo_mode {
+///     pub const FOO_OFF: foo_mode = foo_mode(0);
+///     pub const FOO_ON: foo_mode = foo_mode(1);
+///     pub const FOO_AUTO: foo_mode = foo_mode(4);
+/// }
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// pub struct foo_flags(pub u8);
+/// impl foo_flags {
+///     pub const FOO_READ: foo_flags = foo_flags(1);
+///     pub const FOO_WRITE: foo_flags = foo_flags(2);
+/// }
+///
+/// cast::bindgen! {
+///     enum foo_mode: u32 { FOO_OFF, FOO_ON, FOO_AUTO }
+///     flags foo_flags: u8 { FOO_READ, FOO_WRITE }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(foo_mode::cast(4i32), Ok(foo_mode::FOO_AUTO));
+/// assert_eq!(foo_mode::cast(2i32), Err(EnumError::UnknownDiscriminant(2)));
+/// assert_eq!(foo_mode::cast(-1i32), Err(EnumError::Cast(Error::NegOverflow)));
+///
+/// assert_eq!(foo_flags::cast(3u64), Ok(foo_flags(3)));
+/// assert_eq!(foo_flags::cast(6u64), Err(FlagsError::UnknownBits(4)));
+///
+/// // the other way around, cast the integer
+/// assert_eq!(cast::u8(foo_mode::FOO_ON.0), Ok(1));
+/// # }
+/// ```
+///
+/// The constants of the "constified" and "rustified" enum styles are plain
+/// integers, or Rust enums, so they don't need this macro; `NumEnum` covers
+/// the latter.
+#[macro_export]
+macro_rules! bindgen {
+    ($($kind:ident $ty:ident: $inner:ty { $($constant:ident),+ $(,)? })*) => {
+        $(
+            impl<Src> $crate::From<Src> for $ty
+            where
+                $inner: $crate::From<Src>,
+                <$inner as $crate::From<Src>>::Output: $crate::IntoResult<$inner>,
+            {
+                type Output = ::core::result::Result<$ty, $crate::bindgen!(@error $kind $inner)>;
+                type Error = $crate::bindgen!(@error $kind $inner);
+                const FALLIBLE: bool = true;
+
+                #[inline]
+                fn cast(src: Src) -> Self::Output {
+                    let inner = $crate::IntoResult::into_result(
+                        <$inner as $crate::From<Src>>::cast(src),
+                    );
+
+                    $crate::bindgen!(@check $kind Src, $ty, inner, $($ty::$constant.0),+).map($ty)
+                }
+            }
+        )*
+    };
+    (@error enum $inner:ty) => { $crate::EnumError<$inner> };
+    (@error flags $inner:ty) => { $crate::FlagsError<$inner> };
+    (@check enum $src:ident, $ty:ident, $inner:ident, $($value:expr),+) => {
+        $crate::bindgen::enumerator::<$src, $ty, _>($inner, &[$($value),+])
+    };
+    (@check flags $src:ident, $ty:ident, $inner:ident, $($value:expr),+) => {
+        $crate::bindgen::flags::<$src, $ty, _>($inner, $($value)|+)
+    };
+}
+
+/// Checks that `inner`, the integer of the `bindgen` enum `Dst`, is one of
+/// its `enumerators`
+#[doc(hidden)]
+#[inline]
+pub fn enumerator<Src, Dst, T>(
+    inner: Result<T, Error>,
+    enumerators: &[T],
+) -> Result<T, EnumError<T>>
+where
+    T: Copy + PartialEq,
+{
+    let inner = inner.map_err(EnumError::Cast)?;
+
+    if enumerators.contains(&inner) {
+        Ok(inner)
+    } else {
+        Err(fail::<Src, Dst, _>(EnumError::UnknownDiscriminant(inner)))
+    }
+}
+
+/// Checks that `inner`, the integer of the `bindgen` flags `Dst`, only has the
+/// bits of `all` set
+#[doc(hidden)]
+#[inline]
+pub fn flags<Src, Dst, T>(inner: Result<T, Error>, all: T) -> Result<T, FlagsError<T>>
+where
+    T: BitAnd<Output = T> + Copy + Default + Not<Output = T> + PartialEq,
+{
+    let inner = inner.map_err(FlagsError::Cast)?;
+
+    let unknown = inner & !all;
+    if unknown == T::default() {
+        Ok(inner)
+    } else {
+        Err(fail::<Src, Dst, _>(FlagsError::UnknownBits(unknown)))
+    }
+}
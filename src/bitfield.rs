@@ -0,0 +1,101 @@
+//! Checked casts between the primitives and `modular-bitfield` types
+//!
+//! `unpack` casts a primitive, e.g. a register value read as a `u64`, into
+//! anything that implements `modular_bitfield::Specifier`: a `#[bitfield]`
+//! struct that derives `BitfieldSpecifier`, an enum that does, or one of the
+//! `B1`..`B128` field types. `pack` goes the other way. Both fail with this
+//! crate's errors, so decoding a register fails the same way as any other cast
+//! does:
+//!
+//! - a value that doesn't fit in the bits of the specifier, or in its
+//!   underlying bytes type, is `Error::Overflow` or `Error::NegOverflow`,
+//!   wrapped in `EnumError::Cast` by `unpack`
+//! - a value that fits in the bits of an enum but is not the discriminant of
+//!   any of its variants is `EnumError::UnknownDiscriminant`, like in the
+//!   other casts into enums
+//!
+//! ```
+//! use cast::bitfield;
+//! use cast::{EnumError, Error};
+//! use modular_bitfield::prelude::*;
+//!
+//! #[derive(BitfieldSpecifier, Clone, Copy, Debug, PartialEq)]
+//! #[bits = 2]
+//! enum Mode {
+//!     Off,
+//!     Low,
+//!     High,
+//! }
+//!
+//! #[bitfield]
+//! #[derive(BitfieldSpecifier, Clone, Copy, Debug, PartialEq)]
+//! struct Control {
+//!     enabled: bool,
+//!     mode: Mode,
+//!     divider: B5,
+//! }
+//!
+//! # fn main() {
+//! let control = bitfield::unpack::<Control, _>(0x8bu32).unwrap();
+//! assert_eq!((control.enabled(), control.mode(), control.divider()), (true, Mode::Low, 17));
+//! assert_eq!(bitfield::unpack::<Control, _>(0x100u32), Err(EnumError::Cast(Error::Overflow)));
+//!
+//! // not a `Mode`
+//! assert_eq!(bitfield::unpack::<Mode, _>(3u8), Err(EnumError::UnknownDiscriminant(3)));
+//!
+//! // 5 bits
+//! assert_eq!(bitfield::unpack::<B5, _>(31i64), Ok(31));
+//! assert_eq!(bitfield::unpack::<B5, _>(32i64), Err(EnumError::Cast(Error::Overflow)));
+//! assert_eq!(bitfield::pack::<u16, B5>(32), Err(Error::Overflow));
+//!
+//! assert_eq!(bitfield::pack::<u64, Control>(control), Ok(0x8b));
+//! # }
+//! ```
+//!
+//! The structs generated by `bitfield-struct` don't need this module: every
+//! bit pattern is valid for them, and they implement `core::convert::From` for
+//! their bits type, so `Register::from(cast::u32(x)?)` is already checked.
+//!
+//! *This module requires the `modular-bitfield` Cargo feature*
+
+use modular_bitfield::Specifier;
+
+use crate::{fail, EnumError, Error, From, IntoResult};
+
+/// Checked cast of `x` into the bytes type of `S`, and then into `S`'s
+/// in-out type; see the module documentation
+#[inline]
+pub fn unpack<S, Src>(x: Src) -> Result<S::InOut, EnumError<S::Bytes>>
+where
+    S: Specifier,
+    S::Bytes: From<Src> + Copy + Into<u128>,
+    <S::Bytes as From<Src>>::Output: IntoResult<S::Bytes>,
+{
+    let bytes = <S::Bytes as From<Src>>::cast(x)
+        .into_result()
+        .map_err(EnumError::Cast)?;
+
+    S::from_bytes(bytes).map_err(|_| {
+        // the bit patterns that fit in `S::BITS` can only be invalid for enums
+        let fits = S::BITS >= 128 || Into::<u128>::into(bytes) >> S::BITS == 0;
+        fail::<Src, S, _>(if fits {
+            EnumError::UnknownDiscriminant(bytes)
+        } else {
+            EnumError::Cast(Error::Overflow)
+        })
+    })
+}
+
+/// Checked cast of `x`, an in-out value of `S`, into `Dst`; see the module
+/// documentation
+#[inline]
+pub fn pack<Dst, S>(x: S::InOut) -> Result<Dst, Error>
+where
+    S: Specifier,
+    Dst: From<S::Bytes>,
+    <Dst as From<S::Bytes>>::Output: IntoResult<Dst>,
+{
+    let bytes = S::into_bytes(x).map_err(|_| fail::<S, Dst, _>(Error::Overflow))?;
+
+    <Dst as From<S::Bytes>>::cast(bytes).into_result()
+}
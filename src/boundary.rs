@@ -0,0 +1,253 @@
+//! Values that are likely to trip up numeric casts, for property tests and
+//! fuzzing
+
+/// Numeric primitives with a set of values that sit on, or next to, the
+/// boundaries that casts check
+///
+/// For integers these are the limits of every integer type (`i8::MAX`,
+/// `i8::MAX + 1`, etc.) and the powers of two above which floats stop being
+/// able to represent every integer. For floats these also include signed zeros,
+/// subnormals, the neighbors of `2^53` (`2^24` for `f32`), the infinities and
+/// NaN.
+///
+/// ```
+/// use cast::BoundaryValues;
+///
+/// # fn main() {
+/// assert!(u8::boundary_values().any(|x| x == u8::MAX));
+/// assert!(f64::boundary_values().any(|x| x == 9_007_199_254_740_992.));
+/// # }
+/// ```
+pub trait BoundaryValues: Copy {
+    /// An iterator over the boundary values
+    type Iter: ExactSizeIterator<Item = Self>;
+
+    /// Returns the boundary values of this type
+    ///
+    /// The values are not deduplicated.
+    fn boundary_values() -> Self::Iter;
+}
+
+macro_rules! int {
+    ($($ty:ident),+) => {
+        $(
+            impl BoundaryValues for $ty {
+                type Iter = core::array::IntoIter<$ty, 59>;
+
+                #[allow(clippy::unnecessary_cast)]
+                fn boundary_values() -> Self::Iter {
+                    IntoIterator::into_iter([
+                        $ty::MIN,
+                        $ty::MIN.wrapping_add(1),
+                        (0 as $ty).wrapping_sub(1),
+                        0,
+                        1,
+                        $ty::MAX - 1,
+                        $ty::MAX,
+                        // integers above these can't all be represented by `f32`
+                        // and `f64`, respectively
+                        (1u64 << 24) as $ty,
+                        ((1u64 << 24) + 1) as $ty,
+                        (1u64 << 53) as $ty,
+                        ((1u64 << 53) + 1) as $ty,
+                        i8::MIN as $ty,
+                        (i8::MIN as $ty).wrapping_sub(1),
+                        i8::MAX as $ty,
+                        (i8::MAX as $ty).wrapping_add(1),
+                        i16::MIN as $ty,
+                        (i16::MIN as $ty).wrapping_sub(1),
+                        i16::MAX as $ty,
+                        (i16::MAX as $ty).wrapping_add(1),
+                        i32::MIN as $ty,
+                        (i32::MIN as $ty).wrapping_sub(1),
+                        i32::MAX as $ty,
+                        (i32::MAX as $ty).wrapping_add(1),
+                        i64::MIN as $ty,
+                        (i64::MIN as $ty).wrapping_sub(1),
+                        i64::MAX as $ty,
+                        (i64::MAX as $ty).wrapping_add(1),
+                        i128::MIN as $ty,
+                        (i128::MIN as $ty).wrapping_sub(1),
+                        i128::MAX as $ty,
+                        (i128::MAX as $ty).wrapping_add(1),
+                        isize::MIN as $ty,
+                        (isize::MIN as $ty).wrapping_sub(1),
+                        isize::MAX as $ty,
+                        (isize::MAX as $ty).wrapping_add(1),
+                        u8::MIN as $ty,
+                        (u8::MIN as $ty).wrapping_sub(1),
+                        u8::MAX as $ty,
+                        (u8::MAX as $ty).wrapping_add(1),
+                        u16::MIN as $ty,
+                        (u16::MIN as $ty).wrapping_sub(1),
+                        u16::MAX as $ty,
+                        (u16::MAX as $ty).wrapping_add(1),
+                        u32::MIN as $ty,
+                        (u32::MIN as $ty).wrapping_sub(1),
+                        u32::MAX as $ty,
+                        (u32::MAX as $ty).wrapping_add(1),
+                        u64::MIN as $ty,
+                        (u64::MIN as $ty).wrapping_sub(1),
+                        u64::MAX as $ty,
+                        (u64::MAX as $ty).wrapping_add(1),
+                        u128::MIN as $ty,
+                        (u128::MIN as $ty).wrapping_sub(1),
+                        u128::MAX as $ty,
+                        (u128::MAX as $ty).wrapping_add(1),
+                        usize::MIN as $ty,
+                        (usize::MIN as $ty).wrapping_sub(1),
+                        usize::MAX as $ty,
+                        (usize::MAX as $ty).wrapping_add(1),
+                    ])
+                }
+            }
+        )+
+    }
+}
+
+int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float {
+    ($($ty:ident),+) => {
+        $(
+            impl BoundaryValues for $ty {
+                type Iter = core::array::IntoIter<$ty, 93>;
+
+                fn boundary_values() -> Self::Iter {
+                    // the next value away from zero
+                    fn away(x: $ty) -> $ty {
+                        $ty::from_bits(x.to_bits() + 1)
+                    }
+
+                    // the next value towards zero
+                    fn toward(x: $ty) -> $ty {
+                        $ty::from_bits(x.to_bits() - 1)
+                    }
+
+                    // above this not every integer can be represented
+                    let exact = (1u64 << $ty::MANTISSA_DIGITS) as $ty;
+                    let subnormal = $ty::from_bits(1);
+
+                    IntoIterator::into_iter([
+                        0.,
+                        -0.,
+                        subnormal,
+                        -subnormal,
+                        $ty::MIN_POSITIVE,
+                        -$ty::MIN_POSITIVE,
+                        0.5,
+                        -0.5,
+                        1.,
+                        -1.,
+                        toward(exact),
+                        exact,
+                        away(exact),
+                        -toward(exact),
+                        -exact,
+                        -away(exact),
+                        $ty::MIN,
+                        $ty::MAX,
+                        $ty::INFINITY,
+                        $ty::NEG_INFINITY,
+                        $ty::NAN,
+                        u8::MAX as $ty,
+                        u8::MAX as $ty + 1.,
+                        toward(u8::MAX as $ty),
+                        away(u8::MAX as $ty),
+                        u16::MAX as $ty,
+                        u16::MAX as $ty + 1.,
+                        toward(u16::MAX as $ty),
+                        away(u16::MAX as $ty),
+                        u32::MAX as $ty,
+                        u32::MAX as $ty + 1.,
+                        toward(u32::MAX as $ty),
+                        away(u32::MAX as $ty),
+                        u64::MAX as $ty,
+                        u64::MAX as $ty + 1.,
+                        toward(u64::MAX as $ty),
+                        away(u64::MAX as $ty),
+                        u128::MAX as $ty,
+                        u128::MAX as $ty + 1.,
+                        toward(u128::MAX as $ty),
+                        away(u128::MAX as $ty),
+                        usize::MAX as $ty,
+                        usize::MAX as $ty + 1.,
+                        toward(usize::MAX as $ty),
+                        away(usize::MAX as $ty),
+                        i8::MAX as $ty,
+                        i8::MAX as $ty + 1.,
+                        toward(i8::MAX as $ty),
+                        away(i8::MAX as $ty),
+                        i8::MIN as $ty,
+                        i8::MIN as $ty - 1.,
+                        toward(i8::MIN as $ty),
+                        away(i8::MIN as $ty),
+                        i16::MAX as $ty,
+                        i16::MAX as $ty + 1.,
+                        toward(i16::MAX as $ty),
+                        away(i16::MAX as $ty),
+                        i16::MIN as $ty,
+                        i16::MIN as $ty - 1.,
+                        toward(i16::MIN as $ty),
+                        away(i16::MIN as $ty),
+                        i32::MAX as $ty,
+                        i32::MAX as $ty + 1.,
+                        toward(i32::MAX as $ty),
+                        away(i32::MAX as $ty),
+                        i32::MIN as $ty,
+                        i32::MIN as $ty - 1.,
+                        toward(i32::MIN as $ty),
+                        away(i32::MIN as $ty),
+                        i64::MAX as $ty,
+                        i64::MAX as $ty + 1.,
+                        toward(i64::MAX as $ty),
+                        away(i64::MAX as $ty),
+                        i64::MIN as $ty,
+                        i64::MIN as $ty - 1.,
+                        toward(i64::MIN as $ty),
+                        away(i64::MIN as $ty),
+                        i128::MAX as $ty,
+                        i128::MAX as $ty + 1.,
+                        toward(i128::MAX as $ty),
+                        away(i128::MAX as $ty),
+                        i128::MIN as $ty,
+                        i128::MIN as $ty - 1.,
+                        toward(i128::MIN as $ty),
+                        away(i128::MIN as $ty),
+                        isize::MAX as $ty,
+                        isize::MAX as $ty + 1.,
+                        toward(isize::MAX as $ty),
+                        away(isize::MAX as $ty),
+                        isize::MIN as $ty,
+                        isize::MIN as $ty - 1.,
+                        toward(isize::MIN as $ty),
+                        away(isize::MIN as $ty),
+                    ])
+                }
+            }
+        )+
+    }
+}
+
+float!(f32, f64);
+
+/// Generates a value of `T` that is a boundary value half of the time, and any
+/// value the other half
+///
+/// Purely random input rarely hits the values where casts change behavior; use
+/// this in `Arbitrary` implementations and fuzz targets to hit them often.
+///
+/// *This API requires the `arbitrary` Cargo feature*
+#[cfg(feature = "arbitrary")]
+pub fn arbitrary_boundary<T>(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<T>
+where
+    T: BoundaryValues + for<'a> arbitrary::Arbitrary<'a>,
+{
+    if u.arbitrary()? {
+        let mut values = T::boundary_values();
+        let i = u.choose_index(values.len())?;
+        Ok(values.nth(i).unwrap())
+    } else {
+        T::arbitrary(u)
+    }
+}
@@ -0,0 +1,76 @@
+//! Shorthands for `From` bounds
+
+use crate::From;
+
+/// `Self` can be casted into `Dst` and the cast never fails
+///
+/// `T: Infallibly<u32>` is equivalent to `u32: From<T, Output = u32>`, but
+/// shorter, and reads in the direction of the cast.
+///
+/// ```
+/// use cast::Infallibly;
+///
+/// fn checksum<T: Infallibly<u32> + Copy>(xs: &[T]) -> u32 {
+///     xs.iter().fold(0u32, |acc, &x| acc.wrapping_add(x.cast_into()))
+/// }
+///
+/// # fn main() {
+/// assert_eq!(checksum(&[1u8, 2, 3]), 6);
+/// assert_eq!(checksum(&[1u16, 2, 3]), 6);
+/// // checksum(&[1i8]); // Compile error: `i8` → `u32` can fail
+/// # }
+/// ```
+pub trait Infallibly<Dst>: Sized {
+    /// Casts `self` into `Dst`
+    fn cast_into(self) -> Dst;
+}
+
+impl<Src, Dst> Infallibly<Dst> for Src
+where
+    Dst: From<Src, Output = Dst>,
+{
+    #[inline]
+    fn cast_into(self) -> Dst {
+        Dst::cast(self)
+    }
+}
+
+/// `Self` can be casted into `Dst` but the cast can fail
+///
+/// `T: Fallibly<u8>` is equivalent to
+/// `u8: From<T, Output = Result<u8, <u8 as From<T>>::Error>>`.
+///
+/// ```
+/// use cast::Fallibly;
+///
+/// fn to_bytes<T>(xs: &[T]) -> Result<Vec<u8>, T::Error>
+/// where
+///     T: Fallibly<u8> + Copy,
+/// {
+///     xs.iter().map(|&x| x.try_cast_into()).collect()
+/// }
+///
+/// # fn main() {
+/// assert_eq!(to_bytes(&[1u16, 2, 3]), Ok(vec![1, 2, 3]));
+/// assert!(to_bytes(&[1i32, 256]).is_err());
+/// # }
+/// ```
+pub trait Fallibly<Dst>: Sized {
+    /// The error reported when the cast fails
+    type Error;
+
+    /// Casts `self` into `Dst`
+    fn try_cast_into(self) -> Result<Dst, Self::Error>;
+}
+
+impl<Src, Dst> Fallibly<Dst> for Src
+where
+    Dst: From<Src, Output = Result<Dst, <Dst as From<Src>>::Error>>,
+{
+    type Error = <Dst as From<Src>>::Error;
+
+    #[inline]
+    fn try_cast_into(self) -> Result<Dst, Self::Error> {
+        Dst::cast(self)
+    }
+}
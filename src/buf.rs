@@ -0,0 +1,98 @@
+//! Checked length prefixes for `bytes` buffers
+//!
+//! A length prefix is usually narrower than `usize`, and `buf.put_u16(len as
+//! u16)` silently writes the wrong length when the payload is too large; the
+//! reader then misparses everything after it. `BufMutExt` writes the prefix
+//! only if the length fits, and `BufExt` reads it back into a `usize`, which
+//! can fail too on targets whose `usize` is narrower than the prefix.
+//!
+//! ```
+//! use bytes::BufMut;
+//! use cast::buf::{BufExt, BufMutExt};
+//! use cast::Error;
+//!
+//! # fn main() {
+//! let payload = [0xaa; 300];
+//! let mut frame = Vec::new();
+//!
+//! assert_eq!(frame.put_len_u8(payload.len()), Err(Error::Overflow));
+//! assert!(frame.is_empty());
+//!
+//! frame.put_len_u16(payload.len()).unwrap();
+//! frame.put_slice(&payload);
+//!
+//! let mut frame = &frame[..];
+//! assert_eq!(frame.get_len_u16(), Ok(300));
+//! assert_eq!(frame.len(), 300);
+//! # }
+//! ```
+//!
+//! *This module requires the `bytes` Cargo feature*
+
+use bytes::{Buf, BufMut};
+
+macro_rules! put {
+    ($($ty:ident: $($put:ident => $method:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $ty,
+
+                    #[doc = concat!("Writes `len` as a `", stringify!($ty), "` with `BufMut::", stringify!($method), "`")]
+                    ///
+                    /// Nothing is written if `len` doesn't fit.
+                    #[inline]
+                    fn $put(&mut self, len: usize) -> Result<(), crate::Error> {
+                        self.$method(crate::auto::<$ty, _>(len)?);
+                        Ok(())
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+/// Checked writes of length prefixes into a `bytes::BufMut`
+pub trait BufMutExt: BufMut {
+    put! {
+        u8: put_len_u8 => put_u8;
+        u16: put_len_u16 => put_u16, put_len_u16_le => put_u16_le;
+        u32: put_len_u32 => put_u32, put_len_u32_le => put_u32_le;
+        u64: put_len_u64 => put_u64, put_len_u64_le => put_u64_le;
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutExt for B {}
+
+macro_rules! get {
+    ($($ty:ident: $($get:ident => $method:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    usize,
+
+                    #[doc = concat!("Reads a `", stringify!($ty), "` with `Buf::", stringify!($method), "` and casts it into a `usize`")]
+                    ///
+                    #[doc = concat!("Like `Buf::", stringify!($method), "`, this panics if there are fewer than ")]
+                    #[doc = concat!("`size_of::<", stringify!($ty), ">()` bytes remaining.")]
+                    #[inline]
+                    fn $get(&mut self) -> Result<usize, crate::Error> {
+                        crate::auto(self.$method())
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+/// Checked reads of length prefixes from a `bytes::Buf`
+pub trait BufExt: Buf {
+    get! {
+        u8: get_len_u8 => get_u8;
+        u16: get_len_u16 => get_u16, get_len_u16_le => get_u16_le;
+        u32: get_len_u32 => get_u32, get_len_u32_le => get_u32_le;
+        u64: get_len_u64 => get_u64, get_len_u64_le => get_u64_le;
+    }
+}
+
+impl<B: Buf + ?Sized> BufExt for B {}
@@ -0,0 +1,259 @@
+//! Conversions between numbers and their byte representation
+//!
+//! The functions that read a number are named after the type and the byte
+//! order, e.g. `u32_le` or `i64_be`. They accept either an array of the exact
+//! size, in which case they can't fail, or a byte slice, in which case they
+//! return an error if the length of the slice isn't the size of the type.
+//!
+//! ```
+//! use cast::bytes::{self, LengthError};
+//!
+//! # fn main() {
+//! assert_eq!(bytes::u32_le([1, 0, 0, 0]), 1);
+//!
+//! let packet = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0x2a];
+//! assert_eq!(bytes::i64_be(&packet[..8]), Ok(-2));
+//! assert_eq!(
+//!     bytes::i64_be(&packet[..]),
+//!     Err(LengthError { expected: 8, found: 9 })
+//! );
+//! # }
+//! ```
+//!
+//! `le`, `be` and `ne` go the other way, for any of these types and for `u8`
+//! and `i8`.
+//!
+//! ```
+//! # fn main() {
+//! assert_eq!(cast::bytes::le(1u32), [1, 0, 0, 0]);
+//! assert_eq!(cast::bytes::be(-2i16), [0xff, 0xfe]);
+//! # }
+//! ```
+//!
+//! The `io` module, behind the `std` Cargo feature, builds on these to read
+//! and write streams of samples.
+//!
+//! `usize` and `isize` are not supported because their size depends on the
+//! target, which makes them unsuitable for portable binary formats.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The error returned when a byte slice doesn't have the size of the type it's
+/// being read as
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LengthError {
+    /// The size of the type, in bytes
+    pub expected: usize,
+    /// The length of the slice
+    pub found: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected {} bytes, found {}", self.expected, self.found)
+    }
+}
+
+impl core::error::Error for LengthError {}
+
+/// The order of the bytes of a number
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ByteOrder {
+    /// Least significant byte first
+    LittleEndian,
+    /// Most significant byte first
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// The byte order of the target
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: ByteOrder = ByteOrder::LittleEndian;
+    /// The byte order of the target
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: ByteOrder = ByteOrder::BigEndian;
+}
+
+pub(crate) mod sealed {
+    use super::ByteOrder;
+
+    /// The inputs of the functions that read a number of `N` bytes
+    // unused when all the destination types are disabled
+    #[allow(dead_code)]
+    pub trait Input<const N: usize> {
+        /// `T`, or `Result<T, LengthError>` when the length has to be checked
+        type Output<T>;
+
+        fn read<T>(self, f: fn([u8; N]) -> T) -> Self::Output<T>;
+    }
+
+    /// The numbers that can be turned into bytes
+    pub trait Number: Copy {
+        type Bytes;
+        /// The size of `Bytes`
+        const SIZE: usize;
+
+        fn le(self) -> Self::Bytes;
+        fn be(self) -> Self::Bytes;
+        fn ne(self) -> Self::Bytes;
+
+        /// Writes `self` into `out`, which must be `SIZE` bytes long
+        fn write(self, order: ByteOrder, out: &mut [u8]);
+        /// Reads a number from `bytes`, which must be `SIZE` bytes long
+        fn read(order: ByteOrder, bytes: &[u8]) -> Self;
+    }
+}
+
+use self::sealed::{Input, Number};
+
+impl<const N: usize> Input<N> for [u8; N] {
+    type Output<T> = T;
+
+    #[inline]
+    fn read<T>(self, f: fn([u8; N]) -> T) -> T {
+        f(self)
+    }
+}
+
+impl<const N: usize> Input<N> for &[u8; N] {
+    type Output<T> = T;
+
+    #[inline]
+    fn read<T>(self, f: fn([u8; N]) -> T) -> T {
+        f(*self)
+    }
+}
+
+impl<const N: usize> Input<N> for &[u8] {
+    type Output<T> = Result<T, LengthError>;
+
+    #[inline]
+    fn read<T>(self, f: fn([u8; N]) -> T) -> Result<T, LengthError> {
+        match <[u8; N]>::try_from(self) {
+            Ok(bytes) => Ok(f(bytes)),
+            Err(_) => Err(LengthError {
+                expected: N,
+                found: self.len(),
+            }),
+        }
+    }
+}
+
+/// The little endian representation of `x`
+#[inline]
+pub fn le<T: Number>(x: T) -> T::Bytes {
+    x.le()
+}
+
+/// The big endian representation of `x`
+#[inline]
+pub fn be<T: Number>(x: T) -> T::Bytes {
+    x.be()
+}
+
+/// The representation of `x` in the byte order of the target
+#[inline]
+pub fn ne<T: Number>(x: T) -> T::Bytes {
+    x.ne()
+}
+
+macro_rules! number {
+    ($($ty:ident: $n:expr;)+) => {
+        $(
+            impl Number for $ty {
+                type Bytes = [u8; $n];
+                const SIZE: usize = $n;
+
+                #[inline]
+                fn le(self) -> [u8; $n] {
+                    self.to_le_bytes()
+                }
+
+                #[inline]
+                fn be(self) -> [u8; $n] {
+                    self.to_be_bytes()
+                }
+
+                #[inline]
+                fn ne(self) -> [u8; $n] {
+                    self.to_ne_bytes()
+                }
+
+                #[inline]
+                fn write(self, order: ByteOrder, out: &mut [u8]) {
+                    out.copy_from_slice(&match order {
+                        ByteOrder::LittleEndian => self.to_le_bytes(),
+                        ByteOrder::BigEndian => self.to_be_bytes(),
+                    })
+                }
+
+                #[inline]
+                fn read(order: ByteOrder, bytes: &[u8]) -> $ty {
+                    let mut array = [0; $n];
+                    array.copy_from_slice(bytes);
+                    match order {
+                        ByteOrder::LittleEndian => $ty::from_le_bytes(array),
+                        ByteOrder::BigEndian => $ty::from_be_bytes(array),
+                    }
+                }
+            }
+        )+
+    }
+}
+
+number! {
+    f32: 4;
+    f64: 8;
+    i8: 1;
+    i16: 2;
+    i32: 4;
+    i64: 8;
+    i128: 16;
+    u8: 1;
+    u16: 2;
+    u32: 4;
+    u64: 8;
+    u128: 16;
+}
+
+macro_rules! fns {
+    ($($ty:ident: $n:expr => $le:ident, $be:ident, $ne:ident;)+) => {
+        $(
+            dst! {
+                $ty,
+
+                #[doc = concat!("Reads a little endian `", stringify!($ty), "`")]
+                #[inline]
+                pub fn $le<B: Input<$n>>(bytes: B) -> B::Output<$ty> {
+                    bytes.read($ty::from_le_bytes)
+                }
+
+                #[doc = concat!("Reads a big endian `", stringify!($ty), "`")]
+                #[inline]
+                pub fn $be<B: Input<$n>>(bytes: B) -> B::Output<$ty> {
+                    bytes.read($ty::from_be_bytes)
+                }
+
+                #[doc = concat!("Reads a `", stringify!($ty), "` in the byte order of the target")]
+                #[inline]
+                pub fn $ne<B: Input<$n>>(bytes: B) -> B::Output<$ty> {
+                    bytes.read($ty::from_ne_bytes)
+                }
+            }
+        )+
+    }
+}
+
+fns! {
+    f32: 4 => f32_le, f32_be, f32_ne;
+    f64: 8 => f64_le, f64_be, f64_ne;
+    i16: 2 => i16_le, i16_be, i16_ne;
+    i32: 4 => i32_le, i32_be, i32_ne;
+    i64: 8 => i64_le, i64_be, i64_ne;
+    i128: 16 => i128_le, i128_be, i128_ne;
+    u16: 2 => u16_le, u16_be, u16_ne;
+    u32: 4 => u32_le, u32_be, u32_ne;
+    u64: 8 => u64_le, u64_be, u64_ne;
+    u128: 16 => u128_le, u128_be, u128_ne;
+}
@@ -0,0 +1,143 @@
+//! Mixed-width checked arithmetic
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::{Error, From, IntoResult};
+
+/// Integer types that support checked arithmetic
+///
+/// Unlike the `checked_*` methods of the primitive types, these report *why*
+/// the operation failed using the crate's `Error` type.
+pub trait Arithmetic: Copy {
+    /// Checked addition
+    fn try_add(self, rhs: Self) -> Result<Self, Error>;
+
+    /// Checked subtraction
+    fn try_sub(self, rhs: Self) -> Result<Self, Error>;
+
+    /// Checked multiplication
+    fn try_mul(self, rhs: Self) -> Result<Self, Error>;
+}
+
+macro_rules! unsigned {
+    ($($ty:ident),+) => {
+        $(
+            impl Arithmetic for $ty {
+                #[inline]
+                fn try_add(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_add(rhs).ok_or(Error::Overflow)
+                }
+
+                #[inline]
+                fn try_sub(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_sub(rhs).ok_or(Error::NegOverflow)
+                }
+
+                #[inline]
+                fn try_mul(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_mul(rhs).ok_or(Error::Overflow)
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! signed {
+    ($($ty:ident),+) => {
+        $(
+            impl Arithmetic for $ty {
+                #[inline]
+                fn try_add(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_add(rhs).ok_or(if rhs < 0 {
+                        Error::NegOverflow
+                    } else {
+                        Error::Overflow
+                    })
+                }
+
+                #[inline]
+                fn try_sub(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_sub(rhs).ok_or(if rhs > 0 {
+                        Error::NegOverflow
+                    } else {
+                        Error::Overflow
+                    })
+                }
+
+                #[inline]
+                fn try_mul(self, rhs: $ty) -> Result<$ty, Error> {
+                    self.checked_mul(rhs).ok_or(if (self < 0) != (rhs < 0) {
+                        Error::NegOverflow
+                    } else {
+                        Error::Overflow
+                    })
+                }
+            }
+        )+
+    }
+}
+
+unsigned!(u8, u16, u32, u64, u128, usize);
+signed!(i8, i16, i32, i64, i128, isize);
+
+/// Checked arithmetic on `T` that accepts any operand that can be casted into
+/// `T`
+///
+/// Each operand is first casted into `T` and then combined using checked
+/// arithmetic. The first error, be it from a cast or from the arithmetic
+/// operation itself, is sticky: it is propagated through every subsequent
+/// operation and reported by `get`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Checked<T>(Result<T, Error>);
+
+impl<T> Checked<T> {
+    /// Wraps `x` for checked arithmetic
+    #[inline]
+    pub fn new(x: T) -> Checked<T> {
+        Checked(Ok(x))
+    }
+
+    /// Returns the result of the arithmetic, or the first error encountered
+    #[inline]
+    pub fn get(self) -> Result<T, Error> {
+        self.0
+    }
+}
+
+macro_rules! ops {
+    ($($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $try_op:ident;)+) => {
+        $(
+            impl<T, Rhs> $Op<Rhs> for Checked<T>
+            where
+                T: From<Rhs> + Arithmetic,
+                <T as From<Rhs>>::Output: IntoResult<T>,
+            {
+                type Output = Checked<T>;
+
+                #[inline]
+                fn $op(self, rhs: Rhs) -> Checked<T> {
+                    Checked(self.0.and_then(|lhs| {
+                        lhs.$try_op(<T as From<Rhs>>::cast(rhs).into_result()?)
+                    }))
+                }
+            }
+
+            impl<T, Rhs> $OpAssign<Rhs> for Checked<T>
+            where
+                T: From<Rhs> + Arithmetic,
+                <T as From<Rhs>>::Output: IntoResult<T>,
+            {
+                #[inline]
+                fn $op_assign(&mut self, rhs: Rhs) {
+                    *self = $Op::$op(*self, rhs);
+                }
+            }
+        )+
+    }
+}
+
+ops! {
+    Add, add, AddAssign, add_assign, try_add;
+    Sub, sub, SubAssign, sub_assign, try_sub;
+    Mul, mul, MulAssign, mul_assign, try_mul;
+}
@@ -0,0 +1,65 @@
+//! Compile time metadata about how casts can fail
+
+use crate::From;
+
+/// How a cast can fail
+///
+/// See `classify`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Fallibility {
+    /// The cast never fails
+    Infallible,
+    /// The cast can only fail with `Error::Overflow`
+    Overflow,
+    /// The cast can only fail with `Error::NegOverflow`
+    NegOverflow,
+    /// The cast can fail in more than one way, or in a way not covered above
+    Fallible,
+}
+
+impl Fallibility {
+    /// Whether the cast can fail
+    #[inline]
+    pub const fn is_fallible(self) -> bool {
+        !matches!(self, Fallibility::Infallible)
+    }
+
+    /// The fallibility of a cast that performs both casts, like a tuple cast
+    #[inline]
+    pub const fn or(self, other: Fallibility) -> Fallibility {
+        match (self, other) {
+            (Fallibility::Infallible, x) | (x, Fallibility::Infallible) => x,
+            (Fallibility::Overflow, Fallibility::Overflow) => Fallibility::Overflow,
+            (Fallibility::NegOverflow, Fallibility::NegOverflow) => Fallibility::NegOverflow,
+            _ => Fallibility::Fallible,
+        }
+    }
+}
+
+/// How the cast from `Src` into `Dst` can fail on the current target
+///
+/// This is a `const fn`, so it can drive code generation and compile time
+/// assertions.
+///
+/// ```
+/// use cast::Fallibility;
+///
+/// # fn main() {
+/// assert_eq!(cast::classify::<u8, u16>(), Fallibility::Infallible);
+/// assert_eq!(cast::classify::<u16, u8>(), Fallibility::Overflow);
+/// assert_eq!(cast::classify::<i8, u16>(), Fallibility::NegOverflow);
+/// assert_eq!(cast::classify::<i16, u8>(), Fallibility::Fallible);
+/// assert_eq!(cast::classify::<f32, u8>(), Fallibility::Fallible);
+///
+/// // depends on the pointer width, unless the `portable` feature is enabled
+/// # #[cfg(all(target_pointer_width = "64", not(feature = "portable")))]
+/// assert_eq!(cast::classify::<u64, usize>(), Fallibility::Infallible);
+/// # }
+/// ```
+#[inline]
+pub const fn classify<Src, Dst>() -> Fallibility
+where
+    Dst: From<Src>,
+{
+    Dst::FALLIBILITY
+}
@@ -0,0 +1,188 @@
+//! Parsing of command line arguments into narrow types
+//!
+//! `parse` parses a number and casts it into the destination type, so
+//! `--port 70000` fails with a message that says what the accepted range is,
+//! instead of "number too large to fit in target type" or, with an `as` cast
+//! after parsing a wider type, a silently truncated port. Its signature fits
+//! the value parsers of `clap`, as well as any other parser that accepts a
+//! `fn(&str) -> Result<T, E>`.
+//!
+//! ```
+//! use clap::{Arg, Command};
+//!
+//! # fn main() {
+//! let cli = Command::new("server").arg(
+//!     Arg::new("port")
+//!         .long("port")
+//!         .value_parser(cast::cli::parse::<u16>),
+//! );
+//!
+//! let args = cli.clone().try_get_matches_from(["server", "--port", "8080"]).unwrap();
+//! assert_eq!(args.get_one::<u16>("port"), Some(&8080));
+//!
+//! let e = cli.try_get_matches_from(["server", "--port", "70000"]).unwrap_err();
+//! assert!(e.to_string().contains("expected ≤ 65535"));
+//! # }
+//! ```
+//!
+//! Integers are parsed as integers, even when the destination type is wider
+//! than needed; `--threads 2.5` is an error rather than `2` threads.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::IntErrorKind;
+
+use crate::{Error, From, IntoResult};
+
+mod sealed {
+    use core::fmt;
+    use core::num::{ParseFloatError, ParseIntError};
+
+    use crate::Error;
+
+    /// Why a string couldn't be parsed
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Kind {
+        Int(ParseIntError),
+        Float(ParseFloatError),
+        Cast(Error),
+    }
+
+    pub trait Arg: Copy + fmt::Display {
+        const MIN: Self;
+        const MAX: Self;
+
+        fn parse(s: &str) -> Result<Self, Kind>;
+    }
+}
+
+use self::sealed::{Arg, Kind};
+
+/// The error returned by `parse`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError<Dst> {
+    kind: Kind,
+    _dst: PhantomData<fn() -> Dst>,
+}
+
+impl<Dst> ParseError<Dst> {
+    /// Why the number couldn't be casted, or `None` if the argument is not a
+    /// number
+    #[inline]
+    pub fn error(&self) -> Option<Error> {
+        match self.kind {
+            Kind::Cast(error) => Some(error),
+            Kind::Int(_) | Kind::Float(_) => None,
+        }
+    }
+}
+
+impl<Dst: Arg> fmt::Display for ParseError<Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            Kind::Int(e) => e.fmt(f),
+            Kind::Float(e) => e.fmt(f),
+            Kind::Cast(Error::Overflow) => write!(f, "expected ≤ {}", Dst::MAX),
+            Kind::Cast(Error::NegOverflow) => write!(f, "expected ≥ {}", Dst::MIN),
+            Kind::Cast(Error::NaN | Error::Infinite) => f.write_str("expected a finite number"),
+        }
+    }
+}
+
+impl<Dst: Arg + fmt::Debug> core::error::Error for ParseError<Dst> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            Kind::Int(e) => Some(e),
+            Kind::Float(e) => Some(e),
+            Kind::Cast(error) => Some(error),
+        }
+    }
+}
+
+/// Parses `s` as a number and casts it into `Dst`
+///
+/// ```
+/// use cast::Error;
+///
+/// # fn main() {
+/// assert_eq!(cast::cli::parse::<u8>("255"), Ok(255));
+/// assert_eq!(cast::cli::parse::<f32>("0.5"), Ok(0.5));
+///
+/// let e = cast::cli::parse::<u8>("-1").unwrap_err();
+/// assert_eq!(e.error(), Some(Error::NegOverflow));
+/// assert_eq!(e.to_string(), "expected ≥ 0");
+///
+/// let e = cast::cli::parse::<u8>("one").unwrap_err();
+/// assert_eq!(e.error(), None);
+/// assert_eq!(e.to_string(), "invalid digit found in string");
+/// # }
+/// ```
+#[inline]
+pub fn parse<Dst: Arg>(s: &str) -> Result<Dst, ParseError<Dst>> {
+    Dst::parse(s).map_err(|kind| ParseError {
+        kind,
+        _dst: PhantomData,
+    })
+}
+
+#[allow(dead_code)]
+fn int<Dst>(s: &str) -> Result<Dst, Kind>
+where
+    Dst: From<i128> + From<u128>,
+    <Dst as From<i128>>::Output: IntoResult<Dst>,
+    <Dst as From<u128>>::Output: IntoResult<Dst>,
+{
+    let cast = match s.parse::<i128>() {
+        Ok(x) => Dst::cast(x).into_result(),
+        // `u128::MAX` doesn't fit in an `i128`
+        Err(e) if *e.kind() == IntErrorKind::PosOverflow => match s.parse::<u128>() {
+            Ok(x) => Dst::cast(x).into_result(),
+            Err(_) => Err(Error::Overflow),
+        },
+        Err(e) => match Error::from_int_error_kind(e.kind()) {
+            Some(error) => Err(error),
+            None => return Err(Kind::Int(e)),
+        },
+    };
+
+    cast.map_err(Kind::Cast)
+}
+
+#[allow(dead_code)]
+fn float<Dst>(s: &str) -> Result<Dst, Kind>
+where
+    Dst: From<f64>,
+    <Dst as From<f64>>::Output: IntoResult<Dst>,
+{
+    match s.parse::<f64>() {
+        Ok(x) => Dst::cast(x).into_result().map_err(Kind::Cast),
+        Err(e) => Err(Kind::Float(e)),
+    }
+}
+
+macro_rules! arg {
+    ($($parse:ident: $($ty:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $ty,
+
+                    impl Arg for $ty {
+                        const MIN: $ty = $ty::MIN;
+                        const MAX: $ty = $ty::MAX;
+
+                        #[inline]
+                        fn parse(s: &str) -> Result<$ty, Kind> {
+                            $parse(s)
+                        }
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+arg! {
+    int: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+    float: f32, f64;
+}
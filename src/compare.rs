@@ -0,0 +1,214 @@
+//! Comparisons between values of different numeric types
+
+use core::cmp::Ordering;
+
+use crate::{auto, Error, From, IntoResult};
+
+/// A numeric value in a form that can be compared without loss of precision
+#[derive(Clone, Copy)]
+pub enum Canonical {
+    /// A signed integer
+    Int(i128),
+    /// An unsigned integer
+    UInt(u128),
+    /// A float; `f32` converts into `f64` losslessly
+    Float(f64),
+}
+
+/// Numeric primitives that can be compared with each other
+///
+/// See `cmp`.
+pub trait Compare: Copy {
+    #[doc(hidden)]
+    fn canonical(self) -> Canonical;
+}
+
+macro_rules! compare {
+    ($($variant:ident: $($ty:ident),+;)+) => {
+        $(
+            $(
+                impl Compare for $ty {
+                    #[inline]
+                    fn canonical(self) -> Canonical {
+                        Canonical::$variant(self as _)
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+compare! {
+    Int: i8, i16, i32, i64, i128, isize;
+    UInt: u8, u16, u32, u64, u128, usize;
+    Float: f32, f64;
+}
+
+// 2^127 and 2^128
+const I128_END: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.;
+const U128_END: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.;
+
+fn cmp_float_int(x: f64, y: i128) -> Option<Ordering> {
+    if x.is_nan() {
+        None
+    } else if x >= I128_END {
+        Some(Ordering::Greater)
+    } else if x < -I128_END {
+        Some(Ordering::Less)
+    } else {
+        // truncating is exact in this range, and so is converting the
+        // truncated value back into a float
+        let t = x as i128;
+        match t.cmp(&y) {
+            Ordering::Equal => x.partial_cmp(&(t as f64)),
+            ord => Some(ord),
+        }
+    }
+}
+
+fn cmp_float_uint(x: f64, y: u128) -> Option<Ordering> {
+    if x.is_nan() {
+        None
+    } else if x < 0. {
+        Some(Ordering::Less)
+    } else if x >= U128_END {
+        Some(Ordering::Greater)
+    } else {
+        // see `cmp_float_int`
+        let t = x as u128;
+        match t.cmp(&y) {
+            Ordering::Equal => x.partial_cmp(&(t as f64)),
+            ord => Some(ord),
+        }
+    }
+}
+
+fn cmp_int_uint(x: i128, y: u128) -> Ordering {
+    if x < 0 {
+        Ordering::Less
+    } else {
+        (x as u128).cmp(&y)
+    }
+}
+
+/// Compares two numbers of possibly different types
+///
+/// The comparison is exact: neither operand is converted lossily, so
+/// `u64::MAX` is greater than `-1i32`, and `16_777_217u32` is greater than
+/// `16_777_216f32`. Like `PartialOrd`, this returns `None` if either operand is
+/// NaN.
+///
+/// ```
+/// use core::cmp::Ordering;
+///
+/// # fn main() {
+/// assert_eq!(cast::cmp(u64::MAX, -1i32), Some(Ordering::Greater));
+/// assert_eq!(cast::cmp(16_777_217u32, 16_777_216f32), Some(Ordering::Greater));
+/// assert_eq!(cast::cmp(-0.5f64, 0u8), Some(Ordering::Less));
+/// assert_eq!(cast::cmp(f32::NAN, 0u8), None);
+/// # }
+/// ```
+#[inline]
+pub fn cmp<A, B>(a: A, b: B) -> Option<Ordering>
+where
+    A: Compare,
+    B: Compare,
+{
+    match (a.canonical(), b.canonical()) {
+        (Canonical::Int(x), Canonical::Int(y)) => Some(x.cmp(&y)),
+        (Canonical::UInt(x), Canonical::UInt(y)) => Some(x.cmp(&y)),
+        (Canonical::Float(x), Canonical::Float(y)) => x.partial_cmp(&y),
+        (Canonical::Int(x), Canonical::UInt(y)) => Some(cmp_int_uint(x, y)),
+        (Canonical::UInt(x), Canonical::Int(y)) => Some(cmp_int_uint(y, x).reverse()),
+        (Canonical::Float(x), Canonical::Int(y)) => cmp_float_int(x, y),
+        (Canonical::Int(x), Canonical::Float(y)) => cmp_float_int(y, x).map(Ordering::reverse),
+        (Canonical::Float(x), Canonical::UInt(y)) => cmp_float_uint(x, y),
+        (Canonical::UInt(x), Canonical::Float(y)) => cmp_float_uint(y, x).map(Ordering::reverse),
+    }
+}
+
+/// Whether two numbers of possibly different types are equal
+///
+/// See `cmp`.
+///
+/// ```
+/// # fn main() {
+/// assert!(cast::eq(255u8, 255.0f32));
+/// assert!(!cast::eq(-1i8, u128::MAX));
+/// # }
+/// ```
+#[inline]
+pub fn eq<A, B>(a: A, b: B) -> bool
+where
+    A: Compare,
+    B: Compare,
+{
+    cmp(a, b) == Some(Ordering::Equal)
+}
+
+/// The smaller of two numbers of possibly different types, casted into `Dst`
+///
+/// The comparison is exact (see `cmp`); the cast into `Dst` is checked, and
+/// `Dst` can be inferred like with `auto`. If either operand is NaN, the other
+/// one is returned.
+///
+/// ```
+/// # fn main() {
+/// let len = 300u64;
+/// let n: i16 = cast::min(len, -1i32).unwrap();
+///
+/// assert_eq!(n, -1);
+/// assert_eq!(cast::min::<u8, _, _>(len, 256u16), Err(cast::Error::Overflow));
+/// # }
+/// ```
+#[inline]
+pub fn min<Dst, A, B>(a: A, b: B) -> Result<Dst, Error>
+where
+    A: Compare,
+    B: Compare,
+    Dst: From<A> + From<B>,
+    <Dst as From<A>>::Output: IntoResult<Dst>,
+    <Dst as From<B>>::Output: IntoResult<Dst>,
+{
+    match cmp(a, b) {
+        Some(Ordering::Greater) => auto(b),
+        Some(_) => auto(a),
+        None if is_nan(a) => auto(b),
+        None => auto(a),
+    }
+}
+
+/// The larger of two numbers of possibly different types, casted into `Dst`
+///
+/// See `min`.
+///
+/// ```
+/// # fn main() {
+/// let n: u64 = cast::max(-1i32, 7u8).unwrap();
+///
+/// assert_eq!(n, 7);
+/// # }
+/// ```
+#[inline]
+pub fn max<Dst, A, B>(a: A, b: B) -> Result<Dst, Error>
+where
+    A: Compare,
+    B: Compare,
+    Dst: From<A> + From<B>,
+    <Dst as From<A>>::Output: IntoResult<Dst>,
+    <Dst as From<B>>::Output: IntoResult<Dst>,
+{
+    match cmp(a, b) {
+        Some(Ordering::Less) => auto(b),
+        Some(_) => auto(a),
+        None if is_nan(a) => auto(b),
+        None => auto(a),
+    }
+}
+
+fn is_nan<T: Compare>(x: T) -> bool {
+    match x.canonical() {
+        Canonical::Float(x) => x.is_nan(),
+        _ => false,
+    }
+}
@@ -0,0 +1,40 @@
+//! Interoperability with the conversion traits of `core`
+
+/// Exposes the casts of this crate through the conversion traits of `core`
+///
+/// Infallible casts (promotions) are available as `core::convert::From`
+/// implementations and every other cast is available as a
+/// `core::convert::TryFrom` implementation whose `Error` is this crate's
+/// `Error`. This lets code that is generic over the `core` traits, like
+/// `T: TryFrom<u64>`, make use of this crate's checks.
+///
+/// ```
+/// use core::convert::TryFrom;
+///
+/// use cast::{Error, StdCompat};
+///
+/// fn parse<T: TryFrom<u64>>(x: u64) -> Result<T, T::Error> {
+///     T::try_from(x)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(parse::<StdCompat<u8>>(255), Ok(StdCompat(255)));
+/// assert_eq!(parse::<StdCompat<u8>>(256), Err(Error::Overflow));
+///
+/// // unlike `core`, this crate also covers float to integer casts
+/// assert_eq!(StdCompat::<u8>::try_from(1. / 0.), Err(Error::Infinite));
+///
+/// // promotions are infallible
+/// assert_eq!(StdCompat::<f64>::from(1u8), StdCompat(1.));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StdCompat<T>(pub T);
+
+impl<T> StdCompat<T> {
+    /// Unwraps the value
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
@@ -0,0 +1,134 @@
+//! Incremental casts of streams of samples
+
+use crate::slice::SliceError;
+use crate::{ElementError, From, IntoResult};
+
+/// Checked cast of a stream of samples that arrives in chunks of any size
+///
+/// The samples are interleaved in frames of `CHANNELS` samples, e.g. `2` for
+/// stereo audio. Each call to `convert` casts every complete frame, counting
+/// the samples left over from the previous call, and keeps the samples of the
+/// last incomplete frame until the next call, so the output always consists of
+/// whole frames. This doesn't allocate, so it can be used in real-time
+/// callbacks.
+///
+/// The indices of the errors are positions in the whole stream, not in the
+/// chunk.
+///
+/// ```
+/// use cast::Converter;
+///
+/// # fn main() {
+/// let mut stereo = Converter::<i32, i16, 2>::new();
+/// let mut buf = [0i16; 4];
+///
+/// // 3 samples: one frame plus the left sample of the next one
+/// assert_eq!(stereo.convert(&[1, 2, 3], &mut buf), Ok(&mut [1, 2][..]));
+/// assert_eq!(stereo.pending(), [3]);
+///
+/// assert_eq!(stereo.convert(&[4], &mut buf), Ok(&mut [3, 4][..]));
+///
+/// let e = stereo.convert(&[5, 6, 70_000, 8], &mut buf).unwrap_err();
+/// assert_eq!(format!("{}", e), "Element 6 (70000): Overflow during numeric conversion");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Converter<Src, Dst, const CHANNELS: usize = 1> {
+    /// The samples of the incomplete frame, in `carry[..carried]`
+    carry: [Src; CHANNELS],
+    carried: usize,
+    /// Number of samples of the stream casted so far
+    position: usize,
+    _dst: core::marker::PhantomData<fn() -> Dst>,
+}
+
+impl<Src, Dst, const CHANNELS: usize> Converter<Src, Dst, CHANNELS>
+where
+    Src: Copy + Default,
+{
+    /// Creates a converter for a new stream
+    ///
+    /// This panics at compile time if `CHANNELS` is `0`.
+    pub fn new() -> Self {
+        const { assert!(CHANNELS > 0, "a frame needs at least one channel") };
+
+        Converter {
+            carry: [Src::default(); CHANNELS],
+            carried: 0,
+            position: 0,
+            _dst: core::marker::PhantomData,
+        }
+    }
+
+    /// Casts the samples left over from the previous call, followed by `src`,
+    /// into `dst`, up to the last complete frame
+    ///
+    /// Returns the written part of `dst`, whose length is a multiple of
+    /// `CHANNELS`. `dst` must have room for all the complete frames.
+    ///
+    /// On error, the state of the converter doesn't change, and the contents
+    /// of `dst` are unspecified.
+    pub fn convert<'a>(
+        &mut self,
+        src: &[Src],
+        dst: &'a mut [Dst],
+    ) -> Result<&'a mut [Dst], SliceError<Src>>
+    where
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+    {
+        let total = self.carried + src.len();
+        let n = total - total % CHANNELS;
+        let dst = dst.get_mut(..n).ok_or(SliceError::BufferTooSmall)?;
+
+        let mut samples = self.carry[..self.carried].iter().chain(src).copied();
+        for (i, (y, x)) in dst.iter_mut().zip(&mut samples).enumerate() {
+            *y = match Dst::cast(x).into_result() {
+                Ok(y) => y,
+                Err(error) => {
+                    let index = self.position + i;
+                    return Err(SliceError::Element(ElementError::new(index, x, error)));
+                }
+            };
+        }
+
+        let mut carry = [Src::default(); CHANNELS];
+        let mut carried = 0;
+        for x in samples {
+            carry[carried] = x;
+            carried += 1;
+        }
+
+        self.carry = carry;
+        self.carried = carried;
+        self.position += n;
+
+        Ok(dst)
+    }
+
+    /// The samples of the incomplete frame that will be casted by the next
+    /// call to `convert`
+    pub fn pending(&self) -> &[Src] {
+        &self.carry[..self.carried]
+    }
+
+    /// The number of samples casted so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Drops the pending samples and starts counting positions from `0` again
+    pub fn reset(&mut self) {
+        self.carried = 0;
+        self.position = 0;
+    }
+}
+
+impl<Src, Dst, const CHANNELS: usize> Default for Converter<Src, Dst, CHANNELS>
+where
+    Src: Copy + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,107 @@
+//! Constant-time casts between integers, for secret data
+//!
+//! The crate's checked casts branch on the value being casted, so how long
+//! they take, and which code they run, tells whether a secret key or nonce was
+//! in range. `cast` does the same check without data-dependent branches, and
+//! returns a `subtle::CtOption`, which can be combined with the rest of the
+//! constant-time code of the program without branching either.
+//!
+//! ```
+//! use cast::ct;
+//!
+//! # fn main() {
+//! assert_eq!(ct::cast::<u8, _>(200u32).unwrap_or(0), 200);
+//! assert_eq!(ct::cast::<u8, _>(256u32).unwrap_or(0), 0);
+//! assert_eq!(ct::cast::<u32, _>(-1i64).unwrap_or(0), 0);
+//! assert!(bool::from(ct::cast::<i8, _>(128u16).is_none()));
+//! # }
+//! ```
+//!
+//! The result doesn't say why the cast failed: telling `Overflow` apart from
+//! `NegOverflow` would be another secret to protect. Only the integers are
+//! supported; floating-point arithmetic is not constant time on every target.
+//!
+//! *This module requires the `subtle` Cargo feature*
+
+use subtle::{Choice, CtOption};
+
+mod sealed {
+    use subtle::{Choice, ConstantTimeEq};
+
+    pub trait Integer: Copy + ConstantTimeEq {
+        /// `self < 0`
+        fn is_negative(self) -> Choice;
+    }
+
+    pub trait Wrap<Dst> {
+        /// `self as Dst`
+        fn wrap(self) -> Dst;
+    }
+}
+
+use self::sealed::{Integer, Wrap};
+
+/// Checked cast of `src` into `Dst` that runs in the same time whether it
+/// succeeds or not; see the module documentation
+///
+/// `src` fits in `Dst` if it survives the round trip through `Dst`, and if
+/// the round trip doesn't flip its sign, which catches e.g. `-1i32` casted
+/// into `u32` and back.
+#[inline]
+pub fn cast<Dst, Src>(src: Src) -> CtOption<Dst>
+where
+    Src: Integer + Wrap<Dst>,
+    Dst: Integer + Wrap<Src>,
+{
+    let dst = src.wrap();
+    let fits = dst.wrap().ct_eq(&src) & !(src.is_negative() ^ dst.is_negative());
+
+    CtOption::new(dst, fits)
+}
+
+macro_rules! integer {
+    (signed: $($signed:ident as $unsigned:ident),+; unsigned: $($ty:ident),+;) => {
+        $(
+            impl Integer for $signed {
+                #[inline]
+                fn is_negative(self) -> Choice {
+                    Choice::from(((self as $unsigned) >> ($unsigned::BITS - 1)) as u8)
+                }
+            }
+        )+
+        $(
+            impl Integer for $ty {
+                #[inline]
+                fn is_negative(self) -> Choice {
+                    Choice::from(0)
+                }
+            }
+        )+
+    };
+}
+
+integer! {
+    signed: i8 as u8, i16 as u16, i32 as u32, i64 as u64, i128 as u128, isize as usize;
+    unsigned: u8, u16, u32, u64, u128, usize;
+}
+
+macro_rules! wrap {
+    ($($src:ident),+) => {
+        wrap!(@ [$($src),+] $($src),+);
+    };
+    (@ $dsts:tt $($src:ident),+) => {
+        $(wrap!(@@ $src $dsts);)+
+    };
+    (@@ $src:ident [$($dst:ident),+]) => {
+        $(
+            impl Wrap<$dst> for $src {
+                #[inline]
+                fn wrap(self) -> $dst {
+                    self as $dst
+                }
+            }
+        )+
+    };
+}
+
+wrap!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
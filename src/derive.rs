@@ -0,0 +1,11 @@
+//! Support of `#[derive(cast::From)]`
+
+use crate::{fail, EnumError};
+
+/// Reports `discriminant`, which fits in the `#[repr]` of the enum `Dst` but
+/// isn't the discriminant of any of its variants
+#[doc(hidden)]
+#[inline]
+pub fn unknown<Src, Dst, D: Copy>(discriminant: D) -> EnumError<D> {
+    fail::<Src, Dst, _>(EnumError::UnknownDiscriminant(discriminant))
+}
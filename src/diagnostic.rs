@@ -0,0 +1,103 @@
+//! `miette::Diagnostic` implementations
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::format;
+use core::any;
+use core::fmt::Display;
+
+use miette::Diagnostic;
+
+use crate::{CastError, Error, NegOverflowError, OverflowError, RangeError};
+
+fn code(error: Error) -> &'static str {
+    match error {
+        Error::Infinite => "cast::infinite",
+        Error::NaN => "cast::nan",
+        Error::Overflow => "cast::overflow",
+        Error::NegOverflow => "cast::neg_overflow",
+    }
+}
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(*self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(Error::help(self)))
+    }
+}
+
+impl Diagnostic for OverflowError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(Error::from(*self))))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(Error::from(*self).help()))
+    }
+}
+
+impl Diagnostic for NegOverflowError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(Error::from(*self))))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(Error::from(*self).help()))
+    }
+}
+
+impl Diagnostic for RangeError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(Error::from(*self))))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(Error::from(*self).help()))
+    }
+}
+
+/// The help text names the destination type
+impl<Src, Dst> Diagnostic for CastError<Src, Dst> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(self.error())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let dst = any::type_name::<Dst>();
+        Some(match self.error() {
+            Error::Overflow => Box::new(format!(
+                "consider clamping the value first, or using a type wider than `{}`",
+                dst
+            )),
+            Error::NegOverflow => Box::new(format!(
+                "consider clamping the value first, or using a type that can hold \
+                 smaller values than `{}`",
+                dst
+            )),
+            error => Box::new(error.help()),
+        })
+    }
+}
+
+/// The help text reports the range of the destination type
+#[cfg(feature = "error-value")]
+impl Diagnostic for crate::ValueError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(code(self.error())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(match self.error() {
+            Error::Overflow | Error::NegOverflow => Box::new(format!(
+                "the value must be between {} and {}",
+                self.min(),
+                self.max()
+            )),
+            error => Box::new(error.help()),
+        })
+    }
+}
@@ -0,0 +1,266 @@
+//! Quantization of floats with dither
+//!
+//! Rounding every sample to the nearest integer on its own turns smooth
+//! gradients into visible bands, and quiet sounds into distortion. The
+//! functions of this module round too, but add some carefully chosen noise
+//! first, which makes the rounding error uncorrelated with the signal.
+//!
+//! The values must be in the range that the checked cast into the output type
+//! accepts; a value outside of it is reported like `slice` does. The noise can
+//! push a value slightly out of the range of the output type, in which case it
+//! saturates.
+//!
+//! # Images
+//!
+//! `serpentine` and `floyd_steinberg` carry the rounding error of every pixel
+//! over to the pixels that haven't been quantized yet, so the average
+//! intensity of every region is preserved. The input is one channel of an
+//! image, in rows of `width` pixels; use `slice::deinterleave` to split
+//! interleaved channels first.
+//!
+//! ```
+//! # fn main() {
+//! // a flat gray between two levels
+//! let gray = [127.25f32; 16];
+//! let mut pixels = [0u8; 16];
+//!
+//! let pixels = cast::dither::serpentine(&gray, 4, &mut pixels).unwrap();
+//! assert_eq!(pixels.iter().filter(|&&p| p == 128).count(), 4);
+//! assert_eq!(pixels.iter().filter(|&&p| p == 127).count(), 12);
+//! # }
+//! ```
+//!
+//! # Audio
+//!
+//! `Tpdf` adds noise with a triangular probability density function, spanning
+//! one step of the output on each side, to every sample; the standard dither
+//! for producing 16-bit audio.
+//!
+//! ```
+//! use cast::dither::Tpdf;
+//!
+//! # fn main() {
+//! let mut dither = Tpdf::new();
+//! let mut pcm = [0i16; 1024];
+//!
+//! // a quiet tone, a quarter of a step above silence
+//! let pcm = dither.i16(&[0.25; 1024], &mut pcm).unwrap();
+//! assert!(pcm.iter().all(|&x| -1 <= x && x <= 1));
+//! let mean = pcm.iter().map(|&x| f32::from(x)).sum::<f32>() / 1024.;
+//! assert!((mean - 0.25).abs() < 0.1);
+//!
+//! assert!(dither.i16(&[40_000.], &mut [0]).is_err());
+//! # }
+//! ```
+
+#[cfg(any(feature = "i16", feature = "u8"))]
+use crate::slice::SliceError;
+#[cfg(any(feature = "i16", feature = "u8"))]
+use crate::{ElementError, From, IntoResult};
+
+/// Checks that `x`, the element at `index` of the input, can be casted into
+/// `T`
+#[cfg(any(feature = "i16", feature = "u8"))]
+#[inline]
+fn check<T>(index: usize, x: f32) -> Result<(), SliceError<f32>>
+where
+    T: From<f32>,
+    <T as From<f32>>::Output: IntoResult<T>,
+{
+    match T::cast(x).into_result() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(SliceError::Element(ElementError::new(index, x, error))),
+    }
+}
+
+dst! {
+    u8,
+
+    /// Rounds `x` to the nearest `u8`, saturating
+    #[inline]
+    fn quantize(x: f32) -> u8 {
+        // `as` saturates, and truncating `x + 0.5` rounds for non-negative `x`
+        (x.max(0.) + 0.5) as u8
+    }
+
+    /// The indices of the pixels of the row `y`, in serpentine order: left to
+    /// right on even rows, right to left on odd ones
+    fn row(y: usize, width: usize, len: usize) -> impl Iterator<Item = usize> {
+        let start = y * width;
+        let end = (start + width).min(len);
+        #[allow(clippy::manual_is_multiple_of)] // `is_multiple_of` is newer than the MSRV
+        let forward = y % 2 == 0;
+        (start..end).map(move |i| if forward { i } else { start + end - 1 - i })
+    }
+
+    /// Quantizes `src` into `dst` carrying the rounding error of every pixel
+    /// over to the next one, along a path that alternates direction on every
+    /// row
+    ///
+    /// This doesn't allocate. Returns the written part of `dst`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `width` is `0`.
+    pub fn serpentine<'a>(
+        src: &[f32],
+        width: usize,
+        dst: &'a mut [u8],
+    ) -> Result<&'a mut [u8], SliceError<f32>> {
+        assert!(width != 0, "`width` must not be zero");
+
+        let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+        let mut error = 0.;
+        for y in 0..src.len().div_ceil(width) {
+            for i in row(y, width, src.len()) {
+                check::<u8>(i, src[i])?;
+
+                let x = src[i] + error;
+                dst[i] = quantize(x);
+                error = x - f32::from(dst[i]);
+            }
+        }
+
+        Ok(dst)
+    }
+
+    /// Quantizes `src` into `dst` spreading the rounding error of every pixel
+    /// over its unquantized neighbors, with the Floyd-Steinberg weights and a
+    /// serpentine scan
+    ///
+    /// This gives better looking results than `serpentine`, at the cost of
+    /// allocating two rows worth of errors. Returns the written part of `dst`.
+    ///
+    /// *This API requires the `alloc` Cargo feature*
+    ///
+    /// # Panics
+    ///
+    /// This panics if `width` is `0`.
+    #[cfg(feature = "alloc")]
+    pub fn floyd_steinberg<'a>(
+        src: &[f32],
+        width: usize,
+        dst: &'a mut [u8],
+    ) -> Result<&'a mut [u8], SliceError<f32>> {
+        extern crate alloc;
+
+        assert!(width != 0, "`width` must not be zero");
+
+        let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+        // the errors carried over to the current and the next row, with one
+        // element of padding on each side
+        let mut current = alloc::vec![0f32; width + 2];
+        let mut next = alloc::vec![0f32; width + 2];
+        for y in 0..src.len().div_ceil(width) {
+            #[allow(clippy::manual_is_multiple_of)]
+            let forward = y % 2 == 0;
+            for i in row(y, width, src.len()) {
+                check::<u8>(i, src[i])?;
+
+                // position of the pixel in the padded rows, and of its
+                // neighbors ahead of and behind it
+                let x = i - y * width + 1;
+                let (ahead, behind) = if forward { (x + 1, x - 1) } else { (x - 1, x + 1) };
+
+                let value = src[i] + current[x];
+                dst[i] = quantize(value);
+                let error = value - f32::from(dst[i]);
+
+                current[ahead] += error * 7. / 16.;
+                next[behind] += error * 3. / 16.;
+                next[x] += error * 5. / 16.;
+                next[ahead] += error / 16.;
+            }
+
+            core::mem::swap(&mut current, &mut next);
+            next.iter_mut().for_each(|e| *e = 0.);
+        }
+
+        Ok(dst)
+    }
+}
+
+/// Dither with a triangular probability density function; see the module
+/// documentation
+///
+/// The noise comes from a small pseudo-random generator whose state carries
+/// over from call to call, so a stream can be converted in chunks of any size.
+/// Two `Tpdf`s created with the same seed produce the same noise.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tpdf {
+    /// The state of a xorshift generator; never `0`
+    state: u32,
+}
+
+impl Tpdf {
+    const SEED: u32 = 0x9e37_79b9;
+
+    /// Creates a dither with the default seed
+    pub const fn new() -> Self {
+        Tpdf { state: Self::SEED }
+    }
+
+    /// Creates a dither with the given seed
+    ///
+    /// A seed of `0` is replaced with the default one.
+    pub const fn with_seed(seed: u32) -> Self {
+        Tpdf {
+            state: if seed == 0 { Self::SEED } else { seed },
+        }
+    }
+
+    /// A random number in `0.0..1.0`
+    #[allow(dead_code)]
+    #[inline]
+    fn uniform(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        // the 24 high bits fit in the mantissa
+        (self.state >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// A random number in `-1.0..1.0`, with a triangular distribution
+    #[allow(dead_code)]
+    #[inline]
+    fn noise(&mut self) -> f32 {
+        self.uniform() - self.uniform()
+    }
+}
+
+impl Default for Tpdf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+dst! {
+    i16,
+
+    impl Tpdf {
+        /// Adds noise to every element of `src` and rounds it into `dst`
+        ///
+        /// Returns the written part of `dst`. On error, the contents of `dst`
+        /// are unspecified.
+        pub fn i16<'a>(
+            &mut self,
+            src: &[f32],
+            dst: &'a mut [i16],
+        ) -> Result<&'a mut [i16], SliceError<f32>> {
+            let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+            for (i, (y, &x)) in dst.iter_mut().zip(src).enumerate() {
+                check::<i16>(i, x)?;
+
+                // offset into the positive range, where truncating `x + 0.5`
+                // rounds, and saturate
+                let x = x + self.noise() + 32768.5;
+                *y = (x as i32 - 32768).clamp(i16::MIN.into(), i16::MAX.into()) as i16;
+            }
+
+            Ok(dst)
+        }
+    }
+}
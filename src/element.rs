@@ -0,0 +1,97 @@
+//! The error of the casts of many elements at once
+
+use core::fmt;
+
+use crate::Error;
+
+/// The first element of a bulk cast that failed to be casted
+///
+/// ```
+/// use cast::slice::SliceError;
+///
+/// # fn main() {
+/// let e = match cast::slice::u8(&[1i32, 2, 300, -1], &mut [0; 4]) {
+///     Err(SliceError::Element(e)) => e,
+///     _ => unreachable!(),
+/// };
+///
+/// assert_eq!(e.index(), 2);
+/// assert_eq!(e.value(), 300);
+/// assert_eq!(e.error(), cast::Error::Overflow);
+/// assert_eq!(
+///     e.to_string(),
+///     "Element 2 (300): Overflow during numeric conversion"
+/// );
+/// # }
+/// ```
+///
+/// `Index` is the type of the position of the element: `usize` for
+/// one-dimensional collections, or the coordinates of the element for
+/// multi-dimensional ones.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ElementError<Src, Index = usize> {
+    index: Index,
+    value: Src,
+    error: Error,
+}
+
+impl<Src, Index> ElementError<Src, Index> {
+    #[inline]
+    pub(crate) fn new(index: Index, value: Src, error: Error) -> Self {
+        ElementError {
+            index,
+            value,
+            error,
+        }
+    }
+
+    /// The position of the element
+    #[inline]
+    pub fn index(&self) -> Index
+    where
+        Index: Clone,
+    {
+        self.index.clone()
+    }
+
+    /// The value of the element
+    #[inline]
+    pub fn value(&self) -> Src
+    where
+        Src: Copy,
+    {
+        self.value
+    }
+
+    /// Why it failed
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+}
+
+impl<Src: fmt::Display, Index: fmt::Debug> fmt::Display for ElementError<Src, Index> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `Debug` prints both integers and tuples of coordinates
+        write!(
+            f,
+            "Element {:?} ({}): {}",
+            self.index, self.value, self.error
+        )
+    }
+}
+
+impl<Src, Index> core::error::Error for ElementError<Src, Index>
+where
+    Src: fmt::Debug + fmt::Display,
+    Index: fmt::Debug,
+{
+    // no `source`: `Display` already prints the error of the element
+}
+
+impl<Src, Index> core::convert::From<ElementError<Src, Index>> for Error {
+    #[inline]
+    fn from(e: ElementError<Src, Index>) -> Error {
+        e.error
+    }
+}
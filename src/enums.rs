@@ -0,0 +1,113 @@
+//! Casts of `num_enum` enums from and into the primitives
+
+use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
+
+use crate::{fail, EnumError, Error, From, IntoResult, TryFromError};
+
+/// Opt-in casts of an enum that derives `num_enum::TryFromPrimitive` from
+/// every primitive, and, if it also derives `num_enum::IntoPrimitive`, into
+/// every primitive
+///
+/// A cast from a primitive first casts it into the `#[repr]` of the enum, and
+/// then looks for the variant with that discriminant. Like the casts into the
+/// enums that derive `cast::From`, it fails with `EnumError`, which converts
+/// into this crate's `Error`. A cast into a primitive casts the discriminant.
+///
+/// ```
+/// use cast::{EnumError, Error, From as _0, NumEnum};
+/// use num_enum::{IntoPrimitive, TryFromPrimitive};
+///
+/// #[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop = 0,
+///     Halt = 0xff,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(NumEnum::cast(255u32), Ok(NumEnum(Opcode::Halt)));
+/// assert_eq!(NumEnum::<Opcode>::cast(256u32), Err(EnumError::Cast(Error::Overflow)));
+/// assert_eq!(NumEnum::<Opcode>::cast(-1i64), Err(EnumError::Cast(Error::NegOverflow)));
+/// assert_eq!(NumEnum::<Opcode>::cast(1u32), Err(EnumError::UnknownDiscriminant(1)));
+///
+/// assert_eq!(cast::u16(NumEnum(Opcode::Halt)), 255);
+/// assert!(cast::i8(NumEnum(Opcode::Halt)).is_err());
+/// # }
+/// ```
+///
+/// `Fallback` works with these enums too, but only from their `#[repr]`.
+///
+/// *This API requires the `num_enum` Cargo feature*
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NumEnum<E>(pub E);
+
+impl<E> NumEnum<E> {
+    /// Unwraps the enum
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+/// An unknown discriminant is mapped to `Error::Overflow`, like
+/// `EnumError::UnknownDiscriminant` is
+impl<E> TryFromError for TryFromPrimitiveError<E>
+where
+    E: TryFromPrimitive,
+{
+    #[inline]
+    fn into_error(self) -> Error {
+        Error::Overflow
+    }
+}
+
+impl<Src, E> From<Src> for NumEnum<E>
+where
+    E: TryFromPrimitive,
+    E::Primitive: From<Src>,
+    <E::Primitive as From<Src>>::Output: IntoResult<E::Primitive>,
+{
+    type Output = Result<NumEnum<E>, EnumError<E::Primitive>>;
+    type Error = EnumError<E::Primitive>;
+    const FALLIBLE: bool = true;
+
+    #[inline]
+    fn cast(src: Src) -> Self::Output {
+        let discriminant = <E::Primitive as From<Src>>::cast(src)
+            .into_result()
+            .map_err(EnumError::Cast)?;
+
+        E::try_from_primitive(discriminant)
+            .map(NumEnum)
+            .map_err(|_| fail::<Src, E, _>(EnumError::UnknownDiscriminant(discriminant)))
+    }
+}
+
+macro_rules! into {
+    ($($dst:ident),+) => {
+        $(
+            dst! {
+                $dst,
+
+                impl<E> From<NumEnum<E>> for $dst
+                where
+                    E: TryFromPrimitive,
+                    E::Primitive: core::convert::From<E>,
+                    $dst: From<E::Primitive>,
+                {
+                    type Output = <$dst as From<E::Primitive>>::Output;
+                    type Error = <$dst as From<E::Primitive>>::Error;
+                    const FALLIBLE: bool = <$dst as From<E::Primitive>>::FALLIBLE;
+                    const FALLIBILITY: crate::Fallibility = <$dst as From<E::Primitive>>::FALLIBILITY;
+
+                    #[inline]
+                    fn cast(src: NumEnum<E>) -> Self::Output {
+                        <$dst as From<E::Primitive>>::cast(src.0.into())
+                    }
+                }
+            }
+        )+
+    }
+}
+
+into!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,97 @@
+//! `core::convert::TryFrom` based casts for types this crate doesn't know about
+
+use core::convert::{Infallible, TryFrom};
+use core::num::TryFromIntError;
+
+use crate::{fail, Error, From};
+
+/// Errors of `core::convert::TryFrom` implementations that can be mapped into
+/// this crate's `Error`
+pub trait TryFromError {
+    /// Maps this error into this crate's `Error`
+    fn into_error(self) -> Error;
+}
+
+impl TryFromError for Error {
+    #[inline]
+    fn into_error(self) -> Error {
+        self
+    }
+}
+
+impl TryFromError for Infallible {
+    #[inline]
+    fn into_error(self) -> Error {
+        match self {}
+    }
+}
+
+/// `TryFromIntError` doesn't say why the conversion failed, so it's always
+/// mapped to `Error::Overflow`, even when the source was negative or, for the
+/// `NonZero` integers, zero
+impl TryFromError for TryFromIntError {
+    #[inline]
+    fn into_error(self) -> Error {
+        Error::Overflow
+    }
+}
+
+/// Opt-in cast into `T` that goes through `core::convert::TryFrom`
+///
+/// `Fallback<T>` implements this crate's `From` trait for every `Src` for
+/// which `T: TryFrom<Src>` holds, as long as the `TryFrom` error can be mapped
+/// into this crate's `Error` (see `TryFromError`). Use it to cast between
+/// pairs of types that this crate doesn't provide implementations for.
+///
+/// ```
+/// use core::convert::TryFrom;
+///
+/// use cast::{Error, Fallback, From as _0};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Percent(u8);
+///
+/// impl TryFrom<u32> for Percent {
+///     type Error = Error;
+///
+///     fn try_from(x: u32) -> Result<Percent, Error> {
+///         if x > 100 {
+///             Err(Error::Overflow)
+///         } else {
+///             Ok(Percent(x as u8))
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Fallback::cast(42u32).map(Fallback::into_inner), Ok(Percent(42)));
+/// assert_eq!(Fallback::<Percent>::cast(101u32), Err(Error::Overflow));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Fallback<T>(pub T);
+
+impl<T> Fallback<T> {
+    /// Unwraps the value
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<Src, Dst> From<Src> for Fallback<Dst>
+where
+    Dst: TryFrom<Src>,
+    Dst::Error: TryFromError,
+{
+    type Output = Result<Fallback<Dst>, Error>;
+    type Error = Error;
+    const FALLIBLE: bool = true;
+
+    #[inline]
+    fn cast(src: Src) -> Self::Output {
+        Dst::try_from(src)
+            .map(Fallback)
+            .map_err(|e| fail::<Src, Dst, _>(e.into_error()))
+    }
+}
@@ -0,0 +1,108 @@
+//! Floats that have been checked to be neither infinite nor NaN
+
+use crate::{fail, Error, Primitive};
+
+/// A float that has been checked to be neither infinite nor NaN
+///
+/// Its casts into integers skip those checks and can only fail with a
+/// `RangeError`. Validating the input once, up front, keeps the checks out of
+/// hot loops:
+///
+/// ```
+/// use cast::{Finite, RangeError};
+///
+/// fn quantize(xs: &[Finite<f32>]) -> Result<Vec<i16>, RangeError> {
+///     xs.iter().map(|&x| cast::i16(x)).collect()
+/// }
+///
+/// # fn main() {
+/// let xs = [0.5f32, -1.5, 300.];
+/// let xs: Vec<_> = xs.iter().map(|&x| Finite::new(x).unwrap()).collect();
+///
+/// assert_eq!(quantize(&xs), Ok(vec![0, -1, 300]));
+/// assert!(Finite::new(f32::NAN).is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Finite<T>(T);
+
+impl<T> Finite<T>
+where
+    T: Float,
+{
+    /// Checks that `x` is neither infinite nor NaN
+    #[inline]
+    pub fn new(x: T) -> Result<Self, Error> {
+        if x.is_nan() {
+            Err(fail::<T, Self, _>(Error::NaN))
+        } else if x.is_infinite() {
+            Err(fail::<T, Self, _>(Error::Infinite))
+        } else {
+            Ok(Finite(x))
+        }
+    }
+
+    /// Returns the wrapped float
+    #[inline]
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+/// The floats that can be wrapped in a `Finite`
+pub trait Float: Primitive {
+    /// Whether `self` is NaN
+    fn is_nan(self) -> bool;
+
+    /// Whether `self` is positive or negative infinity
+    fn is_infinite(self) -> bool;
+}
+
+macro_rules! finite {
+    ($($src:ident => $($dst:ident),+;)+) => {
+        $(
+            impl Float for $src {
+                #[inline]
+                fn is_nan(self) -> bool {
+                    $src::is_nan(self)
+                }
+
+                #[inline]
+                fn is_infinite(self) -> bool {
+                    $src::is_infinite(self)
+                }
+            }
+
+            $(
+                dst! {
+                    $dst,
+
+                    impl crate::From<Finite<$src>> for $dst {
+                        type Output = Result<$dst, crate::RangeError>;
+                        type Error = crate::RangeError;
+                        const FALLIBLE: bool = true;
+
+                        #[inline]
+                        fn cast(src: Finite<$src>) -> Self::Output {
+                            // the same bounds as the cast from `$src`
+                            let range = <$dst as crate::SourceRange<$src>>::source_range();
+
+                            if src.0 > *range.end() {
+                                Err(fail::<$src, $dst, _>(crate::RangeError::Overflow))
+                            } else if src.0 < *range.start() {
+                                Err(fail::<$src, $dst, _>(crate::RangeError::NegOverflow))
+                            } else {
+                                Ok(src.0 as $dst)
+                            }
+                        }
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+finite! {
+    f32 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+    f64 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+}
@@ -0,0 +1,194 @@
+//! The narrowest integer type that can represent a value
+
+/// A fixed-width integer type
+///
+/// ```
+/// use cast::IntType;
+///
+/// # fn main() {
+/// assert_eq!(IntType::I16.bits(), 16);
+/// assert!(IntType::I16.is_signed());
+/// assert_eq!(IntType::U64.to_string(), "u64");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum IntType {
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u128`
+    U128,
+    /// `i8`
+    I8,
+    /// `i16`
+    I16,
+    /// `i32`
+    I32,
+    /// `i64`
+    I64,
+    /// `i128`
+    I128,
+}
+
+impl IntType {
+    /// The width of this type in bits
+    pub const fn bits(self) -> u32 {
+        match self {
+            IntType::U8 | IntType::I8 => 8,
+            IntType::U16 | IntType::I16 => 16,
+            IntType::U32 | IntType::I32 => 32,
+            IntType::U64 | IntType::I64 => 64,
+            IntType::U128 | IntType::I128 => 128,
+        }
+    }
+
+    /// Whether this type can represent negative numbers
+    pub const fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128
+        )
+    }
+
+    /// The name of this type, e.g. `"u8"`
+    pub const fn name(self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+        }
+    }
+}
+
+impl core::fmt::Display for IntType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Integers whose values can be measured against the fixed-width integer types
+///
+/// See `bits_required` and `smallest_fitting_type`.
+pub trait Fit: Copy {
+    /// The number of bits needed to represent `self` in a type of the same
+    /// signedness, including the sign bit for signed types
+    fn bits_required(self) -> u32;
+
+    /// The narrowest type, of the same signedness, that can represent `self`
+    fn smallest_fitting_type(self) -> IntType;
+}
+
+macro_rules! fit {
+    ($($ty:ident: $($narrow:ident),+;)+) => {
+        $(
+            impl Fit for $ty {
+                #[inline]
+                fn bits_required(self) -> u32 {
+                    bits_required!($ty, self)
+                }
+
+                #[inline]
+                fn smallest_fitting_type(self) -> IntType {
+                    let bits = self.bits_required();
+                    $(
+                        if bits <= IntType::$narrow.bits() {
+                            return IntType::$narrow;
+                        }
+                    )+
+                    unreachable!()
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! bits_required {
+    (u8, $x:expr) => {
+        u8::BITS - $x.leading_zeros()
+    };
+    (u16, $x:expr) => {
+        u16::BITS - $x.leading_zeros()
+    };
+    (u32, $x:expr) => {
+        u32::BITS - $x.leading_zeros()
+    };
+    (u64, $x:expr) => {
+        u64::BITS - $x.leading_zeros()
+    };
+    (u128, $x:expr) => {
+        u128::BITS - $x.leading_zeros()
+    };
+    (usize, $x:expr) => {
+        usize::BITS - $x.leading_zeros()
+    };
+    // the sign bit plus the magnitude; `!x` maps negative values onto
+    // non-negative ones with the same number of significant bits
+    ($ity:ident, $x:expr) => {{
+        let x = $x;
+        let magnitude = if x < 0 { !x } else { x };
+        $ity::BITS - magnitude.leading_zeros() + 1
+    }};
+}
+
+fit! {
+    u8: U8;
+    u16: U8, U16;
+    u32: U8, U16, U32;
+    u64: U8, U16, U32, U64;
+    u128: U8, U16, U32, U64, U128;
+    usize: U8, U16, U32, U64, U128;
+    i8: I8;
+    i16: I8, I16;
+    i32: I8, I16, I32;
+    i64: I8, I16, I32, I64;
+    i128: I8, I16, I32, I64, I128;
+    isize: I8, I16, I32, I64, I128;
+}
+
+/// The number of bits needed to represent `x` in a type of the same signedness
+///
+/// For signed types this includes the sign bit.
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::bits_required(0u128), 0);
+/// assert_eq!(cast::bits_required(255u128), 8);
+/// assert_eq!(cast::bits_required(0i128), 1);
+/// assert_eq!(cast::bits_required(127i128), 8);
+/// assert_eq!(cast::bits_required(-128i128), 8);
+/// assert_eq!(cast::bits_required(128i128), 9);
+/// # }
+/// ```
+#[inline]
+pub fn bits_required<T: Fit>(x: T) -> u32 {
+    x.bits_required()
+}
+
+/// The narrowest fixed-width integer type, of the same signedness as `T`,
+/// that can represent `x`
+///
+/// ```
+/// use cast::IntType;
+///
+/// # fn main() {
+/// assert_eq!(cast::smallest_fitting_type(300u128), IntType::U16);
+/// assert_eq!(cast::smallest_fitting_type(-129i128), IntType::I16);
+/// assert_eq!(cast::smallest_fitting_type(-128i128), IntType::I8);
+/// # }
+/// ```
+#[inline]
+pub fn smallest_fitting_type<T: Fit>(x: T) -> IntType {
+    x.smallest_fitting_type()
+}
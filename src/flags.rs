@@ -0,0 +1,103 @@
+//! Casts of `bitflags` types from and into the primitives
+
+use bitflags::{Bits, Flags};
+
+use crate::{FlagsError, From, IntoResult};
+
+/// Opt-in casts of a type generated by `bitflags` from and into every
+/// primitive
+///
+/// A cast from a primitive first casts it into the bits type of the flags, and
+/// then fails if any of the bits set doesn't correspond to a flag:
+/// `from_bits_truncate` and `from_bits_retain` quietly drop or keep those bits,
+/// which is rarely what a decoder of untrusted input wants. A cast into a
+/// primitive casts the bits.
+///
+/// ```
+/// use bitflags::bitflags;
+/// use cast::{BitFlags, Error, FlagsError, From as _0};
+///
+/// bitflags! {
+///     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///     struct Permissions: u8 {
+///         const READ = 0b001;
+///         const WRITE = 0b010;
+///         const EXECUTE = 0b100;
+///     }
+/// }
+///
+/// # fn main() {
+/// let rw = BitFlags::<Permissions>::cast(3u32);
+/// assert_eq!(rw, Ok(BitFlags(Permissions::READ | Permissions::WRITE)));
+///
+/// assert_eq!(BitFlags::<Permissions>::cast(0x18u32), Err(FlagsError::UnknownBits(0x18)));
+/// assert_eq!(BitFlags::<Permissions>::cast(256u32), Err(FlagsError::Cast(Error::Overflow)));
+///
+/// assert_eq!(cast::u32(BitFlags(Permissions::all())), 7);
+/// # }
+/// ```
+///
+/// *This API requires the `bitflags` Cargo feature*
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BitFlags<F>(pub F);
+
+impl<F> BitFlags<F> {
+    /// Unwraps the flags
+    #[inline]
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<Src, F> From<Src> for BitFlags<F>
+where
+    F: Flags,
+    F::Bits: From<Src>,
+    <F::Bits as From<Src>>::Output: IntoResult<F::Bits>,
+{
+    type Output = Result<BitFlags<F>, FlagsError<F::Bits>>;
+    type Error = FlagsError<F::Bits>;
+    const FALLIBLE: bool = true;
+
+    #[inline]
+    fn cast(src: Src) -> Self::Output {
+        let bits = <F::Bits as From<Src>>::cast(src)
+            .into_result()
+            .map_err(FlagsError::Cast)?;
+
+        let unknown = bits & !F::all().bits();
+        if unknown == F::Bits::EMPTY {
+            Ok(BitFlags(F::from_bits_retain(bits)))
+        } else {
+            Err(crate::fail::<Src, F, _>(FlagsError::UnknownBits(unknown)))
+        }
+    }
+}
+
+macro_rules! into {
+    ($($dst:ident),+) => {
+        $(
+            dst! {
+                $dst,
+
+                impl<F> From<BitFlags<F>> for $dst
+                where
+                    F: Flags,
+                    $dst: From<F::Bits>,
+                {
+                    type Output = <$dst as From<F::Bits>>::Output;
+                    type Error = <$dst as From<F::Bits>>::Error;
+                    const FALLIBLE: bool = <$dst as From<F::Bits>>::FALLIBLE;
+                    const FALLIBILITY: crate::Fallibility = <$dst as From<F::Bits>>::FALLIBILITY;
+
+                    #[inline]
+                    fn cast(src: BitFlags<F>) -> Self::Output {
+                        <$dst as From<F::Bits>>::cast(src.0.bits())
+                    }
+                }
+            }
+        )+
+    }
+}
+
+into!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,41 @@
+//! Element-wise casts of `heapless::Vec`s
+
+use crate::{ElementError, From, IntoResult};
+
+/// Checked cast of every element of a `heapless::Vec`
+///
+/// The output has the same capacity, `N`, as the input.
+///
+/// ```
+/// use heapless::Vec;
+///
+/// # fn main() {
+/// let readings: Vec<i16, 4> = Vec::from_slice(&[12, -3, 40]).unwrap();
+///
+/// let e = cast::heapless_vec::<u8, _, 4>(readings).unwrap_err();
+/// assert_eq!((e.index(), e.value()), (1, -3));
+///
+/// let samples: Vec<u8, 8> = Vec::from_slice(&[0, 255]).unwrap();
+/// let pixels: Vec<f32, 8> = cast::heapless_vec(samples).unwrap();
+/// assert_eq!(pixels, [0., 255.]);
+/// # }
+/// ```
+///
+/// *This API requires the `heapless` Cargo feature*
+#[inline]
+pub fn heapless_vec<Dst, Src, const N: usize>(
+    v: heapless::Vec<Src, N>,
+) -> Result<heapless::Vec<Dst, N>, ElementError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    v.into_iter()
+        .enumerate()
+        .map(|(index, x)| match Dst::cast(x).into_result() {
+            Ok(y) => Ok(y),
+            Err(error) => Err(ElementError::new(index, x, error)),
+        })
+        .collect()
+}
@@ -0,0 +1,65 @@
+//! Global hook invoked whenever a cast fails
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+
+use crate::Error;
+
+// a critical section, unlike a lock, can't deadlock when an interrupt handler
+// fails a cast while the hook is being replaced
+static HOOK: Mutex<Cell<Option<Hook>>> = Mutex::new(Cell::new(None));
+
+type Hook = fn(&Error);
+
+/// Registers a function that will be called with the error of every failed
+/// cast
+///
+/// Only one hook can be registered at a time; registering a new one replaces
+/// the previous one. This is meant for diagnostics, e.g. to log or count the
+/// conversion failures of a program in a single place.
+///
+/// The hook is stored in a `critical_section::Mutex`. With the `std` feature
+/// enabled, this crate enables `critical-section`'s implementation for
+/// hosted targets; otherwise the application has to provide one.
+///
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static FAILURES: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn count(_: &cast::Error) {
+///     FAILURES.fetch_add(1, Ordering::Relaxed);
+/// }
+///
+/// # fn main() {
+/// cast::set_failure_hook(count);
+///
+/// assert!(cast::u8(256u16).is_err());
+/// assert!(cast::i8(-129i16).is_err());
+/// assert_eq!(FAILURES.load(Ordering::Relaxed), 2);
+///
+/// cast::clear_failure_hook();
+/// # }
+/// ```
+///
+/// *This API requires the `failure-hook` Cargo feature*
+pub fn set_failure_hook(hook: fn(&Error)) {
+    critical_section::with(|cs| HOOK.borrow(cs).set(Some(hook)));
+}
+
+/// Unregisters the hook registered with `set_failure_hook`, if any
+///
+/// *This API requires the `failure-hook` Cargo feature*
+pub fn clear_failure_hook() {
+    critical_section::with(|cs| HOOK.borrow(cs).set(None));
+}
+
+pub(crate) fn call(error: &Error) {
+    // copied out, so the hook doesn't run inside of the critical section
+    let hook = critical_section::with(|cs| HOOK.borrow(cs).get());
+
+    if let Some(hook) = hook {
+        hook(error);
+    }
+}
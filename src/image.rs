@@ -0,0 +1,190 @@
+//! Conversions between the channel types of `image` pixels
+//!
+//! The `image` crate stores channels as `u8` (`0..=255`), `u16` (`0..=65535`)
+//! or `f32` (`0.0..=1.0`), and the same color has a different value in each.
+//! The conversions of this module scale the channels between those ranges,
+//! rounding to the nearest integer, and check the float channels: a NaN, an
+//! infinity or a value outside of `0.0..=1.0` is an error, rather than a
+//! silently clamped pixel.
+//!
+//! ```
+//! use cast::image::{self as convert, ChannelError};
+//! use cast::Error;
+//! use image::{ImageBuffer, Rgb};
+//!
+//! # fn main() {
+//! assert_eq!(convert::pixel::<u16, _>(Rgb([0u8, 128, 255])), Ok(Rgb([0, 32896, 65535])));
+//! assert_eq!(convert::pixel::<u8, _>(Rgb([0.5f32, 1., 0.])), Ok(Rgb([128, 255, 0])));
+//! assert_eq!(convert::pixel::<u8, _>(Rgb([0.5f32, 1.5, 0.])), Err(Error::Overflow));
+//!
+//! let hdr = ImageBuffer::from_raw(2, 1, vec![0.25f32, 0., 1., 0., -0.5, 0.]).unwrap();
+//! let e: ChannelError<f32> = convert::buffer::<u8, Rgb<f32>, _>(&hdr).unwrap_err();
+//! assert_eq!((e.index(), e.value(), e.error()), ((1, 0, 1), -0.5, Error::NegOverflow));
+//! # }
+//! ```
+//!
+//! *This module requires the `image` Cargo feature*
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use image::{ImageBuffer, Luma, LumaA, Rgb, Rgba};
+
+use crate::{fail, ElementError, Error};
+
+mod sealed {
+    use crate::Error;
+
+    pub trait Channel: image::Primitive {
+        /// The value scaled into `0.0..=1.0`
+        fn to_unit(self) -> Result<f64, Error>;
+
+        /// `x` is in `0.0..=1.0`
+        fn from_unit(x: f64) -> Self;
+    }
+
+    pub trait Pixel: image::Pixel<Subpixel: Channel> {
+        type Map<T: Channel>;
+
+        fn try_map<T: Channel>(self) -> Result<Self::Map<T>, Error>;
+    }
+}
+
+use self::sealed::{Channel, Pixel};
+
+macro_rules! int {
+    ($($ty:ident),+) => {
+        $(
+            impl Channel for $ty {
+                #[inline]
+                fn to_unit(self) -> Result<f64, Error> {
+                    Ok(f64::from(self) / f64::from($ty::MAX))
+                }
+
+                #[inline]
+                fn from_unit(x: f64) -> $ty {
+                    // `x` is not negative so this rounds to the nearest integer
+                    (x * f64::from($ty::MAX) + 0.5) as $ty
+                }
+            }
+        )+
+    }
+}
+
+int!(u8, u16);
+
+impl Channel for f32 {
+    #[inline]
+    fn to_unit(self) -> Result<f64, Error> {
+        if self.is_nan() {
+            Err(Error::NaN)
+        } else if self.is_infinite() {
+            Err(Error::Infinite)
+        } else if self > 1. {
+            Err(Error::Overflow)
+        } else if self < 0. {
+            Err(Error::NegOverflow)
+        } else {
+            Ok(f64::from(self))
+        }
+    }
+
+    #[inline]
+    fn from_unit(x: f64) -> f32 {
+        x as f32
+    }
+}
+
+macro_rules! pixel {
+    ($($pixel:ident: $n:expr),+) => {
+        $(
+            pixel!(@channels $pixel, $n, u8, u16, f32);
+        )+
+    };
+    (@channels $pixel:ident, $n:expr, $($ty:ident),+) => {
+        $(
+            impl Pixel for $pixel<$ty> {
+                type Map<T: Channel> = $pixel<T>;
+
+                #[inline]
+                fn try_map<T: Channel>(self) -> Result<$pixel<T>, Error> {
+                    let mut channels = [T::DEFAULT_MIN_VALUE; $n];
+                    for (y, &x) in channels.iter_mut().zip(&self.0) {
+                        *y = channel(x)?;
+                    }
+                    Ok($pixel(channels))
+                }
+            }
+        )+
+    };
+}
+
+pixel!(Luma: 1, LumaA: 2, Rgb: 3, Rgba: 4);
+
+/// The channel of an image that failed to be converted, at the coordinates
+/// `(x, y, channel)`
+pub type ChannelError<Src> = ElementError<Src, (u32, u32, usize)>;
+
+/// Converts a channel value from the range of `Src` into the range of `Dst`
+///
+/// `Src` and `Dst` are `u8`, `u16` or `f32`.
+#[inline]
+pub fn channel<Dst, Src>(x: Src) -> Result<Dst, Error>
+where
+    Src: Channel,
+    Dst: Channel,
+{
+    match x.to_unit() {
+        Ok(x) => Ok(Dst::from_unit(x)),
+        Err(error) => Err(fail::<Src, Dst, _>(error)),
+    }
+}
+
+/// Converts every channel of `pixel`, a `Luma`, `LumaA`, `Rgb` or `Rgba`, into
+/// `Dst`; see `channel`
+#[inline]
+pub fn pixel<Dst, P>(pixel: P) -> Result<P::Map<Dst>, Error>
+where
+    P: Pixel,
+    Dst: Channel,
+{
+    pixel.try_map()
+}
+
+/// `P` with its channels converted into `Dst`
+type Converted<P, Dst> = ImageBuffer<<P as Pixel>::Map<Dst>, Vec<Dst>>;
+
+/// Converts every channel of `image` into `Dst`; see `channel`
+///
+/// The error reports the first channel that couldn't be converted.
+pub fn buffer<Dst, P, C>(
+    image: &ImageBuffer<P, C>,
+) -> Result<Converted<P, Dst>, ChannelError<P::Subpixel>>
+where
+    P: Pixel,
+    P::Map<Dst>: image::Pixel<Subpixel = Dst>,
+    Dst: Channel,
+    C: Deref<Target = [P::Subpixel]>,
+{
+    let width = image.width() as usize;
+    let channels = usize::from(P::CHANNEL_COUNT);
+
+    // `image` derefs into the samples of its pixels, without any excess capacity
+    let samples: &[P::Subpixel] = image;
+    let samples = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            channel(x).map_err(|error| {
+                let pixel = i / channels;
+                let index = ((pixel % width) as u32, (pixel / width) as u32, i % channels);
+                ElementError::new(index, x, error)
+            })
+        })
+        .collect::<Result<Vec<Dst>, _>>()?;
+
+    // same dimensions and number of channels per pixel as `image`
+    Ok(ImageBuffer::from_raw(image.width(), image.height(), samples).unwrap())
+}
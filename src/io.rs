@@ -0,0 +1,531 @@
+//! Readers and writers that cast streams of samples on the fly
+//!
+//! `Reader` wraps a `std::io::Read` that produces numbers of type `Src`, in a
+//! given byte order, and yields them casted into `Dst`. `Writer` does the
+//! opposite: it takes numbers of type `Src`, casts them into `Dst` and writes
+//! their bytes into a `std::io::Write`. Samples that fail to be casted are
+//! reported as errors of kind `InvalidData`, whose payload is the
+//! `ElementError` with the position of the sample in the stream.
+//!
+//! The casts keep the values, so the `i16` sample `-32768` reads as the `f32`
+//! `-32768.0`. Audio code usually wants the samples normalized instead, with
+//! full scale mapped onto `-1.0..1.0`: `read_normalized` and
+//! `write_normalized` do that scaling between integer PCM samples and floats.
+//!
+//! ```
+//! use cast::bytes::ByteOrder;
+//! use cast::io::{Reader, Writer};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! // 16-bit little endian PCM, e.g. the data of a WAV file
+//! let pcm: &[u8] = &[0x00, 0x80, 0xff, 0xff, 0x01, 0x00];
+//!
+//! let mut reader = Reader::<_, i16, f32>::new(pcm, ByteOrder::LittleEndian);
+//! let mut samples = [0.; 8];
+//! let n = reader.read_samples(&mut samples)?;
+//! assert_eq!(&samples[..n], [-32768., -1., 1.]);
+//!
+//! let mut writer = Writer::<_, f32, u8>::new(Vec::new(), ByteOrder::BigEndian);
+//! writer.write_samples(&[1., 2.])?;
+//! assert_eq!(writer.into_inner(), [1, 2]);
+//!
+//! let mut writer = Writer::<_, f32, u8>::new(Vec::new(), ByteOrder::BigEndian);
+//! let e = writer.write_samples(&[1., -2.]).unwrap_err();
+//! assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+//!
+//! // the same PCM data, normalized
+//! let mut reader = Reader::<_, i16, f32>::new(pcm, ByteOrder::LittleEndian);
+//! let n = reader.read_normalized(&mut samples)?;
+//! assert_eq!(&samples[..n], [-1., -1. / 32768., 1. / 32768.]);
+//!
+//! let mut writer = Writer::<_, f32, i16>::new(Vec::new(), ByteOrder::LittleEndian);
+//! writer.write_normalized(&[-1., 0.5])?;
+//! assert_eq!(writer.into_inner(), [0x00, 0x80, 0x00, 0x40]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Parsers of binary formats that read the fields themselves can send cast
+//! failures down the same error channel: `Error` converts into an `io::Error`
+//! of kind `InvalidData`, so `?` works in functions that return `io::Result`,
+//! and `ResultExt` adds context that says which field was wrong.
+//!
+//! ```
+//! use std::io::{self, Read};
+//!
+//! use cast::io::ResultExt;
+//!
+//! struct Header {
+//!     channels: u8,
+//!     bits: u16,
+//! }
+//!
+//! fn header(mut r: impl Read) -> io::Result<Header> {
+//!     let mut buf = [0; 8];
+//!     r.read_exact(&mut buf)?;
+//!
+//!     let channels = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+//!     let bits = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+//!     Ok(Header {
+//!         channels: cast::u8(channels).context("number of channels")?,
+//!         bits: cast::u16(bits).context("bits per sample")?,
+//!     })
+//! }
+//!
+//! # fn main() {
+//! let e = header(&[0, 1, 0, 0, 0, 0, 0, 0][..]).err().unwrap();
+//! assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+//! assert_eq!(e.to_string(), "number of channels: Overflow during numeric conversion");
+//! # }
+//! ```
+//!
+//! *This module requires the `std` Cargo feature*
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::io::{self, ErrorKind, Read, Write};
+
+use self::sealed::{Float, Pcm};
+use crate::bytes::sealed::Number;
+use crate::bytes::ByteOrder;
+use crate::{fail, ElementError, Error, From, IntoResult};
+
+/// Size of the stack buffer used to move bytes from and to the inner reader or
+/// writer; a multiple of the size of every number
+const BUFFER: usize = 4096;
+
+pub(crate) mod sealed {
+    use crate::Error;
+
+    /// The integer PCM samples
+    pub trait Pcm: Copy {
+        /// `self` scaled into `-1.0..1.0`
+        fn normalize(self) -> f64;
+
+        /// `x`, in `-1.0..1.0`, scaled into the nearest sample
+        fn denormalize(x: f64) -> Result<Self, Error>;
+    }
+
+    /// The floats that normalized samples are read into and written from
+    pub trait Float: Copy {
+        fn from_f64(x: f64) -> Self;
+        fn to_f64(self) -> f64;
+    }
+}
+
+/// Implements `Pcm` for samples whose silence is `$zero`; full scale is
+/// `2^(BITS - 1)` away from it
+macro_rules! pcm {
+    ($($ty:ident: $zero:expr;)+) => {
+        $(
+            impl Pcm for $ty {
+                #[inline]
+                fn normalize(self) -> f64 {
+                    const FULL_SCALE: f64 = (1u64 << ($ty::BITS - 1)) as f64;
+
+                    (f64::from(self) - $zero) / FULL_SCALE
+                }
+
+                #[inline]
+                fn denormalize(x: f64) -> Result<Self, Error> {
+                    const FULL_SCALE: f64 = (1u64 << ($ty::BITS - 1)) as f64;
+
+                    let y = (x * FULL_SCALE).round() + $zero;
+
+                    Err(fail::<f64, $ty, _>(if y.is_nan() {
+                        Error::NaN
+                    } else if y.is_infinite() {
+                        Error::Infinite
+                    } else if y < $ty::MIN as f64 {
+                        Error::NegOverflow
+                    } else if y > $ty::MAX as f64 {
+                        Error::Overflow
+                    } else {
+                        return Ok(y as $ty);
+                    }))
+                }
+            }
+        )+
+    };
+}
+
+// the unsigned samples are offset binary
+pcm! {
+    i8: 0.;
+    i16: 0.;
+    i32: 0.;
+    u8: 128.;
+    u16: 32_768.;
+    u32: 2_147_483_648.;
+}
+
+impl Float for f32 {
+    #[inline]
+    fn from_f64(x: f64) -> f32 {
+        // the normalized samples are in range, so this only rounds
+        x as f32
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Float for f64 {
+    #[inline]
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+fn invalid<Src>(index: usize, value: Src, error: Error) -> io::Error
+where
+    Src: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    io::Error::new(
+        ErrorKind::InvalidData,
+        ElementError::new(index, value, error),
+    )
+}
+
+/// The error is the payload of the `io::Error`, so it can be recovered with
+/// `get_ref` and `downcast_ref::<cast::Error>`
+impl core::convert::From<Error> for io::Error {
+    #[inline]
+    fn from(error: Error) -> io::Error {
+        io::Error::new(ErrorKind::InvalidData, error)
+    }
+}
+
+/// A cast error with a description of what was being casted; the payload of
+/// the `io::Error`s created by `ResultExt`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ContextError<C> {
+    context: C,
+    error: Error,
+}
+
+impl<C> ContextError<C> {
+    /// What was being casted
+    #[inline]
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Why it failed
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for ContextError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.error)
+    }
+}
+
+// `Display` prints the error after the context, so there's no `source`
+impl<C: fmt::Debug + fmt::Display> std::error::Error for ContextError<C> {}
+
+/// Adds context to the results of the casts, turning them into `io::Result`s
+pub trait ResultExt<T> {
+    /// Turns a cast error into an `io::Error` of kind `InvalidData` that says
+    /// what was being casted
+    ///
+    /// The payload of the `io::Error` is a `ContextError<C>`.
+    fn context<C>(self, context: C) -> io::Result<T>
+    where
+        C: fmt::Debug + fmt::Display + Send + Sync + 'static;
+
+    /// Like `context`, but the context is only computed if the cast failed
+    fn with_context<C, F>(self, f: F) -> io::Result<T>
+    where
+        C: fmt::Debug + fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    Error: core::convert::From<E>,
+{
+    #[inline]
+    fn context<C>(self, context: C) -> io::Result<T>
+    where
+        C: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        self.with_context(|| context)
+    }
+
+    #[inline]
+    fn with_context<C, F>(self, f: F) -> io::Result<T>
+    where
+        C: fmt::Debug + fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| {
+            let error = ContextError {
+                context: f(),
+                error: Error::from(e),
+            };
+
+            io::Error::new(ErrorKind::InvalidData, error)
+        })
+    }
+}
+
+/// Reads numbers of type `Src` from a `std::io::Read` and casts them into
+/// `Dst`; see the module documentation
+#[derive(Debug)]
+pub struct Reader<R, Src, Dst> {
+    inner: R,
+    order: ByteOrder,
+    /// The bytes of an incomplete number, in `carry[..carried]`
+    carry: [u8; 16],
+    carried: usize,
+    /// Number of samples read so far
+    position: usize,
+    _types: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<R, Src, Dst> Reader<R, Src, Dst> {
+    /// Creates a reader of numbers stored in the given byte order
+    pub fn new(inner: R, order: ByteOrder) -> Self {
+        Reader {
+            inner,
+            order,
+            carry: [0; 16],
+            carried: 0,
+            position: 0,
+            _types: PhantomData,
+        }
+    }
+
+    /// The number of samples read so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Gets a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader
+    ///
+    /// The bytes of a number that was only partially read are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, Src, Dst> Reader<R, Src, Dst>
+where
+    R: Read,
+    Src: Number + fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    /// Reads samples into `dst` and returns how many were read
+    ///
+    /// Like `Read::read`, this may read fewer samples than fit in `dst`, and
+    /// returns `0` at the end of the stream. If the stream ends in the middle
+    /// of a number, this returns an error of kind `UnexpectedEof`.
+    ///
+    /// If a sample fails to be casted, the samples read by this call are
+    /// dropped and the next call continues after them.
+    pub fn read_samples(&mut self, dst: &mut [Dst]) -> io::Result<usize>
+    where
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+    {
+        self.read_with(dst, |x| Dst::cast(x).into_result())
+    }
+
+    /// Reads integer PCM samples into `dst`, normalized into `-1.0..1.0`, and
+    /// returns how many were read
+    ///
+    /// The most negative sample, e.g. `-32768` for `i16`, reads as `-1.0`,
+    /// silence as `0.0` and the largest sample as one step below `1.0`. The
+    /// unsigned samples are offset binary, i.e. silence is `128` for `u8`.
+    ///
+    /// No sample fails to be normalized; otherwise this behaves like
+    /// `read_samples`.
+    pub fn read_normalized(&mut self, dst: &mut [Dst]) -> io::Result<usize>
+    where
+        Src: Pcm,
+        Dst: Float,
+    {
+        self.read_with(dst, |x| Ok(Dst::from_f64(x.normalize())))
+    }
+
+    fn read_with<F>(&mut self, dst: &mut [Dst], convert: F) -> io::Result<usize>
+    where
+        F: Fn(Src) -> Result<Dst, Error>,
+    {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+
+        let size = Src::SIZE;
+        let mut buf = [0; BUFFER];
+        let want = (dst.len() * size).min(BUFFER);
+        let mut len = self.carried;
+        buf[..len].copy_from_slice(&self.carry[..len]);
+
+        while len < size {
+            let n = match self.inner.read(&mut buf[len..want]) {
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.carry[..len].copy_from_slice(&buf[..len]);
+                    self.carried = len;
+                    return Err(e);
+                }
+            };
+
+            if n == 0 {
+                self.carried = 0;
+                return if len == 0 {
+                    Ok(0)
+                } else {
+                    Err(ErrorKind::UnexpectedEof.into())
+                };
+            }
+
+            len += n;
+        }
+
+        let samples = len / size;
+        let rest = &buf[samples * size..len];
+        self.carry[..rest.len()].copy_from_slice(rest);
+        self.carried = rest.len();
+        let position = self.position;
+        self.position += samples;
+
+        let bytes = buf[..samples * size].chunks_exact(size);
+        for (i, (y, bytes)) in dst.iter_mut().zip(bytes).enumerate() {
+            let x = Src::read(self.order, bytes);
+            *y = match convert(x) {
+                Ok(y) => y,
+                Err(error) => return Err(invalid(position + i, x, error)),
+            };
+        }
+
+        Ok(samples)
+    }
+}
+
+/// Casts numbers of type `Src` into `Dst` and writes them into a
+/// `std::io::Write`; see the module documentation
+#[derive(Debug)]
+pub struct Writer<W, Src, Dst> {
+    inner: W,
+    order: ByteOrder,
+    /// Number of samples written so far
+    position: usize,
+    _types: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<W, Src, Dst> Writer<W, Src, Dst> {
+    /// Creates a writer of numbers stored in the given byte order
+    pub fn new(inner: W, order: ByteOrder) -> Self {
+        Writer {
+            inner,
+            order,
+            position: 0,
+            _types: PhantomData,
+        }
+    }
+
+    /// The number of samples written so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Gets a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, Src, Dst> Writer<W, Src, Dst>
+where
+    W: Write,
+    Src: Copy + fmt::Debug + fmt::Display + Send + Sync + 'static,
+    Dst: Number,
+{
+    /// Casts all the samples of `src` and writes them
+    ///
+    /// The samples are written in batches; if a sample fails to be casted,
+    /// the samples of its batch are not written.
+    pub fn write_samples(&mut self, src: &[Src]) -> io::Result<()>
+    where
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+    {
+        self.write_with(src, |x| Dst::cast(x).into_result())
+    }
+
+    /// Scales all the samples of `src`, normalized into `-1.0..1.0`, into the
+    /// nearest integer PCM samples and writes them
+    ///
+    /// This is the inverse of `Reader::read_normalized`: `-1.0` is written as
+    /// the most negative sample. `1.0` rounds to one step above the largest
+    /// sample, so it fails with `Overflow`, like the other samples outside of
+    /// `-1.0..1.0`; NaN and the infinities fail too. Otherwise this behaves
+    /// like `write_samples`.
+    pub fn write_normalized(&mut self, src: &[Src]) -> io::Result<()>
+    where
+        Src: Float,
+        Dst: Pcm,
+    {
+        self.write_with(src, |x| Dst::denormalize(x.to_f64()))
+    }
+
+    fn write_with<F>(&mut self, src: &[Src], convert: F) -> io::Result<()>
+    where
+        F: Fn(Src) -> Result<Dst, Error>,
+    {
+        let size = Dst::SIZE;
+        let mut buf = [0; BUFFER];
+
+        for xs in src.chunks(BUFFER / size) {
+            for (i, (&x, out)) in xs.iter().zip(buf.chunks_exact_mut(size)).enumerate() {
+                match convert(x) {
+                    Ok(y) => y.write(self.order, out),
+                    Err(error) => return Err(invalid(self.position + i, x, error)),
+                }
+            }
+
+            self.inner.write_all(&buf[..xs.len() * size])?;
+            self.position += xs.len();
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the inner writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,181 @@
+//! Casting the items of an iterator
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::{saturating, Compare, Error, From, IntoResult, Primitive};
+
+/// Adds `cast_checked` and `cast_saturating` to every iterator
+pub trait CastIterator: Iterator + Sized {
+    /// Casts every item into `Dst`, yielding a `Result` per item
+    ///
+    /// The items are `Result<Dst, Error>` whether the cast is fallible or not,
+    /// so collecting into a `Result` stops at, and reports, the first error:
+    ///
+    /// ```
+    /// use cast::{CastIterator, Error};
+    ///
+    /// # fn main() {
+    /// let ports = [80u32, 443, 8080];
+    /// let ports: Result<Vec<u16>, Error> = ports.iter().copied().cast_checked().collect();
+    /// assert_eq!(ports, Ok(vec![80, 443, 8080]));
+    ///
+    /// let ports = [80u32, 70_000, u32::MAX];
+    /// let ports: Result<Vec<u16>, Error> = ports.iter().copied().cast_checked().collect();
+    /// assert_eq!(ports, Err(Error::Overflow));
+    /// # }
+    /// ```
+    #[inline]
+    fn cast_checked<Dst>(self) -> CastChecked<Self, Dst>
+    where
+        Dst: From<Self::Item>,
+        <Dst as From<Self::Item>>::Output: IntoResult<Dst>,
+    {
+        CastChecked {
+            iter: self,
+            _dst: PhantomData,
+        }
+    }
+
+    /// Casts every item into `Dst`, clamping out of range values
+    ///
+    /// See `cast::saturating` for how each item is casted. Unlike
+    /// `cast_checked`, this never aborts the stream:
+    ///
+    /// ```
+    /// use cast::CastIterator;
+    ///
+    /// # fn main() {
+    /// let samples = [0.25f32, 1.5, -7.0, f32::NAN];
+    /// let pixels: Vec<u8> = samples.iter().map(|x| x * 255.).cast_saturating().collect();
+    ///
+    /// assert_eq!(pixels, [63, 255, 0, 0]);
+    /// # }
+    /// ```
+    #[inline]
+    fn cast_saturating<Dst>(self) -> CastSaturating<Self, Dst>
+    where
+        Self::Item: Compare,
+        Dst: From<Self::Item> + Primitive + Default,
+        <Dst as From<Self::Item>>::Output: IntoResult<Dst>,
+    {
+        CastSaturating {
+            iter: self,
+            _dst: PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> CastIterator for I {}
+
+/// The iterator returned by `CastIterator::cast_checked`
+#[derive(Clone, Debug)]
+pub struct CastChecked<I, Dst> {
+    iter: I,
+    _dst: PhantomData<fn() -> Dst>,
+}
+
+impl<I, Dst> Iterator for CastChecked<I, Dst>
+where
+    I: Iterator,
+    Dst: From<I::Item>,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+    type Item = Result<Dst, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<Dst, Error>> {
+        self.iter.next().map(|x| Dst::cast(x).into_result())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, Dst> DoubleEndedIterator for CastChecked<I, Dst>
+where
+    I: DoubleEndedIterator,
+    Dst: From<I::Item>,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Result<Dst, Error>> {
+        self.iter.next_back().map(|x| Dst::cast(x).into_result())
+    }
+}
+
+impl<I, Dst> ExactSizeIterator for CastChecked<I, Dst>
+where
+    I: ExactSizeIterator,
+    Dst: From<I::Item>,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+}
+
+impl<I, Dst> FusedIterator for CastChecked<I, Dst>
+where
+    I: FusedIterator,
+    Dst: From<I::Item>,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+}
+
+/// The iterator returned by `CastIterator::cast_saturating`
+#[derive(Clone, Debug)]
+pub struct CastSaturating<I, Dst> {
+    iter: I,
+    _dst: PhantomData<fn() -> Dst>,
+}
+
+impl<I, Dst> Iterator for CastSaturating<I, Dst>
+where
+    I: Iterator,
+    I::Item: Compare,
+    Dst: From<I::Item> + Primitive + Default,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+    type Item = Dst;
+
+    #[inline]
+    fn next(&mut self) -> Option<Dst> {
+        self.iter.next().map(saturating)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, Dst> DoubleEndedIterator for CastSaturating<I, Dst>
+where
+    I: DoubleEndedIterator,
+    I::Item: Compare,
+    Dst: From<I::Item> + Primitive + Default,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Dst> {
+        self.iter.next_back().map(saturating)
+    }
+}
+
+impl<I, Dst> ExactSizeIterator for CastSaturating<I, Dst>
+where
+    I: ExactSizeIterator,
+    I::Item: Compare,
+    Dst: From<I::Item> + Primitive + Default,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+}
+
+impl<I, Dst> FusedIterator for CastSaturating<I, Dst>
+where
+    I: FusedIterator,
+    I::Item: Compare,
+    Dst: From<I::Item> + Primitive + Default,
+    <Dst as From<I::Item>>::Output: IntoResult<Dst>,
+{
+}
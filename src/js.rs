@@ -0,0 +1,93 @@
+//! Casts that keep integers within JavaScript's safe range
+//!
+//! A JavaScript number is an `f64`, which represents every integer up to 2^53
+//! exactly, but not all of those above it: `2 ** 53 + 1` evaluates to `2 **
+//! 53`. An `i64` that crosses into JavaScript, e.g. through WASM bindings or a
+//! JSON API, is silently rounded if it's outside of the safe range, and an
+//! `f64` that comes back may have been rounded already. These casts reject
+//! those values instead.
+//!
+//! ```
+//! use cast::{js, Error};
+//!
+//! # fn main() {
+//! assert_eq!(js::f64(1 << 52), Ok(4503599627370496.));
+//! assert_eq!(js::f64(1 << 53), Err(Error::Overflow));
+//!
+//! assert_eq!(js::i64(-9007199254740991.), Ok(js::MIN_SAFE_INTEGER));
+//! assert_eq!(js::i64(9007199254740992.), Err(Error::Overflow));
+//! assert_eq!(js::i64(f64::NAN), Err(Error::NaN));
+//! # }
+//! ```
+//!
+//! Integers beyond the safe range cross the boundary as `BigInt`s. With the
+//! `wasm-bindgen` Cargo feature, `js_sys::BigInt` can be casted from and into
+//! `i64`, `u64`, `i128` and `u128`.
+//!
+//! ``` no_run
+//! # #[cfg(feature = "wasm-bindgen")]
+//! # fn main() {
+//! use cast::Error;
+//! use js_sys::BigInt;
+//!
+//! let x = cast::i64(BigInt::from(u64::MAX));
+//! assert_eq!(x, Err(Error::Overflow));
+//!
+//! let big: BigInt = <BigInt as cast::From<_>>::cast(u128::MAX);
+//! assert_eq!(cast::u128(big), Ok(u128::MAX));
+//! # }
+//! # #[cfg(not(feature = "wasm-bindgen"))]
+//! # fn main() {}
+//! ```
+
+/// The largest integer that JavaScript numbers represent exactly along with
+/// its neighbors, 2^53 - 1; `Number.MAX_SAFE_INTEGER`
+pub const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// The smallest integer that JavaScript numbers represent exactly along with
+/// its neighbors, -(2^53 - 1); `Number.MIN_SAFE_INTEGER`
+pub const MIN_SAFE_INTEGER: i64 = -MAX_SAFE_INTEGER;
+
+dst! {
+    i64,
+
+    /// Checked cast of a JavaScript number into an `i64`, which must be within
+    /// `MIN_SAFE_INTEGER..=MAX_SAFE_INTEGER`
+    ///
+    /// Like `cast::i64`, this truncates the fractional part.
+    #[inline]
+    pub fn i64(x: f64) -> Result<i64, crate::Error> {
+        use crate::{fail, Error};
+
+        if x.is_nan() {
+            Err(fail::<f64, i64, _>(Error::NaN))
+        } else if x.is_infinite() {
+            Err(fail::<f64, i64, _>(Error::Infinite))
+        } else if x >= (MAX_SAFE_INTEGER + 1) as f64 {
+            Err(fail::<f64, i64, _>(Error::Overflow))
+        } else if x <= (MIN_SAFE_INTEGER - 1) as f64 {
+            Err(fail::<f64, i64, _>(Error::NegOverflow))
+        } else {
+            Ok(x as i64)
+        }
+    }
+}
+
+dst! {
+    f64,
+
+    /// Checked cast of an `i64` into a JavaScript number, which fails if `x`
+    /// is outside of `MIN_SAFE_INTEGER..=MAX_SAFE_INTEGER`
+    #[inline]
+    pub fn f64(x: i64) -> Result<f64, crate::Error> {
+        use crate::{fail, Error};
+
+        if x > MAX_SAFE_INTEGER {
+            Err(fail::<i64, f64, _>(Error::Overflow))
+        } else if x < MIN_SAFE_INTEGER {
+            Err(fail::<i64, f64, _>(Error::NegOverflow))
+        } else {
+            Ok(x as f64)
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Conversions between `serde_json::Number` and the primitives
+//!
+//! A `Number` holds a `u64`, an `i64` or an `f64`, depending on how it was
+//! written in the JSON text. `from_number` casts whichever it is into the
+//! primitive the program expects, and, unlike the crate's casts, rejects the
+//! numbers that would change in the process, e.g. `2.5` into an integer or
+//! `9007199254740993` into an `f64`: silently rounded input is a common source
+//! of bugs when ingesting JSON.
+//!
+//! ```
+//! use cast::json::{self, NumberError};
+//! use cast::Error;
+//! use serde_json::Number;
+//!
+//! # fn main() {
+//! let n = |text: &str| serde_json::from_str::<Number>(text).unwrap();
+//!
+//! assert_eq!(json::from_number::<u8>(&n("255")), Ok(255));
+//! assert_eq!(json::from_number::<u8>(&n("2.0")), Ok(2));
+//! assert_eq!(json::from_number::<u8>(&n("256")), Err(NumberError::Cast(Error::Overflow)));
+//! assert_eq!(json::from_number::<i64>(&n("-1e30")), Err(NumberError::Cast(Error::NegOverflow)));
+//! assert_eq!(json::from_number::<u8>(&n("2.5")), Err(NumberError::Imprecise));
+//! assert_eq!(json::from_number::<f32>(&n("0.1")), Err(NumberError::Imprecise));
+//!
+//! assert_eq!(json::to_number(0.5f32), Ok(n("0.5")));
+//! assert_eq!(json::to_number(f64::NAN), Err(Error::NaN));
+//! # }
+//! ```
+//!
+//! *This module requires the `serde_json` Cargo feature*
+
+use core::fmt;
+
+use serde_json::Number;
+
+use crate::{eq, fail, Compare, Error, From, IntoResult};
+
+/// The error returned by `from_number`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NumberError {
+    /// The number doesn't fit in the destination type
+    Cast(Error),
+    /// The number fits in the destination type, but would be rounded
+    Imprecise,
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::Cast(error) => error.fmt(f),
+            NumberError::Imprecise => f.write_str("Loss of precision during numeric conversion"),
+        }
+    }
+}
+
+impl core::error::Error for NumberError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            NumberError::Cast(error) => Some(error),
+            NumberError::Imprecise => None,
+        }
+    }
+}
+
+impl core::convert::From<Error> for NumberError {
+    fn from(error: Error) -> NumberError {
+        NumberError::Cast(error)
+    }
+}
+
+/// Casts `n` into `Dst` if it can be represented exactly; see the module
+/// documentation
+pub fn from_number<Dst>(n: &Number) -> Result<Dst, NumberError>
+where
+    Dst: Compare + From<u64> + From<i64> + From<f64>,
+    <Dst as From<u64>>::Output: IntoResult<Dst>,
+    <Dst as From<i64>>::Output: IntoResult<Dst>,
+    <Dst as From<f64>>::Output: IntoResult<Dst>,
+{
+    if let Some(x) = n.as_u64() {
+        exact(x)
+    } else if let Some(x) = n.as_i64() {
+        exact(x)
+    } else if let Some(x) = n.as_f64() {
+        exact(x)
+    } else {
+        // only arbitrary precision numbers, which are neither, get here
+        Err(NumberError::Cast(fail::<Number, Dst, _>(Error::Overflow)))
+    }
+}
+
+fn exact<Dst, Src>(x: Src) -> Result<Dst, NumberError>
+where
+    Src: Compare,
+    Dst: Compare + From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let y = Dst::cast(x).into_result()?;
+
+    // `eq` compares the values exactly, without converting either of them
+    if eq(x, y) {
+        Ok(y)
+    } else {
+        Err(NumberError::Imprecise)
+    }
+}
+
+mod sealed {
+    use crate::Error;
+
+    pub trait ToNumber: Copy {
+        fn to_number(self) -> Result<serde_json::Number, Error>;
+    }
+}
+
+use self::sealed::ToNumber;
+
+macro_rules! to_number {
+    (int: $($ty:ident),+; float: $($float:ident),+;) => {
+        $(
+            impl ToNumber for $ty {
+                #[inline]
+                fn to_number(self) -> Result<Number, Error> {
+                    Ok(Number::from(self))
+                }
+            }
+        )+
+        $(
+            impl ToNumber for $float {
+                #[inline]
+                fn to_number(self) -> Result<Number, Error> {
+                    match Number::from_f64(f64::from(self)) {
+                        Some(n) => Ok(n),
+                        None if self.is_nan() => Err(fail::<$float, Number, _>(Error::NaN)),
+                        None => Err(fail::<$float, Number, _>(Error::Infinite)),
+                    }
+                }
+            }
+        )+
+    }
+}
+
+to_number! {
+    int: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize;
+    float: f32, f64;
+}
+
+/// Converts `x` into a `Number`
+///
+/// JSON can't represent NaN nor the infinities, so they are rejected.
+/// `i128` and `u128` are not supported because `Number` can't hold every value
+/// of theirs; cast them into `i64` or `u64` first.
+#[inline]
+pub fn to_number<T>(x: T) -> Result<Number, Error>
+where
+    T: ToNumber,
+{
+    x.to_number()
+}
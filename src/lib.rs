@@ -4,7 +4,7 @@
 //! Use these functions to perform a cast from any other numeric primitive:
 //!
 //! ```
-//! use cast::{u8, u16, Error};
+//! use cast::{u8, u16, Error, NegOverflowError, OverflowError};
 //!
 //! # fn main() {
 //! // Infallible operations, like integer promotion, are equivalent to a normal
@@ -14,10 +14,15 @@
 //! // Everything else will return a `Result` depending on the success of the
 //! // operation
 //! assert_eq!(u8(0u16), Ok(0u8));
-//! assert_eq!(u8(256u16), Err(Error::Overflow));
-//! assert_eq!(u8(-1i8), Err(Error::Underflow));
+//! assert_eq!(u8(256i16), Err(Error::Overflow));
+//! assert_eq!(u8(-1i16), Err(Error::NegOverflow));
 //! assert_eq!(u8(1. / 0.), Err(Error::Infinite));
 //! assert_eq!(u8(0. / 0.), Err(Error::NaN));
+//!
+//! // Casts that can only fail in one way report a narrower error type, which
+//! // converts into `Error`
+//! assert_eq!(u8(256u16), Err(OverflowError));
+//! assert_eq!(u8(-1i8), Err(NegOverflowError));
 //! # }
 //! ```
 //!
@@ -73,22 +78,88 @@
 //! # }
 //! ```
 //!
+//! Integers of different widths can be mixed in arithmetic through the
+//! `Checked` wrapper, which casts each operand into the accumulator type and
+//! reports both cast failures and arithmetic overflow as an `Error`:
+//!
+//! ```
+//! use cast::{Checked, Error};
+//!
+//! # fn main() {
+//! let mut total = Checked::new(0u32);
+//! for &n in [200u8, 100, 50].iter() {
+//!     total += n;
+//! }
+//! assert_eq!(total.get(), Ok(350));
+//!
+//! assert_eq!((Checked::new(250u8) + 10u16).get(), Err(Error::Overflow));
+//! assert_eq!((Checked::new(1u8) - 2u8).get(), Err(Error::NegOverflow));
+//! # }
+//! ```
+//!
 //! ## Minimal Supported Rust Version
 //!
-//! This crate is guaranteed to compile *as a dependency* on stable Rust 1.31 and up.
+//! This crate is guaranteed to compile *as a dependency* on stable Rust 1.81 and up,
+//! the first release where `Error` can implement `core::error::Error`.
 //! It's not guaranteed that `cargo test`-ing this crate follows the MSRV.
 //! It *might* compile on older versions but that may change in any new patch release.
 //!
 //! ## Building without `std`
 //!
-//! This crate can be used without Rust's `std` crate by declaring it as
-//! follows in your `Cargo.toml`:
+//! This crate doesn't depend on Rust's `std` crate unless the `std` Cargo
+//! feature is enabled. `Error` implements `core::error::Error`, which `std`
+//! re-exports as `std::error::Error`, either way so cast errors can be boxed or
+//! propagated with `?` alongside other errors, and be used as the `source` of
+//! other errors, also in `no_std` crates. The `std` feature enables the APIs
+//! that need `std`: the `io` module, the conversion of `Error` into
+//! `io::Error` and the runtime AVX2 detection of the `simd` kernels.
+//!
+//! ## Selecting destination types
+//!
+//! Each destination type has a Cargo feature of the same name (`u8`, `i32`,
+//! `f64`, etc.) that enables its cast function and all the `From`
+//! implementations *into* that type. All of them are enabled by default. To cut
+//! down compile time, disable the default features and list only the types you
+//! cast into:
 //!
 //! ``` toml
-//! cast = { version = "*", default-features = false }
+//! cast = { version = "*", default-features = false, features = ["u8", "usize"] }
 //! ```
+//!
+//! `usize` and `isize` need the casts into the fixed-size integers as wide as
+//! a pointer can be, so their features enable those.
+//!
+//! ## Portable pointer-sized casts
+//!
+//! `usize` and `isize` cast like the fixed-size integers of the same width as
+//! a pointer, so whether a cast like `cast::usize(x: u64)` can fail, and hence
+//! its output type, depends on the target. With the `portable` Cargo feature
+//! every cast from or into `usize` and `isize` returns a `Result<_, Error>`,
+//! on every target, so code that builds on a 64-bit host also builds on a
+//! 32-bit one:
+//!
+//! ```
+//! # #[cfg(all(feature = "portable", not(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))))]
+//! # fn main() {
+//! let len: Result<usize, cast::Error> = cast::usize(1u64 << 32);
+//! let byte: Result<u8, cast::Error> = cast::u8(255usize);
+//! # let _ = (len, byte);
+//! # }
+//! # #[cfg(not(all(feature = "portable", not(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr")))))]
+//! # fn main() {}
+//! ```
+//!
+//! This changes the output types for every crate that depends on this one, so
+//! it's meant for binaries, and for checking that a library's code is
+//! portable, rather than to be enabled by libraries.
+//!
+//! An application that only ever ships on known targets can instead enable
+//! `assume-32bit-ptr`, for targets whose pointers are 32 or 64 bits wide, or
+//! `assume-64bit-ptr`, for 64-bit targets. The casts that can't fail on any of
+//! those targets, like `cast::usize(x: u32)` with `assume-32bit-ptr`, are then
+//! infallible, and the others return a `Result`. Building for any other target
+//! is an error.
 
-#![allow(const_err)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
@@ -97,16 +168,215 @@
 #[cfg(test)]
 #[macro_use]
 extern crate quickcheck;
+// lets the tests use `#[derive(From)]`, whose expansion refers to `::cast`
+#[cfg(all(test, feature = "derive"))]
+extern crate self as cast;
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+use core::convert::Infallible;
 use core::fmt;
-#[cfg(feature = "std")]
-use std::error;
+use core::num::{IntErrorKind, TryFromIntError};
 
+/// Only compiles the `$item`s if the Cargo feature of the `$dst` type is
+/// enabled
+///
+/// Defined before the `mod` declarations so that submodules can use it
+macro_rules! dst {
+    (f32, $($item:item)*) => { $(#[cfg(feature = "f32")] $item)* };
+    (f64, $($item:item)*) => { $(#[cfg(feature = "f64")] $item)* };
+    (i8, $($item:item)*) => { $(#[cfg(feature = "i8")] $item)* };
+    (i16, $($item:item)*) => { $(#[cfg(feature = "i16")] $item)* };
+    (i32, $($item:item)*) => { $(#[cfg(feature = "i32")] $item)* };
+    (i64, $($item:item)*) => { $(#[cfg(feature = "i64")] $item)* };
+    (i128, $($item:item)*) => { $(#[cfg(feature = "i128")] $item)* };
+    (isize, $($item:item)*) => { $(#[cfg(feature = "isize")] $item)* };
+    (u8, $($item:item)*) => { $(#[cfg(feature = "u8")] $item)* };
+    (u16, $($item:item)*) => { $(#[cfg(feature = "u16")] $item)* };
+    (u32, $($item:item)*) => { $(#[cfg(feature = "u32")] $item)* };
+    (u64, $($item:item)*) => { $(#[cfg(feature = "u64")] $item)* };
+    (u128, $($item:item)*) => { $(#[cfg(feature = "u128")] $item)* };
+    (usize, $($item:item)*) => { $(#[cfg(feature = "usize")] $item)* };
+}
+
+pub mod adc;
+mod array;
+#[cfg(feature = "arrayvec")]
+mod array_vec;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "az")]
+pub mod az;
+pub mod bench_support;
+#[cfg(feature = "wasm-bindgen")]
+mod bigint;
+// only for the expansion of `bindgen!`
+#[doc(hidden)]
+pub mod bindgen;
+#[cfg(feature = "modular-bitfield")]
+pub mod bitfield;
+mod boundary;
+mod bounds;
+#[cfg(feature = "bytes")]
+pub mod buf;
+pub mod bytes;
+mod checked;
+mod classify;
+pub mod cli;
+mod compare;
+mod compat;
+mod converter;
+#[cfg(feature = "subtle")]
+pub mod ct;
+// public only for the expansion of `#[derive(From)]`
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive;
+#[cfg(feature = "miette")]
+mod diagnostic;
+pub mod dither;
+mod element;
+#[cfg(feature = "num_enum")]
+mod enums;
+mod fallback;
+mod finite;
+mod fit;
+#[cfg(feature = "bitflags")]
+mod flags;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+#[cfg(feature = "failure-hook")]
+mod hook;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "std")]
+pub mod io;
+mod iter;
+pub mod js;
+#[cfg(feature = "serde_json")]
+pub mod json;
+mod lossless;
+mod narrow;
+#[cfg(feature = "ndarray")]
+mod nd;
+#[cfg(feature = "num-traits")]
+pub mod num;
+mod option;
+mod outcome;
+#[cfg(feature = "rayon")]
+pub mod par;
+mod pointer;
+mod primitive;
+mod promote;
+mod proof;
+pub mod proto;
+#[cfg(feature = "pyo3")]
+pub mod py;
+mod range;
+#[cfg(any(feature = "bytemuck", feature = "zerocopy"))]
+pub mod reinterpret;
+mod saturating;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod signedness;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod slice;
+#[cfg(feature = "small-code")]
+mod small_code;
+mod source_range;
+mod split;
 #[cfg(test)]
 mod test;
+mod transparent;
+mod try_cast;
+mod tuple;
+mod typed;
+mod unknown;
+#[cfg(feature = "uom")]
+pub mod uom;
+#[cfg(feature = "error-value")]
+mod value;
+#[cfg(feature = "alloc")]
+mod vec;
+mod widest;
+
+// the derive macro lives in the macro namespace, next to the trait
+#[cfg(feature = "derive")]
+pub use cast_derive::From;
+
+#[cfg(feature = "arrayvec")]
+pub use crate::array_vec::array_vec;
+#[cfg(feature = "arrow")]
+pub use crate::arrow::arrow;
+#[cfg(feature = "arbitrary")]
+pub use crate::boundary::arbitrary_boundary;
+pub use crate::boundary::BoundaryValues;
+pub use crate::bounds::{Fallibly, Infallibly};
+pub use crate::checked::{Arithmetic, Checked};
+pub use crate::classify::{classify, Fallibility};
+pub use crate::compare::{cmp, eq, max, min, Compare};
+pub use crate::compat::StdCompat;
+pub use crate::converter::Converter;
+pub use crate::element::ElementError;
+#[cfg(feature = "num_enum")]
+pub use crate::enums::NumEnum;
+pub use crate::fallback::{Fallback, TryFromError};
+pub use crate::finite::Finite;
+pub use crate::fit::{bits_required, smallest_fitting_type, Fit, IntType};
+#[cfg(feature = "bitflags")]
+pub use crate::flags::BitFlags;
+#[cfg(feature = "heapless")]
+pub use crate::heapless_vec::heapless_vec;
+#[cfg(feature = "failure-hook")]
+pub use crate::hook::{clear_failure_hook, set_failure_hook};
+pub use crate::iter::{CastChecked, CastIterator, CastSaturating};
+pub use crate::lossless::{Fits, Lossless, Roundtrip};
+#[allow(deprecated)]
+pub use crate::narrow::{NegOverflowError, OverflowError, RangeError};
+#[cfg(feature = "ndarray")]
+pub use crate::nd::{ndarray, ndarray_saturating};
+pub use crate::option::{opt, CastOption};
+pub use crate::primitive::Primitive;
+pub use crate::promote::Promote;
+pub use crate::proof::{InRange, NonNegative};
+pub use crate::saturating::saturating;
+pub use crate::signedness::{
+    to_signed, to_signed_bits, to_unsigned, to_unsigned_bits, SignedOf, Signedness, UnsignedOf,
+};
+pub use crate::source_range::{source_range, SourceRange};
+pub use crate::split::{join, split, DoubleWidth, HalfWidth, Split};
+pub use crate::transparent::{Repr, Transparent};
+pub use crate::try_cast::TryCast;
+pub use crate::typed::{typed, CastError};
+pub use crate::unknown::{EnumError, FlagsError};
+#[cfg(feature = "error-value")]
+pub use crate::value::{with_value, ToValue, Value, ValueError};
+#[cfg(feature = "alloc")]
+pub use crate::vec::{vec, vec_partition};
+pub use crate::widest::{widest, Widen, Widest};
 
 /// Cast errors
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// `Error` implements `Display`, also without `std`, so it can be formatted
+/// directly in logs:
+///
+/// ```
+/// # fn main() {
+/// let e = cast::u8(256u16).unwrap_err();
+/// assert_eq!(e.to_string(), "Overflow during numeric conversion");
+/// # }
+/// ```
+///
+/// `Error` is a single byte and doesn't record where the failed cast was
+/// called; with the `track-location` Cargo feature enabled, the errors returned
+/// by `typed` and `with_value` do.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize, ::serde::Serialize))]
+#[non_exhaustive]
 pub enum Error {
     /// Infinite value casted to a type that can only represent finite values
     Infinite,
@@ -117,53 +387,540 @@ pub enum Error {
     Overflow,
     /// Source value is smaller than the minimum value that the destination type
     /// can hold
-    Underflow,
+    ///
+    /// This used to be called `Underflow`, a name that is still available as a
+    /// deprecated alias.
+    #[cfg_attr(feature = "serde", serde(alias = "Underflow"))]
+    NegOverflow,
+}
+
+/// Categories of cast errors
+///
+/// Matching on the kind of an error, rather than on the error itself, keeps
+/// working when new, more specific, error variants are added.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize, ::serde::Serialize))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The source value is outside the range of the destination type
+    /// (`Error::Overflow`, `Error::NegOverflow`)
+    Range,
+    /// The source value is not a finite number (`Error::Infinite`,
+    /// `Error::NaN`)
+    NotFinite,
 }
 
 impl Error {
-    /// A private helper function that implements `description`, because
-    /// `description` is only available when we have `std` enabled.
+    /// The old name of `Error::NegOverflow`
+    ///
+    /// "Underflow" usually refers to a float that rounds to zero; the new name
+    /// matches the terminology of `core::num::IntErrorKind`.
+    #[allow(non_upper_case_globals)]
+    #[deprecated(note = "renamed to `Error::NegOverflow`")]
+    pub const Underflow: Error = Error::NegOverflow;
+
+    /// Returns the category of this error
+    ///
+    /// ```
+    /// use cast::ErrorKind;
+    ///
+    /// # fn main() {
+    /// assert_eq!(cast::u8(-1i16).unwrap_err().kind(), ErrorKind::Range);
+    /// assert_eq!(cast::u8(0f64 / 0.).unwrap_err().kind(), ErrorKind::NotFinite);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::Infinite | Error::NaN => ErrorKind::NotFinite,
+            Error::Overflow | Error::NegOverflow => ErrorKind::Range,
+        }
+    }
+
+    /// Classifies this error using the terminology of `core::num::IntErrorKind`
+    ///
+    /// `Overflow` maps to `PosOverflow` and `NegOverflow` to `NegOverflow`.
+    /// Errors that have no integer counterpart, like `NaN`, map to `None`.
+    ///
+    /// ```
+    /// use core::num::IntErrorKind;
+    ///
+    /// # fn main() {
+    /// let e = cast::u8(-1i16).unwrap_err();
+    /// assert_eq!(e.int_error_kind(), Some(IntErrorKind::NegOverflow));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn int_error_kind(&self) -> Option<IntErrorKind> {
+        match *self {
+            Error::Overflow => Some(IntErrorKind::PosOverflow),
+            Error::NegOverflow => Some(IntErrorKind::NegOverflow),
+            Error::Infinite | Error::NaN => None,
+        }
+    }
+
+    /// The inverse of `int_error_kind`
+    ///
+    /// Use this to normalize, for example, a `ParseIntError` into an `Error`:
+    ///
+    /// ```
+    /// use cast::Error;
+    ///
+    /// # fn main() {
+    /// let e = "300".parse::<u8>().unwrap_err();
+    /// assert_eq!(Error::from_int_error_kind(e.kind()), Some(Error::Overflow));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_int_error_kind(kind: &IntErrorKind) -> Option<Error> {
+        match *kind {
+            IntErrorKind::PosOverflow => Some(Error::Overflow),
+            IntErrorKind::NegOverflow => Some(Error::NegOverflow),
+            _ => None,
+        }
+    }
+
+    /// Returns a numeric code that identifies this error
+    ///
+    /// The codes are stable: they won't change in future releases and new
+    /// variants will get new codes. `0` is never used so it can stand for "no
+    /// error" on the other side of an FFI boundary.
+    ///
+    /// | Error         | Code |
+    /// |---------------|------|
+    /// | `Infinite`    | 1    |
+    /// | `NaN`         | 2    |
+    /// | `Overflow`    | 3    |
+    /// | `NegOverflow` | 4    |
+    ///
+    /// ```
+    /// use cast::Error;
+    ///
+    /// # fn main() {
+    /// let code = cast::u8(256i32).unwrap_err().code();
+    /// assert_eq!(code, 3);
+    /// assert_eq!(Error::from_code(code), Some(Error::Overflow));
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn code(&self) -> u8 {
+        match *self {
+            Error::Infinite => 1,
+            Error::NaN => 2,
+            Error::Overflow => 3,
+            Error::NegOverflow => 4,
+        }
+    }
+
+    /// The inverse of `code`
+    ///
+    /// Returns `None` if `code` doesn't identify any error.
+    #[inline]
+    pub const fn from_code(code: u8) -> Option<Error> {
+        match code {
+            1 => Some(Error::Infinite),
+            2 => Some(Error::NaN),
+            3 => Some(Error::Overflow),
+            4 => Some(Error::NegOverflow),
+            _ => None,
+        }
+    }
+
+    /// Returns a suggestion on how to avoid this error
+    ///
+    /// Unlike `Display`, which says what went wrong, this says what to do about
+    /// it, which makes it a good fit for the "help" line of a command line
+    /// tool's error message.
+    ///
+    /// ```
+    /// # fn main() {
+    /// let e = cast::u8(256i32).unwrap_err();
+    /// assert_eq!(
+    ///     e.help(),
+    ///     "consider clamping the value first, or widening the destination type"
+    /// );
+    /// # }
+    /// ```
+    #[inline]
+    pub fn help(&self) -> &'static str {
+        match *self {
+            Error::Infinite => "consider checking the value with `is_finite` first",
+            Error::NaN => "consider checking the value with `is_nan` first",
+            Error::Overflow => {
+                "consider clamping the value first, or widening the destination type"
+            }
+            Error::NegOverflow => {
+                "consider clamping the value first, or using a destination type that \
+                 can hold negative values"
+            }
+        }
+    }
+
+    /// Returns `true` if this is an `Error::Infinite` error
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        *self == Error::Infinite
+    }
+
+    /// Returns `true` if this is an `Error::NaN` error
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        *self == Error::NaN
+    }
+
+    /// Returns `true` if this is an `Error::Overflow` error
+    ///
+    /// ```
+    /// # fn main() {
+    /// assert!(cast::u8(256i32).unwrap_err().is_overflow());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_overflow(&self) -> bool {
+        *self == Error::Overflow
+    }
+
+    /// Returns `true` if this is an `Error::NegOverflow` error
+    #[inline]
+    pub fn is_neg_overflow(&self) -> bool {
+        *self == Error::NegOverflow
+    }
+
+    /// The old name of `is_neg_overflow`
+    #[deprecated(note = "renamed to `is_neg_overflow`")]
+    #[inline]
+    pub fn is_underflow(&self) -> bool {
+        self.is_neg_overflow()
+    }
+
+    /// A private helper function that implements `description` and
+    /// `Display`
     fn description_helper(&self) -> &str {
         match *self {
             Error::Infinite => "Cannot store infinite value in finite type",
             Error::NaN => "Cannot store NaN in type which does not support it",
             Error::Overflow => "Overflow during numeric conversion",
-            Error::Underflow => "Underflow during numeric conversion",
+            Error::NegOverflow => "Negative overflow during numeric conversion",
         }
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.description_helper())
+        f.pad(self.description_helper())
     }
 }
 
-#[cfg(feature = "std")]
-impl error::Error for Error {
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Error {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(self.description_helper())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for Error {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match *self {
+            Error::Infinite => "Infinite",
+            Error::NaN => "NaN",
+            Error::Overflow => "Overflow",
+            Error::NegOverflow => "NegOverflow",
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Error {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Error> {
+        // `code` is the single source of truth of which variants exist
+        Ok(Error::from_code(u.int_in_range(1..=4)?).unwrap())
+    }
+
+    fn size_hint(_: usize) -> (usize, Option<usize>) {
+        (1, Some(1))
+    }
+}
+
+/// Lets `?` propagate the error of infallible conversions, like the
+/// `TryFrom` implementations derived from `core::convert::From`, as an `Error`
+impl core::convert::From<Infallible> for Error {
+    #[inline]
+    fn from(x: Infallible) -> Error {
+        match x {}
+    }
+}
+
+/// All errors map to the one, opaque, `TryFromIntError`
+impl core::convert::From<Error> for TryFromIntError {
+    #[inline]
+    fn from(_: Error) -> TryFromIntError {
+        // `TryFromIntError` can't be constructed directly
+        <u8 as core::convert::TryFrom<i8>>::try_from(-1).unwrap_err()
+    }
+}
+
+impl core::error::Error for Error {
     fn description(&self) -> &str {
         self.description_helper()
     }
 }
 
+/// Reports a failed cast from `Src` into `Dst`
+///
+/// Every error returned by the casts of this crate is funneled through here.
+#[cfg_attr(
+    not(any(feature = "failure-hook", feature = "tracing")),
+    allow(clippy::extra_unused_type_parameters)
+)]
+#[inline]
+fn fail<Src: ?Sized, Dst, E>(error: E) -> E
+where
+    E: Copy,
+    Error: core::convert::From<E>,
+{
+    #[cfg(all(
+        any(feature = "failure-hook", feature = "tracing"),
+        not(feature = "small-code")
+    ))]
+    report::<Src, Dst>(Error::from(error));
+    #[cfg(all(
+        any(feature = "failure-hook", feature = "tracing"),
+        feature = "small-code"
+    ))]
+    small_code::report(
+        core::any::type_name::<Src>(),
+        core::any::type_name::<Dst>(),
+        Error::from(error),
+    );
+
+    error
+}
+
+/// The out of line part of `fail`
+///
+/// `#[cold]` tells the optimizer that casts are expected to succeed, so it
+/// keeps the reporting code, which is large when `tracing` is enabled, out of
+/// the happy path.
+#[cfg(all(
+    any(feature = "failure-hook", feature = "tracing"),
+    not(feature = "small-code")
+))]
+#[cfg_attr(not(feature = "tracing"), allow(clippy::extra_unused_type_parameters))]
+#[cold]
+#[inline(never)]
+fn report<Src: ?Sized, Dst>(error: Error) {
+    #[cfg(feature = "failure-hook")]
+    crate::hook::call(&error);
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        src = core::any::type_name::<Src>(),
+        dst = core::any::type_name::<Dst>(),
+        error = ?error,
+        "cast failed"
+    );
+}
+
 /// The "cast from" operation
+///
+/// Implementations outside this crate can report their own, richer error
+/// type through `Error` and still be used by code that is generic over this
+/// trait. Implementations that have no particular need for a custom error
+/// type should use this crate's `Error`.
+///
+/// ```
+/// use cast::From as _0;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Port(u16);
+///
+/// #[derive(Debug, PartialEq)]
+/// enum PortError {
+///     Reserved,
+///     OutOfRange(cast::Error),
+/// }
+///
+/// impl cast::From<u32> for Port {
+///     type Output = Result<Port, PortError>;
+///     type Error = PortError;
+///     const FALLIBLE: bool = true;
+///
+///     fn cast(src: u32) -> Self::Output {
+///         match cast::u16(src) {
+///             Ok(0) => Err(PortError::Reserved),
+///             Ok(port) => Ok(Port(port)),
+///             Err(e) => Err(PortError::OutOfRange(e.into())),
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Port::cast(80u32), Ok(Port(80)));
+/// assert_eq!(Port::cast(0u32), Err(PortError::Reserved));
+/// assert_eq!(
+///     Port::cast(65_536u32),
+///     Err(PortError::OutOfRange(cast::Error::Overflow))
+/// );
+/// # }
+/// ```
+///
+/// Tuples of up to 8 elements, arrays and `Option`s are casted element-wise.
+/// The cast is fallible, and reports this crate's `Error`, if any of the
+/// element casts is fallible:
+///
+/// ```
+/// use cast::{Error, From};
+///
+/// # fn main() {
+/// assert_eq!(<(u16, i64)>::cast((1u8, -2i32)), (1, -2));
+/// assert_eq!(<(u8, i8)>::cast((1u16, -2i32)), Ok((1, -2)));
+/// assert_eq!(<(u8, i8)>::cast((1u16, 128i32)), Err(Error::Overflow));
+///
+/// assert_eq!(<[f32; 3]>::cast([1u8, 2, 3]), [1., 2., 3.]);
+/// assert_eq!(<[u8; 3]>::cast([0.5f32, 1., 256.]), Err(Error::Overflow));
+/// # }
+/// ```
 pub trait From<Src> {
-    /// The result of the cast operation: either `Self` or `Result<Self, Error>`
+    /// The result of the cast operation: either `Self` or
+    /// `Result<Self, Self::Error>`
     type Output;
 
+    /// The error reported when the cast fails
+    ///
+    /// This would default to this crate's `Error`, but associated type
+    /// defaults aren't stable, so every implementation has to set it;
+    /// infallible casts and casts without an error type of their own should
+    /// set it to `Error`.
+    type Error;
+
+    /// Whether the cast can fail, i.e. whether `Output` is a `Result`
+    ///
+    /// This lets generic code and macros decide at compile time whether error
+    /// handling is needed. It defaults to `true`, which is never wrong;
+    /// infallible casts should set it to `false`.
+    ///
+    /// ```
+    /// use cast::From;
+    ///
+    /// # fn main() {
+    /// assert!(!<u16 as From<u8>>::FALLIBLE);
+    /// assert!(<u8 as From<u16>>::FALLIBLE);
+    /// # }
+    /// ```
+    const FALLIBLE: bool = true;
+
+    /// How the cast can fail
+    ///
+    /// This defaults to `Fallible` for fallible casts; implementations that can
+    /// only fail in one way should narrow it down. See `classify`.
+    const FALLIBILITY: Fallibility = if Self::FALLIBLE {
+        Fallibility::Fallible
+    } else {
+        Fallibility::Infallible
+    };
+
     /// Checked cast from `Src` to `Self`
     fn cast(_: Src) -> Self::Output;
+
+    // `cast::saturating`, which the casts from floats into integers override
+    // with `as`: it saturates the same way, and compiles to the target's
+    // saturating conversion, where it has one
+    #[doc(hidden)]
+    #[inline]
+    fn saturating(src: Src) -> Self
+    where
+        Src: Compare,
+        Self: Primitive + Default + Sized,
+        Self::Output: IntoResult<Self>,
+    {
+        saturating::clamp(src)
+    }
+
+    // `cast::slice::auto`, which the casts that the `simd` module has kernels
+    // for override with them
+    #[doc(hidden)]
+    #[inline]
+    fn slice<'a>(src: &[Src], dst: &'a mut [Self]) -> Result<&'a mut [Self], slice::SliceError<Src>>
+    where
+        Src: Copy,
+        Self: Sized,
+        Self::Output: IntoResult<Self>,
+    {
+        slice::scalar(src, dst)
+    }
+
+    // `cast::vec`, which the casts that the `simd` module has kernels for
+    // override with them
+    #[cfg(feature = "alloc")]
+    #[doc(hidden)]
+    #[inline]
+    fn vec(v: alloc::vec::Vec<Src>) -> Result<alloc::vec::Vec<Self>, ElementError<Src>>
+    where
+        Src: Copy,
+        Self: Sized,
+        Self::Output: IntoResult<Self>,
+    {
+        vec::scalar(v)
+    }
+}
+
+/// The output of a cast operation, normalized into a `Result`
+///
+/// This is implemented for every type that appears as the `Output` of a
+/// `From` implementation, so generic code can handle fallible and infallible
+/// casts uniformly.
+pub trait IntoResult<T> {
+    /// Converts the output of a cast operation into a `Result`
+    fn into_result(self) -> Result<T, Error>;
+}
+
+impl<T, E> IntoResult<T> for Result<T, E>
+where
+    Error: core::convert::From<E>,
+{
+    #[inline]
+    fn into_result(self) -> Result<T, Error> {
+        self.map_err(Error::from)
+    }
+}
+
+macro_rules! into_result {
+    ($($ty:ident),+) => {
+        $(
+            impl IntoResult<$ty> for $ty {
+                #[inline]
+                fn into_result(self) -> Result<$ty, Error> {
+                    Ok(self)
+                }
+            }
+        )+
+    }
 }
 
+into_result!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+into_result!(i128, u128);
+
 macro_rules! fns {
     ($($ty:ident),+) => {
         $(
-            /// Checked cast function
-            #[inline]
-            pub fn $ty<T>(x: T) -> <$ty as From<T>>::Output
-                where $ty: From<T>
-            {
-                <$ty as From<T>>::cast(x)
+            dst! {
+                $ty,
+
+                /// Checked cast function
+                #[inline]
+                pub fn $ty<T>(x: T) -> <$ty as From<T>>::Output
+                    where $ty: From<T>
+                {
+                    <$ty as From<T>>::cast(x)
+                }
             }
          )+
     }
@@ -173,17 +930,128 @@ fns!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
 
 fns!(i128, u128);
 
+/// Checked cast whose destination type is inferred from the context
+///
+/// The output is always a `Result`, even for infallible casts, which is what
+/// lets the compiler infer `Dst` through the `?` operator. See also the `auto!`
+/// macro.
+#[inline]
+pub fn auto<Dst, Src>(src: Src) -> Result<Dst, Error>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    <Dst as From<Src>>::cast(src).into_result()
+}
+
+/// Checked cast whose destination type is inferred from the context
+///
+/// This expands to a call to the `auto` function and evaluates to a
+/// `Result<Dst, Error>`, where `Dst` is inferred from the surrounding code,
+/// e.g. from the type annotation of a `let` binding or from a struct field.
+///
+/// ```
+/// use cast::Error;
+///
+/// struct Header {
+///     len: u16,
+/// }
+///
+/// fn header(len: usize) -> Result<Header, Error> {
+///     // changing the type of `len` doesn't require touching this code
+///     Ok(Header { len: cast::auto!(len)? })
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let x: u8 = cast::auto!(255u32)?;
+/// assert_eq!(x, 255);
+///
+/// assert_eq!(header(1024).map(|h| h.len), Ok(1024));
+/// assert_eq!(header(65_536).map(|h| h.len), Err(Error::Overflow));
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! auto {
+    ($src:expr) => {
+        $crate::auto($src)
+    };
+}
+
+/// Overrides `From::slice` and `From::vec` with the `simd` kernel of the cast
+/// from `$src` into `$dst`, if there's one
+// unused if none of the destination type features is enabled
+#[allow(unused_macros)]
+macro_rules! simd {
+    (f32, u8) => {
+        simd!(f32, u8, u8_from_f32);
+    };
+    (f32, i16) => {
+        simd!(f32, i16, i16_from_f32);
+    };
+    (u8, f32) => {
+        simd!(u8, f32, f32_from_u8);
+    };
+    (i16, f32) => {
+        simd!(i16, f32, f32_from_i16);
+    };
+    (u8, i16) => {
+        simd!(u8, i16, i16_from_u8);
+    };
+    ($src:ident, $dst:ident) => {};
+    ($src:ident, $dst:ident, $kernel:ident) => {
+        #[cfg(feature = "simd")]
+        #[inline]
+        fn slice<'a>(
+            src: &[$src],
+            dst: &'a mut [$dst],
+        ) -> Result<&'a mut [$dst], crate::slice::SliceError<$src>> {
+            crate::simd::$kernel(src, dst)
+        }
+
+        #[cfg(all(feature = "simd", feature = "alloc"))]
+        #[inline]
+        fn vec(
+            v: alloc::vec::Vec<$src>,
+        ) -> Result<alloc::vec::Vec<$dst>, crate::ElementError<$src>> {
+            crate::simd::vec(v, crate::simd::$kernel)
+        }
+    };
+}
+
 /// `$dst` can hold any value of `$src`
 macro_rules! promotion {
-    ($($src:ty => $($dst: ty),+);+;) => {
+    ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                    type Output = $dst;
+                dst! {
+                    $dst,
 
-                    #[inline]
-                    fn cast(src: $src) -> $dst {
-                        src as $dst
+                    impl From<$src> for $dst {
+                        type Output = $dst;
+                        type Error = Error;
+                        const FALLIBLE: bool = false;
+
+                        #[inline]
+                        fn cast(src: $src) -> $dst {
+                            src as $dst
+                        }
+
+                        simd!($src, $dst);
+                    }
+
+                    impl core::convert::From<$src> for crate::StdCompat<$dst> {
+                        #[inline]
+                        fn from(src: $src) -> crate::StdCompat<$dst> {
+                            crate::StdCompat(<$dst as From<$src>>::cast(src))
+                        }
+                    }
+
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            $src::MIN..=$src::MAX
+                        }
                     }
                 }
             )+
@@ -193,18 +1061,43 @@ macro_rules! promotion {
 
 /// `$dst` can hold any positive value of `$src`
 macro_rules! half_promotion {
-    ($($src:ty => $($dst:ty),+);+;) => {
+    ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                    type Output = Result<$dst, Error>;
-
-                    #[inline]
-                    fn cast(src: $src) -> Self::Output {
-                        if src < 0 {
-                            Err(Error::Underflow)
-                        } else {
-                            Ok(src as $dst)
+                dst! {
+                    $dst,
+
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, crate::NegOverflowError>;
+                        type Error = crate::NegOverflowError;
+                        const FALLIBLE: bool = true;
+                        const FALLIBILITY: crate::Fallibility = crate::Fallibility::NegOverflow;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            if src < 0 {
+                                Err(crate::fail::<$src, $dst, _>(crate::NegOverflowError))
+                            } else {
+                                Ok(src as $dst)
+                            }
+                        }
+                    }
+
+                    impl core::convert::TryFrom<$src> for crate::StdCompat<$dst> {
+                        type Error = Error;
+
+                        #[inline]
+                        fn try_from(src: $src) -> Result<crate::StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src)
+                                .map(crate::StdCompat)
+                                .map_err(Error::from)
+                        }
+                    }
+
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            0..=$src::MAX
                         }
                     }
                 }
@@ -218,17 +1111,45 @@ macro_rules! from_unsigned {
     ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                    type Output = Result<$dst, Error>;
+                dst! {
+                    $dst,
 
-                    #[inline]
-                    fn cast(src: $src) -> Self::Output {
-                        use core::$dst;
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, crate::OverflowError>;
+                        type Error = crate::OverflowError;
+                        const FALLIBLE: bool = true;
+                        const FALLIBILITY: crate::Fallibility = crate::Fallibility::Overflow;
 
-                        if src > $dst::MAX as $src {
-                            Err(Error::Overflow)
-                        } else {
-                            Ok(src as $dst)
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            #[cfg(not(feature = "small-code"))]
+                            let fits = src <= $dst::MAX as $src;
+                            #[cfg(feature = "small-code")]
+                            let fits = crate::small_code::from_unsigned(src as u128, $dst::MAX as u128);
+
+                            if fits {
+                                Ok(src as $dst)
+                            } else {
+                                Err(crate::fail::<$src, $dst, _>(crate::OverflowError))
+                            }
+                        }
+                    }
+
+                    impl core::convert::TryFrom<$src> for crate::StdCompat<$dst> {
+                        type Error = Error;
+
+                        #[inline]
+                        fn try_from(src: $src) -> Result<crate::StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src)
+                                .map(crate::StdCompat)
+                                .map_err(Error::from)
+                        }
+                    }
+
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            0..=$dst::MAX as $src
                         }
                     }
                 }
@@ -242,20 +1163,56 @@ macro_rules! from_signed {
     ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                    type Output = Result<$dst, Error>;
-
-                    #[inline]
-                    fn cast(src: $src) -> Self::Output {
-                        use core::$dst;
-
-                        Err(if src < $dst::MIN as $src {
-                            Error::Underflow
-                        } else if src > $dst::MAX as $src {
-                            Error::Overflow
-                        } else {
-                            return Ok(src as $dst);
-                        })
+                dst! {
+                    $dst,
+
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, Error>;
+                        type Error = Error;
+                        const FALLIBLE: bool = true;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            let dst = src as $dst;
+
+                            // `src` fits iff it survives the round trip, which is a single
+                            // comparison; which bound it's out of only matters on failure
+                            #[cfg(not(feature = "small-code"))]
+                            let error = if dst as $src == src {
+                                None
+                            } else if src < 0 {
+                                Some(Error::NegOverflow)
+                            } else {
+                                Some(Error::Overflow)
+                            };
+                            #[cfg(feature = "small-code")]
+                            let error = crate::small_code::from_signed(
+                                src as i128,
+                                <$dst as crate::Primitive>::BITS,
+                                <$dst as crate::Primitive>::SIGNED,
+                            );
+
+                            match error {
+                                None => Ok(dst),
+                                Some(error) => Err(crate::fail::<$src, $dst, _>(error)),
+                            }
+                        }
+                    }
+
+                    impl core::convert::TryFrom<$src> for crate::StdCompat<$dst> {
+                        type Error = Error;
+
+                        #[inline]
+                        fn try_from(src: $src) -> Result<crate::StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src).map(crate::StdCompat)
+                        }
+                    }
+
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            $dst::MIN as $src..=$dst::MAX as $src
+                        }
                     }
                 }
             )+
@@ -263,54 +1220,119 @@ macro_rules! from_signed {
     }
 }
 
+/// The error of casting `$x: $src` into a type whose range is `$min..=$max`,
+/// if any
+///
+/// Flipping the bits of negative floats, and the sign bit of the others, turns
+/// their bit patterns into integers that are ordered like the floats, with NaN
+/// at both ends. The range check is then a single integer comparison, instead
+/// of one floating-point comparison per error, and the kind of error is only
+/// worked out when it fails. `$min` and `$max` must be finite.
+// unused if none of the integer destination type features is enabled
+#[allow(unused_macros)]
+macro_rules! float_range_error {
+    ($src:ident, $x:expr, $min:expr, $max:expr) => {{
+        let sign = (-(0.0 as $src)).to_bits();
+        let key = |x: $src| {
+            let bits = x.to_bits();
+
+            // all ones if `x` is negative, the sign bit otherwise
+            bits ^ ((bits >> sign.trailing_zeros()).wrapping_neg() | sign)
+        };
+
+        let (min, max) = (key($min), key($max));
+        if key($x).wrapping_sub(min) <= max - min {
+            None
+        } else {
+            let magnitude = $x.to_bits() & !sign;
+
+            Some(if magnitude > $src::INFINITY.to_bits() {
+                Error::NaN
+            } else if magnitude == $src::INFINITY.to_bits() {
+                Error::Infinite
+            } else if $x.to_bits() & sign != 0 {
+                Error::NegOverflow
+            } else {
+                Error::Overflow
+            })
+        }
+    }};
+}
+
 /// From a float `$src` to an integer `$dst`
 macro_rules! from_float {
     ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                    type Output = Result<$dst, Error>;
-
-                    #[inline]
-                    fn cast(src: $src) -> Self::Output {
-                        use core::{$dst, $src};
-
-                        Err(if src != src {
-                            Error::NaN
-                        } else if src == $src::INFINITY ||
-                            src == $src::NEG_INFINITY {
-                            Error::Infinite
-                        } else if {
-                            // this '$dst::BITS' works on 1.31.0 (MSRV)
-                            let dst_bits = core::mem::size_of::<$dst>() as u32 * 8;
-                            let lossless = dst_bits < core::$src::MANTISSA_DIGITS;
-
-                            let max = if lossless {
+                dst! {
+                    $dst,
+
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, Error>;
+                        type Error = Error;
+                        const FALLIBLE: bool = true;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            // when casting to an unsigned integer, negative values close to 0 but
+                            // larger than -1.0 are truncated to 0; this behavior matches casting
+                            // from a float to a signed integer
+                            let min = if !<$dst as crate::Primitive>::SIGNED {
+                                // the float closest to, but greater than, -1
+                                -$src::from_bits((1.0 as $src).to_bits() - 1)
+                            } else {
+                                $dst::MIN as $src
+                            };
+                            let max = if <$dst as crate::Primitive>::BITS < $src::MANTISSA_DIGITS {
                                 $dst::MAX as $src
                             } else {
                                 // we subtract 1 ULP (unit of least precision) here because some
                                 // lossy conversions like `u64::MAX as f64` round *up* and we want
-                                // to avoid the check below evaluating to false in that case
+                                // to avoid accepting that value, which is out of range
                                 $src::from_bits(($dst::MAX as $src).to_bits() - 1)
                             };
 
-                            src > max
-                        } {
-                            Error::Overflow
-                        } else if $dst::MIN == 0 {
-                            // when casting to unsigned integer, negative values close to 0 but
-                            // larger than 1.0 should be truncated to 0; this behavior matches
-                            // casting from a float to a signed integer
-                            if src <= -1.0 {
-                                Error::Underflow
-                            } else {
-                                return Ok(src as $dst);
+                            match float_range_error!($src, src, min, max) {
+                                None => Ok(src as $dst),
+                                Some(error) => Err(crate::fail::<$src, $dst, _>(error)),
                             }
-                        } else if src < $dst::MIN as $src {
-                            Error::Underflow
-                        } else  {
-                            return Ok(src as $dst);
-                        })
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            src as $dst
+                        }
+
+                        simd!($src, $dst);
+                    }
+
+                    impl core::convert::TryFrom<$src> for crate::StdCompat<$dst> {
+                        type Error = Error;
+
+                        #[inline]
+                        fn try_from(src: $src) -> Result<crate::StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src).map(crate::StdCompat)
+                        }
+                    }
+
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            let min = if !<$dst as crate::Primitive>::SIGNED {
+                                // the float closest to, but greater than, -1
+                                -$src::from_bits((1.0 as $src).to_bits() - 1)
+                            } else {
+                                $dst::MIN as $src
+                            };
+                            // see `cast` above
+                            let max = if <$dst as crate::Primitive>::BITS < $src::MANTISSA_DIGITS {
+                                $dst::MAX as $src
+                            } else {
+                                $src::from_bits(($dst::MAX as $src).to_bits() - 1)
+                            };
+
+                            min..=max
+                        }
                     }
                 }
             )+
@@ -324,135 +1346,122 @@ macro_rules! from_float_dst {
     ($($src:ident => $($dst:ident),+);+;) => {
         $(
             $(
-                impl From<$src> for $dst {
-                     type Output = Result<$dst, Error>;
-
-                    #[inline]
-                    #[allow(unused_comparisons)]
-                    fn cast(src: $src) -> Self::Output {
-                        use core::{$dst, $src};
-
-                        Err(if src != src {
-                            Error::NaN
-                        } else if src == $src::INFINITY ||
-                            src == $src::NEG_INFINITY {
-                            Error::Infinite
-                        } else if ($dst::MIN == 0) && src <= -1.0 {
-                            Error::Underflow
-                        } else {
-                            return Ok(src as $dst);
-                        })
-                    }
-                }
-            )+
-        )+
-    }
-}
+                dst! {
+                    $dst,
 
-// PLAY TETRIS! ;-)
+                    impl From<$src> for $dst {
+                         type Output = Result<$dst, Error>;
+                         type Error = Error;
+                         const FALLIBLE: bool = true;
 
-#[cfg(target_pointer_width = "32")]
-mod _32 {
-    use crate::{Error, From};
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            let min = if !<$dst as crate::Primitive>::SIGNED {
+                                // the float closest to, but greater than, -1
+                                -$src::from_bits((1.0 as $src).to_bits() - 1)
+                            } else {
+                                $src::MIN
+                            };
 
-    // Signed
-    promotion! {
-        i8    => f32, f64, i8, i16, i32, isize, i64;
-        i16   => f32, f64,     i16, i32, isize, i64;
-        i32   => f32, f64,          i32, isize, i64;
-        isize => f32, f64,          i32, isize, i64;
-        i64   => f32, f64,                      i64;
-    }
+                            match float_range_error!($src, src, min, $src::MAX) {
+                                None => Ok(src as $dst),
+                                Some(error) => Err(crate::fail::<$src, $dst, _>(error)),
+                            }
+                        }
 
-    half_promotion! {
-        i8    =>                                     u8, u16, u32, usize, u64;
-        i16   =>                                         u16, u32, usize, u64;
-        i32   =>                                              u32, usize, u64;
-        isize =>                                              u32, usize, u64;
-        i64   =>                                                          u64;
-    }
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            src as $dst
+                        }
+                    }
 
-    from_signed! {
+                    impl core::convert::TryFrom<$src> for crate::StdCompat<$dst> {
+                        type Error = Error;
 
-        i16   =>           i8,                       u8;
-        i32   =>           i8, i16,                  u8, u16;
-        isize =>           i8, i16,                  u8, u16;
-        i64   =>           i8, i16, i32, isize,      u8, u16, u32, usize;
-    }
+                        #[inline]
+                        fn try_from(src: $src) -> Result<crate::StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src).map(crate::StdCompat)
+                        }
+                    }
 
-    // Unsigned
-    promotion! {
-        u8    => f32, f64,     i16, i32, isize, i64, u8, u16, u32, usize, u64;
-        u16   => f32, f64,          i32, isize, i64,     u16, u32, usize, u64;
-        u32   => f32, f64,                      i64,          u32, usize, u64;
-        usize => f32, f64,                      i64,          u32, usize, u64;
-        u64   => f32, f64,                                                u64;
-    }
+                    impl crate::SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> core::ops::RangeInclusive<$src> {
+                            let min = if !<$dst as crate::Primitive>::SIGNED {
+                                // the float closest to, but greater than, -1
+                                -$src::from_bits((1.0 as $src).to_bits() - 1)
+                            } else {
+                                $src::MIN
+                            };
 
-    from_unsigned! {
-        u8    =>           i8;
-        u16   =>           i8, i16,                  u8;
-        u32   =>           i8, i16, i32, isize,      u8, u16;
-        usize =>           i8, i16, i32, isize,      u8, u16;
-        u64   =>           i8, i16, i32, isize, i64, u8, u16, u32, usize;
+                            min..=$src::MAX
+                        }
+                    }
+                }
+            )+
+        )+
     }
+}
 
-    // Float
-    promotion! {
-        f32   => f32, f64;
-        f64   =>      f64;
-    }
+/// `$dst` can hold every value of `$src` exactly
+macro_rules! lossless {
+    ($($src:ident => $($dst:ident),+);+;) => {
+        $(
+            $(
+                dst! {
+                    $dst,
 
-    from_float! {
-        f32 =>             i8, i16, i32, isize, i64, u8, u16, u32, usize, u64;
-        f64 =>             i8, i16, i32, isize, i64, u8, u16, u32, usize, u64;
+                    impl crate::Lossless<$src> for $dst {}
+                }
+            )+
+        )+
     }
 }
 
-#[cfg(target_pointer_width = "64")]
-mod _64 {
+// PLAY TETRIS! ;-)
+//
+// The casts from and into `usize` and `isize` are in `pointer`
+
+mod _x64 {
+    // unused if none of the destination types of this module is enabled
+    #[allow(unused_imports)]
     use crate::{Error, From};
 
     // Signed
     promotion! {
-        i8    => f32, f64, i8, i16, i32, i64, isize;
-        i16   => f32, f64,     i16, i32, i64, isize;
-        i32   => f32, f64,          i32, i64, isize;
-        i64   => f32, f64,               i64, isize;
-        isize => f32, f64,               i64, isize;
+        i8    => f32, f64, i8, i16, i32, i64;
+        i16   => f32, f64,     i16, i32, i64;
+        i32   => f32, f64,          i32, i64;
+        i64   => f32, f64,               i64;
     }
 
     half_promotion! {
-        i8    =>                                     u8, u16, u32, u64, usize;
-        i16   =>                                         u16, u32, u64, usize;
-        i32   =>                                              u32, u64, usize;
-        i64   =>                                                   u64, usize;
-        isize =>                                                   u64, usize;
+        i8    =>                                u8, u16, u32, u64;
+        i16   =>                                    u16, u32, u64;
+        i32   =>                                         u32, u64;
+        i64   =>                                              u64;
     }
 
     from_signed! {
 
-        i16   =>           i8,                       u8;
-        i32   =>           i8, i16,                  u8, u16;
-        i64   =>           i8, i16, i32,             u8, u16, u32;
-        isize =>           i8, i16, i32,             u8, u16, u32;
+        i16   =>           i8,                  u8;
+        i32   =>           i8, i16,             u8, u16;
+        i64   =>           i8, i16, i32,        u8, u16, u32;
     }
 
     // Unsigned
     promotion! {
-        u8    => f32, f64,     i16, i32, i64, isize, u8, u16, u32, u64, usize;
-        u16   => f32, f64,          i32, i64, isize,     u16, u32, u64, usize;
-        u32   => f32, f64,               i64, isize,          u32, u64, usize;
-        u64   => f32, f64,                                         u64, usize;
-        usize => f32, f64,                                         u64, usize;
+        u8    => f32, f64,     i16, i32, i64, u8, u16, u32, u64;
+        u16   => f32, f64,          i32, i64,     u16, u32, u64;
+        u32   => f32, f64,               i64,          u32, u64;
+        u64   => f32, f64,                                  u64;
     }
 
     from_unsigned! {
         u8    =>           i8;
-        u16   =>           i8, i16,                  u8;
-        u32   =>           i8, i16, i32,             u8, u16;
-        u64   =>           i8, i16, i32, i64, isize, u8, u16, u32;
-        usize =>           i8, i16, i32, i64, isize, u8, u16, u32;
+        u16   =>           i8, i16,             u8;
+        u32   =>           i8, i16, i32,        u8, u16;
+        u64   =>           i8, i16, i32, i64,   u8, u16, u32;
     }
 
     // Float
@@ -462,12 +1471,28 @@ mod _64 {
     }
 
     from_float! {
-        f32 =>             i8, i16, i32, i64, isize, u8, u16, u32, u64, usize;
-        f64 =>             i8, i16, i32, i64, isize, u8, u16, u32, u64, usize;
+        f32 =>             i8, i16, i32, i64, u8, u16, u32, u64;
+        f64 =>             i8, i16, i32, i64, u8, u16, u32, u64;
+    }
+
+    // Value-preserving subset of the promotions above
+    lossless! {
+        i8    => f32, f64, i8, i16, i32, i64;
+        i16   => f32, f64,     i16, i32, i64;
+        i32   =>      f64,          i32, i64;
+        i64   =>                         i64;
+        u8    => f32, f64,     i16, i32, i64, u8, u16, u32, u64;
+        u16   => f32, f64,          i32, i64,     u16, u32, u64;
+        u32   =>      f64,               i64,          u32, u64;
+        u64   =>                                            u64;
+        f32   => f32, f64;
+        f64   =>      f64;
     }
 }
 
 mod _x128 {
+    // unused if none of the destination types of this module is enabled
+    #[allow(unused_imports)]
     use crate::{Error, From};
 
     // Signed
@@ -476,7 +1501,6 @@ mod _x128 {
         i16   =>                              i128;
         i32   =>                              i128;
         i64   =>                              i128;
-        isize =>                              i128;
         i128  => f32, f64,                    i128;
     }
 
@@ -485,12 +1509,11 @@ mod _x128 {
         i16   =>                                                              u128;
         i32   =>                                                              u128;
         i64   =>                                                              u128;
-        isize =>                                                              u128;
         i128  =>                                                              u128;
     }
 
     from_signed! {
-        i128  =>           i8, i16, i32, i64,       isize, u8, u16, u32, u64,       usize;
+        i128  =>           i8, i16, i32, i64,       u8, u16, u32, u64;
     }
 
     // Unsigned
@@ -499,12 +1522,11 @@ mod _x128 {
         u16   =>                              i128,                           u128;
         u32   =>                              i128,                           u128;
         u64   =>                              i128,                           u128;
-        usize =>                              i128,                           u128;
         u128  =>      f64,                                                    u128;
     }
 
     from_unsigned! {
-        u128 => f32,       i8, i16, i32, i64, i128, isize, u8, u16, u32, u64,       usize;
+        u128 => f32,       i8, i16, i32, i64, i128, u8, u16, u32, u64;
     }
 
     // Float
@@ -516,24 +1538,57 @@ mod _x128 {
         f32 =>                                i128;
         f64 =>                                i128,                           u128;
     }
+
+    // Value-preserving subset of the promotions above
+    lossless! {
+        i8    =>                              i128;
+        i16   =>                              i128;
+        i32   =>                              i128;
+        i64   =>                              i128;
+        i128  =>                              i128;
+        u8    =>                              i128,                           u128;
+        u16   =>                              i128,                           u128;
+        u32   =>                              i128,                           u128;
+        u64   =>                              i128,                           u128;
+        u128  =>                                                              u128;
+    }
 }
 
 // The missing piece
+#[cfg(feature = "f32")]
 impl From<f64> for f32 {
     type Output = Result<f32, Error>;
+    type Error = Error;
+    const FALLIBLE: bool = true;
 
     #[inline]
     fn cast(src: f64) -> Self::Output {
-        use core::{f32, f64};
-
-        if src != src || src == f64::INFINITY || src == f64::NEG_INFINITY {
+        if src.is_nan() || src == f64::INFINITY || src == f64::NEG_INFINITY {
             Ok(src as f32)
         } else if src < f32::MIN as f64 {
-            Err(Error::Underflow)
+            Err(fail::<f64, f32, _>(Error::NegOverflow))
         } else if src > f32::MAX as f64 {
-            Err(Error::Overflow)
+            Err(fail::<f64, f32, _>(Error::Overflow))
         } else {
             Ok(src as f32)
         }
     }
 }
+
+#[cfg(feature = "f32")]
+impl SourceRange<f64> for f32 {
+    #[inline]
+    fn source_range() -> core::ops::RangeInclusive<f64> {
+        f32::MIN as f64..=f32::MAX as f64
+    }
+}
+
+#[cfg(feature = "f32")]
+impl core::convert::TryFrom<f64> for StdCompat<f32> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(src: f64) -> Result<StdCompat<f32>, Error> {
+        <f32 as From<f64>>::cast(src).map(StdCompat)
+    }
+}
@@ -0,0 +1,139 @@
+//! Value-preserving casts
+
+use crate::{Error, From, IntoResult};
+
+/// Casts from `Src` that preserve every value of `Src`
+///
+/// This is a subset of the infallible casts: for example, `i32` → `f32` can't
+/// fail but it rounds integers larger than `2^24`, so `f32` doesn't implement
+/// `Lossless<i32>`. Implementations only exist for the destination types whose
+/// Cargo feature is enabled.
+///
+/// As `From<Src, Output = Self>` is a supertrait, the cast can be performed
+/// without extra bounds:
+///
+/// ```
+/// use cast::{From as _0, Lossless};
+///
+/// fn sum<T>(xs: &[T]) -> f64
+/// where
+///     T: Copy,
+///     f64: Lossless<T>,
+/// {
+///     xs.iter().map(|&x| f64::cast(x)).sum()
+/// }
+///
+/// # fn main() {
+/// assert_eq!(sum(&[1u8, 2, 3]), 6.);
+/// assert_eq!(sum(&[-1i32, 1]), 0.);
+/// // sum(&[1u64]); // Compile error: not every `u64` can be represented as `f64`
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't hold every value of `{Src}` on this target",
+    label = "not every `{Src}` fits in `{Self}`"
+)]
+pub trait Lossless<Src>: From<Src, Output = Self> {}
+
+/// `Self` can be casted into `Dst` without losing any value
+///
+/// This is `Lossless` with the type parameters the other way around, which
+/// reads better in bounds (`where T: Fits<u64>`) and is what `assert_lossless!`
+/// checks.
+#[diagnostic::on_unimplemented(
+    message = "not every value of `{Self}` fits in `{Dst}` on this target",
+    label = "`{Self}` doesn't fit in `{Dst}`"
+)]
+pub trait Fits<Dst> {}
+
+impl<Src, Dst> Fits<Dst> for Src where Dst: Lossless<Src> {}
+
+/// Fails to compile unless each cast preserves every value on the current
+/// target
+///
+/// Use it to document, and enforce, the assumptions about type widths that
+/// the surrounding code makes, e.g. that a `usize` always fits in a `u64`.
+///
+/// ```
+/// # #[cfg(not(feature = "portable"))]
+/// cast::assert_lossless!(u32 => u64, usize => u64, i16 => f32);
+/// # fn main() {}
+/// ```
+///
+/// ``` compile_fail
+/// // `f64` can't represent every `u64`
+/// cast::assert_lossless!(u64 => f64);
+/// # fn main() {}
+/// ```
+///
+/// This checks `Fits`, so the casts must be enabled through the Cargo features
+/// of the destination types.
+#[macro_export]
+macro_rules! assert_lossless {
+    ($($src:ty => $dst:ty),+ $(,)?) => {
+        const _: () = {
+            fn assert_lossless<Src, Dst>()
+            where
+                Src: $crate::Fits<Dst>,
+            {
+            }
+
+            #[allow(dead_code)]
+            fn check() {
+                $(assert_lossless::<$src, $dst>();)+
+            }
+        };
+    };
+}
+
+/// Casting `Self` into `B` and then back into `Self` gives back the original
+/// value, for every value of `Self`
+///
+/// This holds whenever `B: Lossless<Self>` and the cast back from `B` exists,
+/// so this trait is implemented for exactly those pairs. It's meant to be used
+/// as a bound where values are stored in a different type than they are used
+/// in, for example in a serializer:
+///
+/// ```
+/// use cast::Roundtrip;
+///
+/// fn store<T: Roundtrip<i64>>(x: T) -> i64 {
+///     x.cast_forth()
+/// }
+///
+/// fn load<T: Roundtrip<i64>>(x: i64) -> Result<T, cast::Error> {
+///     T::cast_back(x)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(load::<u32>(store(u32::MAX)), Ok(u32::MAX));
+/// assert!(load::<u32>(-1).is_err());
+/// // store(u64::MAX); // Compile error: not every `u64` fits in an `i64`
+/// # }
+/// ```
+pub trait Roundtrip<B>: Sized {
+    /// Casts `self` into `B`; this never fails
+    fn cast_forth(self) -> B;
+
+    /// Casts `b` back into `Self`
+    ///
+    /// This can only fail if `b` wasn't produced by `cast_forth`.
+    fn cast_back(b: B) -> Result<Self, Error>;
+}
+
+impl<A, B> Roundtrip<B> for A
+where
+    A: From<B>,
+    B: Lossless<A>,
+    <A as From<B>>::Output: IntoResult<A>,
+{
+    #[inline]
+    fn cast_forth(self) -> B {
+        B::cast(self)
+    }
+
+    #[inline]
+    fn cast_back(b: B) -> Result<A, Error> {
+        A::cast(b).into_result()
+    }
+}
@@ -0,0 +1,189 @@
+//! Error types of casts that can only fail in one way
+
+use core::fmt;
+
+use crate::Error;
+
+macro_rules! narrow {
+    ($($(#[$attr:meta])* $ty:ident => $variant:ident;)+) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+            pub struct $ty;
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&Error::$variant, f)
+                }
+            }
+
+            #[cfg(feature = "ufmt")]
+            impl ufmt::uDisplay for $ty {
+                fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+                where
+                    W: ufmt::uWrite + ?Sized,
+                {
+                    ufmt::uDisplay::fmt(&Error::$variant, f)
+                }
+            }
+
+            #[cfg(feature = "ufmt")]
+            impl ufmt::uDebug for $ty {
+                fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+                where
+                    W: ufmt::uWrite + ?Sized,
+                {
+                    f.write_str(stringify!($ty))
+                }
+            }
+
+            impl core::error::Error for $ty {}
+
+            impl core::convert::From<$ty> for Error {
+                #[inline]
+                fn from(_: $ty) -> Error {
+                    Error::$variant
+                }
+            }
+
+            impl PartialEq<Error> for $ty {
+                #[inline]
+                fn eq(&self, other: &Error) -> bool {
+                    *other == Error::$variant
+                }
+            }
+
+            impl PartialEq<$ty> for Error {
+                #[inline]
+                fn eq(&self, _: &$ty) -> bool {
+                    *self == Error::$variant
+                }
+            }
+        )+
+    }
+}
+
+narrow! {
+    /// The error of casts that can overflow but never negatively overflow, like
+    /// `u16` → `u8`
+    ///
+    /// It converts into `Error::Overflow` and compares equal to it.
+    ///
+    /// ```
+    /// use cast::{Error, OverflowError};
+    ///
+    /// # fn main() {
+    /// assert_eq!(cast::u8(256u16), Err(OverflowError));
+    /// assert_eq!(cast::u8(256u16).unwrap_err(), Error::Overflow);
+    /// # }
+    /// ```
+    OverflowError => Overflow;
+
+    /// The error of casts that can only fail by going below the minimum value of
+    /// the destination type, like `i8` → `u8`
+    ///
+    /// It converts into `Error::NegOverflow` and compares equal to it.
+    ///
+    /// ```
+    /// use cast::{Error, NegOverflowError};
+    ///
+    /// # fn main() {
+    /// assert_eq!(cast::u8(-1i8), Err(NegOverflowError));
+    /// assert_eq!(cast::u8(-1i8).unwrap_err(), Error::NegOverflow);
+    /// # }
+    /// ```
+    NegOverflowError => NegOverflow;
+}
+
+/// The error of casts that can overflow in either direction but can't fail
+/// otherwise, like `Finite<f64>` → `i32`
+///
+/// It converts into the `Error` variant of the same name and compares equal
+/// to it.
+///
+/// ```
+/// use cast::{Error, Finite, RangeError};
+///
+/// # fn main() {
+/// let x = Finite::new(-1e10f64).unwrap();
+///
+/// assert_eq!(cast::i32(x), Err(RangeError::NegOverflow));
+/// assert_eq!(cast::i32(x).unwrap_err(), Error::NegOverflow);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum RangeError {
+    /// Number too large to be represented by the destination type
+    Overflow,
+    /// Number too small to be represented by the destination type
+    NegOverflow,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Error::from(*self), f)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for RangeError {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        ufmt::uDisplay::fmt(&Error::from(*self), f)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for RangeError {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match *self {
+            RangeError::Overflow => "Overflow",
+            RangeError::NegOverflow => "NegOverflow",
+        })
+    }
+}
+
+impl core::error::Error for RangeError {}
+
+impl core::convert::From<RangeError> for Error {
+    #[inline]
+    fn from(e: RangeError) -> Error {
+        match e {
+            RangeError::Overflow => Error::Overflow,
+            RangeError::NegOverflow => Error::NegOverflow,
+        }
+    }
+}
+
+impl core::convert::From<OverflowError> for RangeError {
+    #[inline]
+    fn from(_: OverflowError) -> RangeError {
+        RangeError::Overflow
+    }
+}
+
+impl core::convert::From<NegOverflowError> for RangeError {
+    #[inline]
+    fn from(_: NegOverflowError) -> RangeError {
+        RangeError::NegOverflow
+    }
+}
+
+impl PartialEq<Error> for RangeError {
+    #[inline]
+    fn eq(&self, other: &Error) -> bool {
+        Error::from(*self) == *other
+    }
+}
+
+impl PartialEq<RangeError> for Error {
+    #[inline]
+    fn eq(&self, other: &RangeError) -> bool {
+        *self == Error::from(*other)
+    }
+}
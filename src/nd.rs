@@ -0,0 +1,86 @@
+//! Element-wise casts of `ndarray` arrays
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use ndarray::{Array, ArrayBase, Data, Dimension};
+
+use crate::{saturating, Compare, ElementError, From, IntoResult, Primitive};
+
+/// Checked cast of every element of an n-dimensional array
+///
+/// The output has the same shape as `a`, in the standard (row major) layout.
+/// The index of the error is the coordinates of the element: a `usize` for
+/// one-dimensional arrays, a `(usize, usize)` for two-dimensional ones, etc.
+///
+/// ```
+/// use ndarray::array;
+///
+/// # fn main() {
+/// let image = array![[0.5f32, 1.5], [255., 256.]];
+///
+/// let e = cast::ndarray::<u8, _, _>(&image).unwrap_err();
+/// assert_eq!(e.index(), (1, 1));
+/// assert_eq!(e.value(), 256.);
+///
+/// assert_eq!(cast::ndarray::<f64, _, _>(&image.t()), Ok(array![[0.5, 255.], [1.5, 256.]]));
+/// # }
+/// ```
+///
+/// *This API requires the `ndarray` Cargo feature*
+pub fn ndarray<Dst, S, D>(
+    a: &ArrayBase<S, D>,
+) -> Result<Array<Dst, D>, ElementError<S::Elem, D::Pattern>>
+where
+    S: Data,
+    S::Elem: Copy,
+    D: Dimension,
+    Dst: From<S::Elem>,
+    <Dst as From<S::Elem>>::Output: IntoResult<Dst>,
+{
+    // the elements are visited in logical order, which for arrays in the
+    // standard layout is a plain walk over their memory
+    let ys = a
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| Dst::cast(x).into_result().map_err(|error| (i, x, error)))
+        .collect::<Result<Vec<_>, _>>();
+
+    match ys {
+        Ok(ys) => match Array::from_shape_vec(a.raw_dim(), ys) {
+            Ok(b) => Ok(b),
+            // `ys` has one element per element of `a`
+            Err(_) => unreachable!(),
+        },
+        Err((i, x, error)) => match a.indexed_iter().nth(i) {
+            Some((index, _)) => Err(ElementError::new(index, x, error)),
+            None => unreachable!(),
+        },
+    }
+}
+
+/// Saturating cast of every element of an n-dimensional array; see
+/// `cast::saturating`
+///
+/// ```
+/// use ndarray::array;
+///
+/// # fn main() {
+/// let samples = array![-1.5f32, 0.25, 7e4, f32::NAN];
+///
+/// assert_eq!(cast::ndarray_saturating::<i16, _, _>(&samples), array![-1, 0, i16::MAX, 0]);
+/// # }
+/// ```
+///
+/// *This API requires the `ndarray` Cargo feature*
+pub fn ndarray_saturating<Dst, S, D>(a: &ArrayBase<S, D>) -> Array<Dst, D>
+where
+    S: Data,
+    S::Elem: Compare,
+    D: Dimension,
+    Dst: From<S::Elem> + Primitive + Default,
+    <Dst as From<S::Elem>>::Output: IntoResult<Dst>,
+{
+    a.mapv(saturating)
+}
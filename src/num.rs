@@ -0,0 +1,235 @@
+//! Interoperability with the `num-traits` conversion traits
+//!
+//! The orphan rules don't allow implementing `num-traits`' traits for the
+//! primitives outside of `num-traits`, so this crate implements them for the
+//! `Num` newtype instead. Its `ToPrimitive`, `FromPrimitive` and `NumCast`
+//! impls are this crate's checked casts with the error discarded, so a
+//! conversion succeeds in generic `num-traits` code exactly when it does here.
+//!
+//! ```
+//! use num_traits::{NumCast, ToPrimitive};
+//!
+//! use cast::num::Num;
+//!
+//! fn mean<T: ToPrimitive>(xs: &[T]) -> Option<f64> {
+//!     let mut sum = 0.;
+//!     for x in xs {
+//!         sum += x.to_f64()?;
+//!     }
+//!     Some(sum / xs.len() as f64)
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(mean(&[Num(1u8), Num(2)]), Some(1.5));
+//!
+//! assert_eq!(Num(300u16).to_u8(), None);
+//! assert_eq!(<Num<i8> as NumCast>::from(Num(-1.5f32)), Some(Num(-1)));
+//! # }
+//! ```
+//!
+//! `cast` goes the other way: it casts any `ToPrimitive`, e.g. the big
+//! integers of the `num-bigint` crate, into a primitive, reporting why the
+//! conversion failed.
+//!
+//! ```
+//! use cast::num::Num;
+//! use cast::Error;
+//!
+//! # fn main() {
+//! assert_eq!(cast::num::cast::<u8, _>(&Num(200u64)), Ok(200));
+//! assert_eq!(cast::num::cast::<u8, _>(&-1i64), Err(Error::NegOverflow));
+//! assert_eq!(cast::num::cast::<i32, _>(&f64::NAN), Err(Error::NaN));
+//! # }
+//! ```
+//!
+//! *This module requires the `num-traits` Cargo feature*
+
+use num_traits::{FromPrimitive, NumCast, ToPrimitive};
+
+use crate::{fail, Error, From, IntoResult, Primitive, Transparent};
+
+/// A primitive that implements the `num-traits` conversion traits in terms of
+/// this crate's casts; see the module documentation
+///
+/// `Num` is `Transparent`, so it can also be casted like the primitive it
+/// wraps.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Num<T>(pub T);
+
+impl<T> Transparent for Num<T>
+where
+    T: Primitive,
+{
+    type Inner = T;
+
+    #[inline]
+    fn from_inner(inner: T) -> Self {
+        Num(inner)
+    }
+
+    #[inline]
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+mod sealed {
+    use num_traits::ToPrimitive;
+
+    use crate::Error;
+
+    /// The primitives that `cast` can produce
+    pub trait Target: Sized {
+        fn from_num<N>(n: &N) -> Result<Self, Error>
+        where
+            N: ToPrimitive + ?Sized;
+    }
+}
+
+use self::sealed::Target;
+
+/// Checked cast of any `ToPrimitive` into a primitive; see the module
+/// documentation
+///
+/// Integers go through `i128` or `u128`, and floats through `f64`, so the
+/// error is the one of the cast from those types.
+#[inline]
+pub fn cast<Dst, Src>(src: &Src) -> Result<Dst, Error>
+where
+    Dst: Target,
+    Src: ToPrimitive + ?Sized,
+{
+    Dst::from_num(src)
+}
+
+macro_rules! to {
+    ($src:ident; $($f:ident -> $dst:ident,)+) => {
+        $(
+            #[inline]
+            fn $f(&self) -> Option<$dst> {
+                <$dst as From<$src>>::cast(self.0).into_result().ok()
+            }
+        )+
+    }
+}
+
+macro_rules! from {
+    ($dst:ident; $($f:ident($src:ident),)+) => {
+        $(
+            #[inline]
+            fn $f(n: $src) -> Option<Self> {
+                <$dst as From<$src>>::cast(n).into_result().ok().map(Num)
+            }
+        )+
+    }
+}
+
+macro_rules! num {
+    ($($ty:ident: $kind:ident,)+) => {
+        $(
+            impl ToPrimitive for Num<$ty> {
+                to! {
+                    $ty;
+                    to_isize -> isize,
+                    to_i8 -> i8,
+                    to_i16 -> i16,
+                    to_i32 -> i32,
+                    to_i64 -> i64,
+                    to_i128 -> i128,
+                    to_usize -> usize,
+                    to_u8 -> u8,
+                    to_u16 -> u16,
+                    to_u32 -> u32,
+                    to_u64 -> u64,
+                    to_u128 -> u128,
+                    to_f32 -> f32,
+                    to_f64 -> f64,
+                }
+            }
+
+            impl FromPrimitive for Num<$ty> {
+                from! {
+                    $ty;
+                    from_isize(isize),
+                    from_i8(i8),
+                    from_i16(i16),
+                    from_i32(i32),
+                    from_i64(i64),
+                    from_i128(i128),
+                    from_usize(usize),
+                    from_u8(u8),
+                    from_u16(u16),
+                    from_u32(u32),
+                    from_u64(u64),
+                    from_u128(u128),
+                    from_f32(f32),
+                    from_f64(f64),
+                }
+            }
+
+            impl NumCast for Num<$ty> {
+                #[inline]
+                fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+                    cast(&n).ok().map(Num)
+                }
+            }
+
+            target!($kind $ty);
+        )+
+    }
+}
+
+macro_rules! target {
+    (int $ty:ident) => {
+        impl Target for $ty {
+            #[inline]
+            fn from_num<N>(n: &N) -> Result<$ty, Error>
+            where
+                N: ToPrimitive + ?Sized,
+            {
+                if let Some(x) = n.to_i128() {
+                    <$ty as From<i128>>::cast(x).into_result()
+                } else if let Some(x) = n.to_u128() {
+                    <$ty as From<u128>>::cast(x).into_result()
+                } else if let Some(x) = n.to_f64() {
+                    // not an integer in the range of `i128` or `u128`: either
+                    // not a finite number, or too large in magnitude
+                    <$ty as From<f64>>::cast(x).into_result()
+                } else {
+                    Err(fail::<N, $ty, _>(Error::Overflow))
+                }
+            }
+        }
+    };
+    (float $ty:ident) => {
+        impl Target for $ty {
+            #[inline]
+            fn from_num<N>(n: &N) -> Result<$ty, Error>
+            where
+                N: ToPrimitive + ?Sized,
+            {
+                match n.to_f64() {
+                    Some(x) => <$ty as From<f64>>::cast(x).into_result(),
+                    None => Err(fail::<N, $ty, _>(Error::Overflow)),
+                }
+            }
+        }
+    };
+}
+
+num! {
+    f32: float,
+    f64: float,
+    i8: int,
+    i16: int,
+    i32: int,
+    i64: int,
+    i128: int,
+    isize: int,
+    u8: int,
+    u16: int,
+    u32: int,
+    u64: int,
+    u128: int,
+    usize: int,
+}
@@ -0,0 +1,87 @@
+//! Casts of optional values
+
+use crate::outcome::{Outcome, Wrap};
+use crate::{Error, Fallibility, From, IntoResult};
+
+impl<Src, Dst> From<Option<Src>> for Option<Dst>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: Outcome<Value = Dst>,
+    <<Dst as From<Src>>::Output as Outcome>::Fallibility: Wrap<Option<Dst>>,
+{
+    type Output =
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<Option<Dst>>>::Output;
+    type Error = Error;
+    const FALLIBLE: bool = <Dst as From<Src>>::FALLIBLE;
+    const FALLIBILITY: Fallibility = <Dst as From<Src>>::FALLIBILITY;
+
+    #[inline]
+    fn cast(src: Option<Src>) -> Self::Output {
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<Option<Dst>>>::wrap(opt(src))
+    }
+}
+
+// the output of the infallible casts
+impl<T> IntoResult<Option<T>> for Option<T> {
+    #[inline]
+    fn into_result(self) -> Result<Option<T>, Error> {
+        Ok(self)
+    }
+}
+
+/// Checked cast of an optional value
+///
+/// `None` casts into `None`. The error, if any, is kept outside the `Option`,
+/// so it can be propagated with `?` without a `transpose`.
+///
+/// ```
+/// use cast::Error;
+///
+/// # fn main() {
+/// assert_eq!(cast::opt::<u8, _>(Some(255u16)), Ok(Some(255)));
+/// assert_eq!(cast::opt::<u8, _>(Some(256u16)), Err(Error::Overflow));
+/// assert_eq!(cast::opt::<u8, u16>(None), Ok(None));
+/// # }
+/// ```
+#[inline]
+pub fn opt<Dst, Src>(src: Option<Src>) -> Result<Option<Dst>, Error>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    match src {
+        Some(x) => Dst::cast(x).into_result().map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Method form of `opt`
+///
+/// ```
+/// use cast::CastOption;
+///
+/// # fn main() {
+/// let depth: Option<i32> = Some(-1);
+///
+/// assert!(depth.cast_opt::<u32>().is_err());
+/// assert_eq!(depth.cast_opt::<i64>(), Ok(Some(-1)));
+/// # }
+/// ```
+pub trait CastOption<Src> {
+    /// Checked cast of the contained value, if any, into `Dst`
+    fn cast_opt<Dst>(self) -> Result<Option<Dst>, Error>
+    where
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>;
+}
+
+impl<Src> CastOption<Src> for Option<Src> {
+    #[inline]
+    fn cast_opt<Dst>(self) -> Result<Option<Dst>, Error>
+    where
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+    {
+        opt(self)
+    }
+}
@@ -0,0 +1,120 @@
+//! Type level bookkeeping of the fallibility of casts that combine other casts
+
+use crate::{Error, IntoResult};
+
+/// Type level `true`: at least one of the casts can fail
+pub enum Fallible {}
+
+/// Type level `false`: none of the casts can fail
+pub enum Infallible {}
+
+/// The outputs of the casts that can be combined into a tuple
+///
+/// This is the output of every primitive cast: either the destination type
+/// itself or a `Result` whose error converts into `Error`.
+pub trait Outcome: IntoResult<Self::Value> {
+    /// The destination type of the cast
+    type Value;
+    /// Whether the cast can fail; either `Fallible` or `Infallible`
+    type Fallibility;
+}
+
+impl<T, E> Outcome for Result<T, E>
+where
+    Error: core::convert::From<E>,
+{
+    type Value = T;
+    type Fallibility = Fallible;
+}
+
+macro_rules! outcome {
+    ($($ty:ident),+) => {
+        $(
+            impl Outcome for $ty {
+                type Value = $ty;
+                type Fallibility = Infallible;
+            }
+        )+
+    }
+}
+
+outcome!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Type level "or" of fallibilities
+pub trait Or<F> {
+    /// `Fallible` if either side is `Fallible`
+    type Output;
+}
+
+impl Or<Infallible> for Infallible {
+    type Output = Infallible;
+}
+
+impl Or<Fallible> for Infallible {
+    type Output = Fallible;
+}
+
+impl<F> Or<F> for Fallible {
+    type Output = Fallible;
+}
+
+/// Type level "or" of a tuple of fallibilities
+pub trait Any {
+    /// `Fallible` if any element is `Fallible`
+    type Output;
+}
+
+impl<F0> Any for (F0,) {
+    type Output = F0;
+}
+
+macro_rules! any {
+    ($f0:ident, $($f:ident),+) => {
+        impl<$f0, $($f),+> Any for ($f0, $($f),+)
+        where
+            ($($f,)+): Any,
+            $f0: Or<<($($f,)+) as Any>::Output>,
+        {
+            type Output = <$f0 as Or<<($($f,)+) as Any>::Output>>::Output;
+        }
+    }
+}
+
+any!(F0, F1);
+any!(F0, F1, F2);
+any!(F0, F1, F2, F3);
+any!(F0, F1, F2, F3, F4);
+any!(F0, F1, F2, F3, F4, F5);
+any!(F0, F1, F2, F3, F4, F5, F6);
+any!(F0, F1, F2, F3, F4, F5, F6, F7);
+
+/// Shapes the combined result of the element casts into the tuple cast output
+pub trait Wrap<T> {
+    /// `T` if infallible, `Result<T, Error>` otherwise
+    type Output;
+
+    /// Converts the combined result into the output
+    fn wrap(result: Result<T, Error>) -> Self::Output;
+}
+
+impl<T> Wrap<T> for Infallible {
+    type Output = T;
+
+    #[inline]
+    fn wrap(result: Result<T, Error>) -> T {
+        match result {
+            Ok(t) => t,
+            // all the element casts are infallible
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl<T> Wrap<T> for Fallible {
+    type Output = Result<T, Error>;
+
+    #[inline]
+    fn wrap(result: Result<T, Error>) -> Result<T, Error> {
+        result
+    }
+}
@@ -0,0 +1,126 @@
+//! Parallel versions of the slice and vector casts, using `rayon`
+//!
+//! The work is split across rayon's global thread pool, but the result is the
+//! same as that of the sequential casts: when several elements fail to be
+//! casted, the error is always the one with the lowest index.
+//!
+//! ```
+//! use cast::slice::SliceError;
+//!
+//! # fn main() {
+//! let samples: Vec<i32> = (0..60_000).collect();
+//! let mut buf = vec![0u16; samples.len()];
+//!
+//! assert!(cast::par::slice(&samples, &mut buf).is_ok());
+//!
+//! match cast::par::vec::<u8, _>(samples) {
+//!     Err(e) => assert_eq!((e.index(), e.value()), (256, 256)),
+//!     Ok(_) => unreachable!(),
+//! }
+//! # }
+//! ```
+//!
+//! *This module requires the `rayon` Cargo feature*
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use rayon::iter::{
+    Either, IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+    ParallelIterator,
+};
+use rayon::slice::ParallelSlice;
+
+use crate::slice::SliceError;
+use crate::{ElementError, From, IntoResult};
+
+/// Number of elements each task of `vec` casts sequentially
+const CHUNK: usize = 4096;
+
+/// Parallel version of `slice::auto`
+///
+/// Returns the written part of `dst`. On error, the contents of `dst` are
+/// unspecified.
+pub fn slice<'a, Dst, Src>(
+    src: &[Src],
+    dst: &'a mut [Dst],
+) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy + Send + Sync,
+    Dst: From<Src> + Send,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+    let failed = dst
+        .par_iter_mut()
+        .zip(src)
+        .enumerate()
+        .filter_map(|(index, (y, &x))| match Dst::cast(x).into_result() {
+            Ok(x) => {
+                *y = x;
+                None
+            }
+            Err(error) => Some(ElementError::new(index, x, error)),
+        })
+        // unlike `find_any`, this returns the failure with the lowest index
+        .find_first(|_| true);
+
+    match failed {
+        Some(e) => Err(SliceError::Element(e)),
+        None => Ok(dst),
+    }
+}
+
+/// Parallel version of `cast::vec`
+///
+/// Unlike `cast::vec`, this always allocates the output.
+pub fn vec<Dst, Src>(v: Vec<Src>) -> Result<Vec<Dst>, ElementError<Src>>
+where
+    Src: Copy + Send + Sync,
+    Dst: From<Src> + Send,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    // each chunk stops at its first failure; the chunks are then joined in
+    // order, so the first failure of the first failed chunk is reported
+    let chunks: Vec<Result<Vec<Dst>, ElementError<Src>>> = v
+        .par_chunks(CHUNK)
+        .enumerate()
+        .map(|(i, xs)| {
+            xs.iter()
+                .enumerate()
+                .map(|(j, &x)| {
+                    Dst::cast(x)
+                        .into_result()
+                        .map_err(|error| ElementError::new(i * CHUNK + j, x, error))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut out = Vec::with_capacity(v.len());
+    for chunk in chunks {
+        out.extend(chunk?);
+    }
+
+    Ok(out)
+}
+
+/// Parallel version of `cast::vec_partition`
+///
+/// Both the casted elements and the failures are returned in their original
+/// order.
+pub fn vec_partition<Dst, Src>(v: Vec<Src>) -> (Vec<Dst>, Vec<ElementError<Src>>)
+where
+    Src: Copy + Send + Sync,
+    Dst: From<Src> + Send,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    v.par_iter()
+        .enumerate()
+        .partition_map(|(index, &x)| match Dst::cast(x).into_result() {
+            Ok(y) => Either::Left(y),
+            Err(error) => Either::Right(ElementError::new(index, x, error)),
+        })
+}
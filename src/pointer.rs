@@ -0,0 +1,468 @@
+//! The casts from and into `usize` and `isize`
+//!
+//! Instead of one table per pointer width, these are derived from the casts of
+//! the fixed-size integers of the same width as a pointer, `Usize` and
+//! `Isize`: on a 32-bit target `usize` casts exactly like `u32`, with the same
+//! `Output`, `Error` and `Fallibility`. A target with a new pointer width only
+//! needs a new pair of aliases.
+//!
+//! With the `portable` feature, every one of these casts returns a
+//! `Result<_, Error>` instead, whatever the pointer width, and `usize` and
+//! `isize` take no part in `Lossless` and `Widen`, whose impls would depend on
+//! it.
+//!
+//! The `assume-32bit-ptr` and `assume-64bit-ptr` features narrow that down to
+//! the targets whose pointers are 32 or 64 bits wide, or 64 bits wide, and
+//! refuse to build for any other target: a cast is infallible if it can't fail
+//! for any of those widths, like `u32 -> usize` with `assume-32bit-ptr`, and
+//! returns a `Result<_, Error>` otherwise.
+
+// unused if none of the destination type features is enabled
+#[allow(unused_imports)]
+use core::{convert::TryFrom, ops::RangeInclusive};
+
+#[allow(unused_imports)]
+use crate::{Error, Fallibility, From, IntoResult, Lossless, SourceRange, StdCompat};
+
+pub(crate) use self::width::{Isize, Usize};
+// unused if neither `usize` nor `isize` is enabled
+#[allow(unused_imports)]
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+use self::width::{IsizeHi, IsizeLo, UsizeHi, UsizeLo};
+
+#[cfg(all(
+    feature = "assume-32bit-ptr",
+    not(any(target_pointer_width = "32", target_pointer_width = "64"))
+))]
+compile_error!(
+    "`assume-32bit-ptr` is enabled, but the pointers of this target are not 32 or 64 bits wide"
+);
+
+#[cfg(all(feature = "assume-64bit-ptr", not(target_pointer_width = "64")))]
+compile_error!(
+    "`assume-64bit-ptr` is enabled, but the pointers of this target are not 64 bits wide"
+);
+
+// `allow`: no target with 128-bit pointers exists yet, and the aliases for
+// the `assume-*` features are unused if neither `usize` nor `isize` is enabled
+#[allow(dead_code, unexpected_cfgs)]
+mod width {
+    #[cfg(target_pointer_width = "16")]
+    pub type Usize = u16;
+    #[cfg(target_pointer_width = "16")]
+    pub type Isize = i16;
+
+    #[cfg(target_pointer_width = "32")]
+    pub type Usize = u32;
+    #[cfg(target_pointer_width = "32")]
+    pub type Isize = i32;
+
+    #[cfg(target_pointer_width = "64")]
+    pub type Usize = u64;
+    #[cfg(target_pointer_width = "64")]
+    pub type Isize = i64;
+
+    #[cfg(target_pointer_width = "128")]
+    pub type Usize = u128;
+    #[cfg(target_pointer_width = "128")]
+    pub type Isize = i128;
+
+    // the narrowest (`Lo`) and the widest (`Hi`) pointers that the `assume-*`
+    // features allow; both are 64 bits wide if both features are enabled
+    #[cfg(all(feature = "assume-32bit-ptr", not(feature = "assume-64bit-ptr")))]
+    pub type UsizeLo = u32;
+    #[cfg(all(feature = "assume-32bit-ptr", not(feature = "assume-64bit-ptr")))]
+    pub type IsizeLo = i32;
+
+    #[cfg(feature = "assume-64bit-ptr")]
+    pub type UsizeLo = u64;
+    #[cfg(feature = "assume-64bit-ptr")]
+    pub type IsizeLo = i64;
+
+    #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+    pub type UsizeHi = u64;
+    #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+    pub type IsizeHi = i64;
+}
+
+/// The output of a cast into `Usize` (`Isize`), as the output of the same cast
+/// into `usize` (`isize`)
+// unused if neither `usize` nor `isize` is enabled
+#[allow(dead_code)]
+pub trait Retype<P> {
+    /// `P`, or `Result<P, E>` if `Self` is a `Result`
+    type Output;
+
+    /// Replaces the fixed-size integer of `self` with `P`
+    fn retype(self) -> Self::Output;
+}
+
+macro_rules! retype {
+    ($($p:ident: $fixed:ident),+) => {
+        $(
+            impl Retype<$p> for $fixed {
+                type Output = $p;
+
+                #[inline]
+                fn retype(self) -> $p {
+                    self as $p
+                }
+            }
+
+            impl<E> Retype<$p> for Result<$fixed, E> {
+                type Output = Result<$p, E>;
+
+                #[inline]
+                fn retype(self) -> Result<$p, E> {
+                    self.map(|x| x as $p)
+                }
+            }
+        )+
+    }
+}
+
+retype!(usize: Usize, isize: Isize);
+
+/// The output of a cast under the `assume-*` features, given `Self`, the output
+/// of the same cast for one of the assumed pointer widths
+///
+/// The cast can only fail if it can fail for that width, so `Self` decides
+/// whether the output is a `Result`.
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+// unused if none of the destination type features is enabled
+#[allow(dead_code)]
+pub trait Assumed<T> {
+    /// `T`, or `Result<T, Error>` if `Self` is a `Result`
+    type Output;
+
+    /// Converts the result of the cast for the actual pointer width
+    fn assume(result: Result<T, Error>) -> Self::Output;
+}
+
+/// The fixed-size integer of the narrowest width that `Self` is assumed to have
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+// unused if neither `usize` nor `isize` is enabled
+#[allow(dead_code)]
+pub trait Narrowest {
+    /// `Self`, unless it's pointer-sized
+    type Fixed;
+}
+
+macro_rules! assumed {
+    ($($ty:ident),+) => {
+        $(
+            #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+            impl<T> Assumed<T> for $ty {
+                type Output = T;
+
+                #[inline]
+                fn assume(result: Result<T, Error>) -> T {
+                    match result {
+                        Ok(x) => x,
+                        Err(_) => unreachable!(),
+                    }
+                }
+            }
+
+            #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+            impl Narrowest for $ty {
+                type Fixed = $ty;
+            }
+        )+
+    }
+}
+
+assumed!(f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+impl<T, F, E> Assumed<T> for Result<F, E> {
+    type Output = Result<T, Error>;
+
+    #[inline]
+    fn assume(result: Result<T, Error>) -> Result<T, Error> {
+        result
+    }
+}
+
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+impl Narrowest for usize {
+    type Fixed = UsizeLo;
+}
+
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+impl Narrowest for isize {
+    type Fixed = IsizeLo;
+}
+
+/// Casts from `$src` into the pointer-sized `$dst`, in terms of the casts into
+/// `$fixed`
+///
+/// `$src` is casted into `$src_fixed` first, which is `$src` itself unless
+/// it's pointer-sized too. Under the `assume-*` features, the cast can fail if
+/// it can fail into `$lo`, the narrowest pointer assumed.
+macro_rules! into_pointer {
+    ($($dst:ident: $fixed:ident, $lo:ident <= $($src:ident as $src_fixed:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $dst,
+
+                    #[cfg(not(feature = "portable"))]
+                    impl From<$src> for $dst {
+                        type Output = <<$fixed as From<$src_fixed>>::Output as Retype<$dst>>::Output;
+                        type Error = <$fixed as From<$src_fixed>>::Error;
+                        const FALLIBLE: bool = <$fixed as From<$src_fixed>>::FALLIBLE;
+                        const FALLIBILITY: Fallibility = <$fixed as From<$src_fixed>>::FALLIBILITY;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            <$fixed as From<$src_fixed>>::cast(src as $src_fixed).retype()
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$fixed as From<$src_fixed>>::saturating(src as $src_fixed) as $dst
+                        }
+                    }
+
+                    #[cfg(all(
+                        feature = "portable",
+                        not(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))
+                    ))]
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, Error>;
+                        type Error = Error;
+                        const FALLIBLE: bool = true;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            <$fixed as From<$src_fixed>>::cast(src as $src_fixed)
+                                .into_result()
+                                .map(|x| x as $dst)
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$fixed as From<$src_fixed>>::saturating(src as $src_fixed) as $dst
+                        }
+                    }
+
+                    #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+                    impl From<$src> for $dst {
+                        type Output = <<$lo as From<<$src as Narrowest>::Fixed>>::Output as Assumed<$dst>>::Output;
+                        type Error = Error;
+                        const FALLIBLE: bool = <$lo as From<<$src as Narrowest>::Fixed>>::FALLIBLE;
+                        const FALLIBILITY: Fallibility = <$lo as From<<$src as Narrowest>::Fixed>>::FALLIBILITY;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            let result = <$fixed as From<$src_fixed>>::cast(src as $src_fixed)
+                                .into_result()
+                                .map(|x| x as $dst);
+
+                            <<$lo as From<<$src as Narrowest>::Fixed>>::Output as Assumed<$dst>>::assume(result)
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$fixed as From<$src_fixed>>::saturating(src as $src_fixed) as $dst
+                        }
+                    }
+
+                    impl SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> RangeInclusive<$src> {
+                            let range = <$fixed as SourceRange<$src_fixed>>::source_range();
+
+                            *range.start() as $src..=*range.end() as $src
+                        }
+                    }
+
+                    // `for<'a>` keeps the bound from being checked, and
+                    // rejected, where the cast into `$fixed` is lossy
+                    #[cfg(not(feature = "portable"))]
+                    impl Lossless<$src> for $dst where for<'a> $fixed: Lossless<$src_fixed> {}
+                }
+            )+
+        )+
+    }
+}
+
+/// Casts from the pointer-sized `$src` into `$dst`, in terms of the casts from
+/// `$fixed`
+///
+/// Under the `assume-*` features, the cast can fail if it can fail from `$hi`,
+/// the widest pointer assumed.
+macro_rules! from_pointer {
+    ($($src:ident: $fixed:ident, $hi:ident => $($dst:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $dst,
+
+                    #[cfg(not(feature = "portable"))]
+                    impl From<$src> for $dst {
+                        type Output = <$dst as From<$fixed>>::Output;
+                        type Error = <$dst as From<$fixed>>::Error;
+                        const FALLIBLE: bool = <$dst as From<$fixed>>::FALLIBLE;
+                        const FALLIBILITY: Fallibility = <$dst as From<$fixed>>::FALLIBILITY;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            <$dst as From<$fixed>>::cast(src as $fixed)
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$dst as From<$fixed>>::saturating(src as $fixed)
+                        }
+                    }
+
+                    #[cfg(all(
+                        feature = "portable",
+                        not(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))
+                    ))]
+                    impl From<$src> for $dst {
+                        type Output = Result<$dst, Error>;
+                        type Error = Error;
+                        const FALLIBLE: bool = true;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            <$dst as From<$fixed>>::cast(src as $fixed).into_result()
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$dst as From<$fixed>>::saturating(src as $fixed)
+                        }
+                    }
+
+                    #[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+                    impl From<$src> for $dst {
+                        type Output = <<$dst as From<$hi>>::Output as Assumed<$dst>>::Output;
+                        type Error = Error;
+                        const FALLIBLE: bool = <$dst as From<$hi>>::FALLIBLE;
+                        const FALLIBILITY: Fallibility = <$dst as From<$hi>>::FALLIBILITY;
+
+                        #[inline]
+                        fn cast(src: $src) -> Self::Output {
+                            let result = <$dst as From<$fixed>>::cast(src as $fixed).into_result();
+
+                            <<$dst as From<$hi>>::Output as Assumed<$dst>>::assume(result)
+                        }
+
+                        #[inline]
+                        fn saturating(src: $src) -> $dst {
+                            <$dst as From<$fixed>>::saturating(src as $fixed)
+                        }
+                    }
+
+                    impl SourceRange<$src> for $dst {
+                        #[inline]
+                        fn source_range() -> RangeInclusive<$src> {
+                            let range = <$dst as SourceRange<$fixed>>::source_range();
+
+                            *range.start() as $src..=*range.end() as $src
+                        }
+                    }
+
+                    // see `into_pointer`
+                    #[cfg(not(feature = "portable"))]
+                    impl Lossless<$src> for $dst where for<'a> $dst: Lossless<$fixed> {}
+                }
+            )+
+        )+
+    }
+}
+
+into_pointer! {
+    usize: Usize, UsizeLo <= f32 as f32, f64 as f64, i8 as i8, i16 as i16, i32 as i32,
+                             i64 as i64, i128 as i128, isize as Isize, u8 as u8, u16 as u16,
+                             u32 as u32, u64 as u64, u128 as u128, usize as Usize;
+    isize: Isize, IsizeLo <= f32 as f32, f64 as f64, i8 as i8, i16 as i16, i32 as i32,
+                             i64 as i64, i128 as i128, isize as Isize, u8 as u8, u16 as u16,
+                             u32 as u32, u64 as u64, u128 as u128, usize as Usize;
+}
+
+from_pointer! {
+    usize: Usize, UsizeHi => f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128;
+    isize: Isize, IsizeHi => f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128;
+}
+
+// `core` only implements `From` between a pointer-sized and a fixed-size
+// integer where the conversion is infallible on every target, so code that
+// compiles on one target compiles on all of them. `StdCompat` does the same:
+// these are the casts that can't fail for any pointer width up to 128 bits,
+// and every other cast from or into `usize` and `isize` is a `TryFrom`.
+
+/// `core::convert::From` impls for the casts that never fail
+macro_rules! std_from {
+    ($($src:ident => $($dst:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $dst,
+
+                    impl core::convert::From<$src> for StdCompat<$dst> {
+                        #[inline]
+                        fn from(src: $src) -> StdCompat<$dst> {
+                            // with `portable`, the cast returns a `Result`
+                            match <$dst as From<$src>>::cast(src).into_result() {
+                                Ok(dst) => StdCompat(dst),
+                                Err(_) => unreachable!(),
+                            }
+                        }
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+/// `core::convert::TryFrom` impls for the casts that can fail on some target
+macro_rules! std_try_from {
+    ($($src:ident => $($dst:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $dst,
+
+                    impl TryFrom<$src> for StdCompat<$dst> {
+                        type Error = Error;
+
+                        #[inline]
+                        fn try_from(src: $src) -> Result<StdCompat<$dst>, Error> {
+                            <$dst as From<$src>>::cast(src).into_result().map(StdCompat)
+                        }
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+std_from! {
+    i8    =>                                    isize;
+    i16   =>                                    isize;
+    isize => f32, f64,                     i128, isize;
+    u8    =>                                    isize,                          usize;
+    u16   =>                                                                    usize;
+    usize =>      f64,                                                   u128, usize;
+}
+
+std_try_from! {
+    f32   =>                                    isize,                          usize;
+    f64   =>                                    isize,                          usize;
+    i8    =>                                                                    usize;
+    i16   =>                                                                    usize;
+    i32   =>                                    isize,                          usize;
+    i64   =>                                    isize,                          usize;
+    i128  =>                                    isize,                          usize;
+    isize =>           i8, i16, i32, i64,             u8, u16, u32, u64, u128, usize;
+    u16   =>                                    isize;
+    u32   =>                                    isize,                          usize;
+    u64   =>                                    isize,                          usize;
+    u128  =>                                    isize,                          usize;
+    usize => f32,      i8, i16, i32, i64, i128, isize, u8, u16, u32, u64;
+}
@@ -0,0 +1,65 @@
+//! Properties of the numeric primitives
+
+/// Numeric primitives and their properties, as associated constants
+///
+/// This is what the crate's own cast tables use to decide which checks a
+/// cast needs. It's implemented for every primitive that the crate can cast
+/// from or into.
+///
+/// ```
+/// use cast::Primitive;
+///
+/// /// Whether every value of `S` can be stored in `D`, ignoring floats
+/// fn fits<S: Primitive, D: Primitive>() -> bool {
+///     match (S::SIGNED, D::SIGNED) {
+///         (false, false) | (true, true) => S::BITS <= D::BITS,
+///         (false, true) => S::BITS < D::BITS,
+///         (true, false) => false,
+///     }
+/// }
+///
+/// # fn main() {
+/// assert!(fits::<u8, i16>());
+/// assert!(!fits::<i8, u64>());
+/// # }
+/// ```
+pub trait Primitive: Copy + PartialOrd + 'static {
+    /// The size of this type in bits
+    const BITS: u32;
+    /// Whether this type can represent negative numbers
+    const SIGNED: bool;
+    /// The smallest value of this type; `-∞` is not considered for floats
+    const MIN: Self;
+    /// The largest value of this type; `∞` is not considered for floats
+    const MAX: Self;
+}
+
+macro_rules! primitive {
+    ($($ty:ident: $signed:expr;)+) => {
+        $(
+            impl Primitive for $ty {
+                const BITS: u32 = core::mem::size_of::<$ty>() as u32 * 8;
+                const SIGNED: bool = $signed;
+                const MIN: $ty = $ty::MIN;
+                const MAX: $ty = $ty::MAX;
+            }
+        )+
+    }
+}
+
+primitive! {
+    f32: true;
+    f64: true;
+    i8: true;
+    i16: true;
+    i32: true;
+    i64: true;
+    i128: true;
+    isize: true;
+    u8: false;
+    u16: false;
+    u32: false;
+    u64: false;
+    u128: false;
+    usize: false;
+}
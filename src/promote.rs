@@ -0,0 +1,88 @@
+//! Widening into the next larger type
+
+use crate::pointer::{Isize, Usize};
+
+/// Numeric types that have a wider counterpart of the same signedness
+///
+/// Integers widen into the integer type of twice their width (`u8` → `u16`,
+/// `i32` → `i64`, etc.), `usize` and `isize` into the integer type of twice
+/// the pointer width, and `f32` into `f64`. The 128-bit integers and `f64`
+/// have no wider counterpart.
+///
+/// Widening before accumulating keeps generic code from overflowing early:
+///
+/// ```
+/// use core::ops::Add;
+///
+/// use cast::Promote;
+///
+/// fn sum<T>(xs: &[T]) -> T::Output
+/// where
+///     T: Promote,
+///     T::Output: Add<Output = T::Output> + Default,
+/// {
+///     xs.iter().fold(Default::default(), |acc, x| acc + x.promote())
+/// }
+///
+/// # fn main() {
+/// assert_eq!(sum(&[200u8, 100]), 300u16);
+/// assert_eq!(sum(&[i32::MIN, -1]), -2_147_483_649i64);
+/// # }
+/// ```
+pub trait Promote: Copy {
+    /// The wider type
+    type Output: Copy;
+
+    /// Converts `self` into the wider type; this never fails nor loses
+    /// precision
+    fn promote(self) -> Self::Output;
+}
+
+macro_rules! promote {
+    ($($src:ident => $dst:ident;)+) => {
+        $(
+            impl Promote for $src {
+                type Output = $dst;
+
+                #[inline]
+                fn promote(self) -> $dst {
+                    self as $dst
+                }
+            }
+        )+
+    }
+}
+
+promote! {
+    u8 => u16;
+    u16 => u32;
+    u32 => u64;
+    u64 => u128;
+    i8 => i16;
+    i16 => i32;
+    i32 => i64;
+    i64 => i128;
+    f32 => f64;
+}
+
+// `usize` and `isize` promote like the fixed-size integer of the same width;
+// `for<'a>` keeps the bound from being rejected where that's a 128-bit integer
+macro_rules! pointer {
+    ($($ty:ident: $fixed:ident),+) => {
+        $(
+            impl Promote for $ty
+            where
+                for<'a> $fixed: Promote,
+            {
+                type Output = <$fixed as Promote>::Output;
+
+                #[inline]
+                fn promote(self) -> Self::Output {
+                    (self as $fixed).promote()
+                }
+            }
+        )+
+    }
+}
+
+pointer!(usize: Usize, isize: Isize);
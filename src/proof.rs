@@ -0,0 +1,199 @@
+//! Wrappers that carry a range check, making later casts infallible
+
+use core::convert::TryFrom;
+
+use crate::{fail, Error, NegOverflowError, Primitive, SignedOf, Signedness};
+
+/// An integer that has been checked to be non-negative
+///
+/// Its casts follow the casts of the unsigned integer of the same width, so
+/// they never fail with `NegOverflow`. For example, a `NonNegative<i64>`
+/// casts into `u64` infallibly, and into `u32` with `OverflowError` as the
+/// only possible error.
+///
+/// ```
+/// use cast::{NonNegative, OverflowError};
+///
+/// # fn main() {
+/// let x = NonNegative::new(300i64).unwrap();
+///
+/// let y: u64 = cast::u64(x);
+/// let z: Result<u8, OverflowError> = cast::u8(x);
+///
+/// assert_eq!(y, 300);
+/// assert_eq!(z, Err(OverflowError));
+/// assert!(NonNegative::new(-1i64).is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NonNegative<T>(T);
+
+impl<T> NonNegative<T>
+where
+    T: Signedness,
+{
+    /// Checks that `x` is non-negative
+    #[inline]
+    pub fn new(x: T) -> Result<Self, NegOverflowError> {
+        // the bit patterns of the negative integers are the ones above the
+        // largest signed integer; `to_unsigned` would report the failure too
+        let max = <SignedOf<T> as Primitive>::MAX.to_unsigned_bits();
+        if T::SIGNED && x.to_unsigned_bits() > max {
+            Err(fail::<T, Self, _>(NegOverflowError))
+        } else {
+            Ok(NonNegative(x))
+        }
+    }
+
+    /// Returns the wrapped integer
+    #[inline]
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+/// An integer that has been checked to be within `MIN..=MAX`
+///
+/// Its casts into every type that can represent the whole range are
+/// infallible. Casts into types that can't are rejected at compile time.
+///
+/// ```
+/// use cast::InRange;
+///
+/// type Percent = InRange<i32, 0, 100>;
+///
+/// # fn main() {
+/// let p = Percent::new(42).unwrap();
+///
+/// let x: u8 = cast::u8(p);
+/// let y: i8 = cast::i8(p);
+///
+/// assert_eq!((x, y), (42, 42));
+/// assert!(Percent::new(101).is_err());
+/// # }
+/// ```
+///
+/// ``` compile_fail
+/// use cast::InRange;
+///
+/// let p = InRange::<i32, -1, 100>::new(42).unwrap();
+/// let x = cast::u8(p); // `-1` doesn't fit in `u8`
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct InRange<T, const MIN: i128, const MAX: i128>(T);
+
+impl<T, const MIN: i128, const MAX: i128> InRange<T, MIN, MAX>
+where
+    T: Integer,
+{
+    /// Checks that `x` is within `MIN..=MAX`
+    #[inline]
+    pub fn new(x: T) -> Result<Self, Error> {
+        match x.to_i128() {
+            Some(y) if y < MIN => Err(fail::<T, Self, _>(Error::NegOverflow)),
+            Some(y) if y <= MAX => Ok(InRange(x)),
+            _ => Err(fail::<T, Self, _>(Error::Overflow)),
+        }
+    }
+
+    /// Returns the wrapped integer
+    #[inline]
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+/// Integers that can be stored in an `InRange`
+pub trait Integer: Signedness {
+    /// Lossless conversion, if possible
+    fn to_i128(self) -> Option<i128>;
+
+    /// Wrapping conversion
+    fn as_i128(self) -> i128;
+}
+
+macro_rules! integer {
+    ($($ty:ident),+) => {
+        $(
+            impl Integer for $ty {
+                #[inline]
+                fn to_i128(self) -> Option<i128> {
+                    i128::try_from(self).ok()
+                }
+
+                #[inline]
+                fn as_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )+
+    }
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! proof {
+    ($($dst:ident: $min:expr, $max:expr;)+) => {
+        $(
+            dst! {
+                $dst,
+
+                impl<T> crate::From<NonNegative<T>> for $dst
+                where
+                    T: Signedness,
+                    $dst: crate::From<crate::UnsignedOf<T>>,
+                {
+                    type Output = <$dst as crate::From<crate::UnsignedOf<T>>>::Output;
+                    type Error = <$dst as crate::From<crate::UnsignedOf<T>>>::Error;
+                    const FALLIBLE: bool = <$dst as crate::From<crate::UnsignedOf<T>>>::FALLIBLE;
+                    const FALLIBILITY: crate::Fallibility =
+                        <$dst as crate::From<crate::UnsignedOf<T>>>::FALLIBILITY;
+
+                    #[inline]
+                    fn cast(src: NonNegative<T>) -> Self::Output {
+                        <$dst as crate::From<crate::UnsignedOf<T>>>::cast(src.0.to_unsigned_bits())
+                    }
+                }
+
+                impl<T, const MIN: i128, const MAX: i128> crate::From<InRange<T, MIN, MAX>> for $dst
+                where
+                    T: Integer,
+                {
+                    type Output = $dst;
+                    type Error = Error;
+                    const FALLIBLE: bool = false;
+
+                    #[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+                    #[inline]
+                    fn cast(src: InRange<T, MIN, MAX>) -> $dst {
+                        const {
+                            assert!(
+                                $min <= MIN && MAX <= $max,
+                                concat!("the range doesn't fit in `", stringify!($dst), "`")
+                            )
+                        };
+
+                        src.0.as_i128() as $dst
+                    }
+                }
+            }
+        )+
+    }
+}
+
+proof! {
+    f32: i128::MIN, i128::MAX;
+    f64: i128::MIN, i128::MAX;
+    i8: i8::MIN as i128, i8::MAX as i128;
+    i16: i16::MIN as i128, i16::MAX as i128;
+    i32: i32::MIN as i128, i32::MAX as i128;
+    i64: i64::MIN as i128, i64::MAX as i128;
+    i128: i128::MIN, i128::MAX;
+    isize: isize::MIN as i128, isize::MAX as i128;
+    u8: 0, u8::MAX as i128;
+    u16: 0, u16::MAX as i128;
+    u32: 0, u32::MAX as i128;
+    u64: 0, u64::MAX as i128;
+    u128: 0, i128::MAX;
+    usize: 0, usize::MAX as i128;
+}
@@ -0,0 +1,136 @@
+//! Narrowing of decoded Protocol Buffers fields
+//!
+//! Protocol Buffers only has 32- and 64-bit integers, so the structs generated
+//! by e.g. `prost` have `i32`, `i64` or `u64` fields where the domain types of
+//! a program use `u8` or `u16`. The functions of this module cast those fields
+//! and, unlike the plain casts, their error names the field that didn't fit:
+//! with many fields to convert, `Overflow during numeric conversion` alone
+//! doesn't say which one was wrong.
+//!
+//! ```
+//! use cast::proto::{self, FieldError};
+//! use cast::Error;
+//!
+//! // generated code
+//! struct ListenerProto {
+//!     port: u32,
+//!     backlog: Option<i64>,
+//! }
+//!
+//! #[derive(Debug)]
+//! struct Listener {
+//!     port: u16,
+//!     backlog: Option<u8>,
+//! }
+//!
+//! fn listener(msg: ListenerProto) -> Result<Listener, FieldError> {
+//!     Ok(Listener {
+//!         port: proto::field("port", msg.port)?,
+//!         backlog: proto::field("backlog", msg.backlog)?,
+//!     })
+//! }
+//!
+//! # fn main() {
+//! assert!(listener(ListenerProto { port: 443, backlog: None }).is_ok());
+//!
+//! let e = listener(ListenerProto { port: 443, backlog: Some(-1) }).unwrap_err();
+//! assert_eq!(e.field(), "backlog");
+//! assert_eq!(e.error(), Error::NegOverflow);
+//! assert_eq!(e.to_string(), "Field `backlog`: Negative overflow during numeric conversion");
+//! # }
+//! ```
+//!
+//! These work with the types of any Protocol Buffers implementation, so they
+//! don't depend on one.
+
+use core::fmt;
+
+use crate::{Error, From, IntoResult};
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The error returned when a field doesn't fit in its destination type
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FieldError {
+    field: &'static str,
+    index: Option<usize>,
+    error: Error,
+}
+
+impl FieldError {
+    /// The label of the field, as passed to `field` or `repeated`
+    #[inline]
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The position of the element that didn't fit, if the field is repeated
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Why it failed
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "Field `{}[{}]`: {}", self.field, index, self.error),
+            None => write!(f, "Field `{}`: {}", self.field, self.error),
+        }
+    }
+}
+
+// the error is part of the message, so it isn't also the `source`
+impl core::error::Error for FieldError {}
+
+/// Checked cast of the field labeled `field`
+///
+/// Optional fields, `Option<Src>`, are casted into `Option<Dst>`.
+#[inline]
+pub fn field<Dst, Src>(field: &'static str, x: Src) -> Result<Dst, FieldError>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    Dst::cast(x).into_result().map_err(|error| FieldError {
+        field,
+        index: None,
+        error,
+    })
+}
+
+/// Checked cast of every element of the repeated field labeled `field`
+///
+/// ```
+/// # fn main() {
+/// let e = cast::proto::repeated::<u8, _>("weights", vec![1i32, 2, 300]).unwrap_err();
+/// assert_eq!(e.index(), Some(2));
+/// assert_eq!(e.to_string(), "Field `weights[2]`: Overflow during numeric conversion");
+/// # }
+/// ```
+///
+/// *This API requires the `alloc` Cargo feature*
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn repeated<Dst, Src>(field: &'static str, xs: Vec<Src>) -> Result<Vec<Dst>, FieldError>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    crate::vec(xs).map_err(|e| FieldError {
+        field,
+        index: Some(e.index()),
+        error: e.error(),
+    })
+}
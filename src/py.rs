@@ -0,0 +1,79 @@
+//! Extraction of Python numbers into the primitives
+//!
+//! `extract` takes a Python `int` of any size, a `float`, or anything that
+//! implements `__float__`, and casts it into the destination type with this
+//! crate's checks. Its errors are `PyErr`s, ready to be raised; `Error`s
+//! returned by the other casts convert into them too, so `?` works in
+//! `#[pyfunction]`s. Overflows are raised as `OverflowError`, and NaN and the
+//! infinities as `ValueError`.
+//!
+//! ```
+//! use pyo3::exceptions::{PyOverflowError, PyValueError};
+//! use pyo3::prelude::*;
+//!
+//! # fn main() {
+//! # pyo3::prepare_freethreaded_python();
+//! Python::with_gil(|py| {
+//!     let x = py.eval(pyo3::ffi::c_str!("2 ** 100"), None, None).unwrap();
+//!     assert_eq!(cast::py::extract::<f32>(&x).unwrap(), 1.2676506e30);
+//!     assert!(cast::py::extract::<u64>(&x).unwrap_err().is_instance_of::<PyOverflowError>(py));
+//!
+//!     let nan = py.eval(pyo3::ffi::c_str!("float('nan')"), None, None).unwrap();
+//!     assert!(cast::py::extract::<i32>(&nan).unwrap_err().is_instance_of::<PyValueError>(py));
+//! });
+//! # }
+//! ```
+//!
+//! `extract` can also be used in the arguments of a `#[pyfunction]`, with
+//! `#[pyo3(from_py_with = "cast::py::extract")]`.
+//!
+//! *This module requires the `pyo3` Cargo feature*
+
+use pyo3::exceptions::{PyOverflowError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyInt;
+
+use crate::{fail, Error, From, IntoResult};
+
+/// Raises `Error::Overflow` and `Error::NegOverflow` as `OverflowError`, and
+/// `Error::NaN` and `Error::Infinite` as `ValueError`
+impl core::convert::From<Error> for PyErr {
+    fn from(error: Error) -> PyErr {
+        match error {
+            Error::Overflow | Error::NegOverflow => PyOverflowError::new_err(error.to_string()),
+            Error::NaN | Error::Infinite => PyValueError::new_err(error.to_string()),
+        }
+    }
+}
+
+/// Extracts a Python number into `Dst`; see the module documentation
+///
+/// Objects that are neither an `int` nor convertible into a `float` raise
+/// `TypeError`.
+pub fn extract<Dst>(obj: &Bound<'_, PyAny>) -> PyResult<Dst>
+where
+    Dst: From<i128> + From<u128> + From<f64>,
+    <Dst as From<i128>>::Output: IntoResult<Dst>,
+    <Dst as From<u128>>::Output: IntoResult<Dst>,
+    <Dst as From<f64>>::Output: IntoResult<Dst>,
+{
+    if obj.downcast::<PyInt>().is_ok() {
+        if let Ok(x) = obj.extract::<i128>() {
+            return Ok(Dst::cast(x).into_result()?);
+        }
+
+        if let Ok(x) = obj.extract::<u128>() {
+            return Ok(Dst::cast(x).into_result()?);
+        }
+
+        // wider than 128 bits, which still fits in the floats up to ~2^1024
+        return match obj.extract::<f64>() {
+            Ok(x) => Ok(Dst::cast(x).into_result()?),
+            Err(_) if obj.lt(0)? => Err(fail::<PyInt, Dst, _>(Error::NegOverflow).into()),
+            Err(_) => Err(fail::<PyInt, Dst, _>(Error::Overflow).into()),
+        };
+    }
+
+    let x = obj.extract::<f64>()?;
+    Ok(Dst::cast(x).into_result()?)
+}
@@ -0,0 +1,88 @@
+//! Casts of ranges, endpoint by endpoint
+//!
+//! ```
+//! use core::ops::{Range, RangeInclusive};
+//!
+//! use cast::Error;
+//!
+//! # fn main() {
+//! let bytes = [0u8; 16];
+//! let window = 4u64..8;
+//!
+//! let window: Range<usize> = cast::auto(window).unwrap();
+//! assert_eq!(bytes[window].len(), 4);
+//!
+//! assert_eq!(cast::auto::<RangeInclusive<u8>, _>(0..=255u32), Ok(0..=255));
+//! assert_eq!(cast::auto::<Range<u8>, _>(0..256u32), Err(Error::Overflow));
+//! # }
+//! ```
+//!
+//! A `RangeInclusive` that has been iterated to the end is empty, but only
+//! because of a flag that `RangeInclusive::into_inner` doesn't return, and that
+//! can't be set on destination types that don't iterate, like `f32`. Its cast
+//! keeps the endpoints, `end..=end`, and so yields `end` once more; check
+//! `is_empty` before casting a range that may be exhausted.
+
+use core::ops::{Range, RangeInclusive};
+
+use crate::outcome::{Outcome, Wrap};
+use crate::{Error, Fallibility, From, IntoResult};
+
+impl<Src, Dst> From<Range<Src>> for Range<Dst>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: Outcome<Value = Dst>,
+    <<Dst as From<Src>>::Output as Outcome>::Fallibility: Wrap<Range<Dst>>,
+{
+    type Output =
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<Range<Dst>>>::Output;
+    type Error = Error;
+    const FALLIBLE: bool = <Dst as From<Src>>::FALLIBLE;
+    const FALLIBILITY: Fallibility = <Dst as From<Src>>::FALLIBILITY;
+
+    #[inline]
+    fn cast(src: Range<Src>) -> Self::Output {
+        let result =
+            (|| Ok(Dst::cast(src.start).into_result()?..Dst::cast(src.end).into_result()?))();
+
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<Range<Dst>>>::wrap(result)
+    }
+}
+
+impl<Src, Dst> From<RangeInclusive<Src>> for RangeInclusive<Dst>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: Outcome<Value = Dst>,
+    <<Dst as From<Src>>::Output as Outcome>::Fallibility: Wrap<RangeInclusive<Dst>>,
+{
+    type Output =
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<RangeInclusive<Dst>>>::Output;
+    type Error = Error;
+    const FALLIBLE: bool = <Dst as From<Src>>::FALLIBLE;
+    const FALLIBILITY: Fallibility = <Dst as From<Src>>::FALLIBILITY;
+
+    #[inline]
+    fn cast(src: RangeInclusive<Src>) -> Self::Output {
+        let (start, end) = src.into_inner();
+        let result = (|| Ok(Dst::cast(start).into_result()?..=Dst::cast(end).into_result()?))();
+
+        <<<Dst as From<Src>>::Output as Outcome>::Fallibility as Wrap<RangeInclusive<Dst>>>::wrap(
+            result,
+        )
+    }
+}
+
+// the output of the infallible casts
+impl<T> IntoResult<Range<T>> for Range<T> {
+    #[inline]
+    fn into_result(self) -> Result<Range<T>, Error> {
+        Ok(self)
+    }
+}
+
+impl<T> IntoResult<RangeInclusive<T>> for RangeInclusive<T> {
+    #[inline]
+    fn into_result(self) -> Result<RangeInclusive<T>, Error> {
+        Ok(self)
+    }
+}
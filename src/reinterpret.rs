@@ -0,0 +1,272 @@
+//! Zero-copy reinterpretation of slices of primitives as slices of other
+//! primitives
+//!
+//! Unlike the rest of the crate, these don't convert values: the elements of
+//! the returned slice are the bits of the original elements, viewed as another
+//! type (like `to_bits` and `from_bits` do for a single float). Nothing is
+//! copied, so this is the way to change the nominal type of a large buffer,
+//! e.g. from `&[i8]` to `&[u8]` or from `&[u32]` to `&[f32]`.
+//!
+//! ```
+//! # #[cfg(feature = "bytemuck")]
+//! # fn main() {
+//! let samples = [-1i8, 0, 1];
+//! assert_eq!(cast::reinterpret::slice::<u8, _>(&samples), [255, 0, 1]);
+//!
+//! let bits = [0x3f80_0000u32, 0xc000_0000];
+//! assert_eq!(cast::reinterpret::slice::<f32, _>(&bits), [1., -2.]);
+//! # }
+//! # #[cfg(not(feature = "bytemuck"))]
+//! # fn main() {}
+//! ```
+//!
+//! `slice` and `slice_mut` check at compile time that every slice of `Src` can
+//! be reinterpreted as a slice of `Dst`. When that depends on the length and
+//! the address of the slice, e.g. from `&[u8]` to `&[u32]`, use `try_slice`
+//! and `try_slice_mut`, which check at runtime.
+//!
+//! The casts themselves are done by `bytemuck`, so this crate doesn't need
+//! `unsafe` code for them. The elements can be of any type that implements
+//! `bytemuck::Pod`, not only the primitives, so a `#[repr(transparent)]`
+//! wrapper can derive `Pod` and be reinterpreted to and from its inner type.
+//! The `zerocopy` module does the same with `zerocopy`, for the types that
+//! implement its equivalent traits instead.
+//!
+//! *This module requires the `bytemuck` or the `zerocopy` Cargo feature; the
+//! functions at its top level require `bytemuck`*
+
+use core::{fmt, mem};
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::{Pod, PodCastError};
+
+/// The reason a slice couldn't be reinterpreted
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReinterpretError {
+    /// The slice doesn't start at an address that is a multiple of the
+    /// alignment of the destination type
+    Misaligned,
+    /// The size of the slice, in bytes, isn't a multiple of the size of the
+    /// destination type
+    Size,
+}
+
+impl fmt::Display for ReinterpretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            ReinterpretError::Misaligned => "Slice is not aligned for the destination type",
+            ReinterpretError::Size => {
+                "Slice size is not a multiple of the size of the destination type"
+            }
+        })
+    }
+}
+
+impl core::error::Error for ReinterpretError {}
+
+#[cfg(feature = "bytemuck")]
+impl ReinterpretError {
+    fn from_pod(e: PodCastError) -> Self {
+        match e {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned
+            | PodCastError::AlignmentMismatch => ReinterpretError::Misaligned,
+            PodCastError::OutputSliceWouldHaveSlop | PodCastError::SizeMismatch => {
+                ReinterpretError::Size
+            }
+        }
+    }
+}
+
+/// Asserts, at compile time, that every `[Src]` is a valid `[Dst]`
+// `is_multiple_of` is newer than the MSRV
+#[allow(clippy::manual_is_multiple_of)]
+const fn assert_always<Dst, Src>() {
+    assert_sized::<Dst>();
+    assert!(
+        mem::size_of::<Src>() % mem::size_of::<Dst>() == 0,
+        "the size of `Src` is not a multiple of the size of `Dst`"
+    );
+    assert!(
+        mem::align_of::<Dst>() <= mem::align_of::<Src>(),
+        "`Dst` has a stricter alignment than `Src`"
+    );
+}
+
+/// Asserts, at compile time, that the length of a `[Dst]` can be computed
+const fn assert_sized<Dst>() {
+    assert!(
+        mem::size_of::<Dst>() != 0,
+        "zero-sized types can't be reinterpreted"
+    );
+}
+
+/// Reinterprets a slice of `Src` as a slice of `Dst`
+///
+/// This only compiles if the size of `Src` is a multiple of the size of `Dst`
+/// and `Dst` doesn't have a stricter alignment than `Src`:
+///
+/// ```compile_fail
+/// let _ = cast::reinterpret::slice::<u32, _>(&[0u8; 4]);
+/// ```
+#[cfg(feature = "bytemuck")]
+#[inline]
+pub fn slice<Dst: Pod, Src: Pod>(src: &[Src]) -> &[Dst] {
+    const { assert_always::<Dst, Src>() };
+
+    // can't fail, given the assertion
+    bytemuck::cast_slice(src)
+}
+
+/// Mutable version of `slice`
+#[cfg(feature = "bytemuck")]
+#[inline]
+pub fn slice_mut<Dst: Pod, Src: Pod>(src: &mut [Src]) -> &mut [Dst] {
+    const { assert_always::<Dst, Src>() };
+
+    // can't fail, given the assertion
+    bytemuck::cast_slice_mut(src)
+}
+
+/// Reinterprets a slice of `Src` as a slice of `Dst`, checking its address and
+/// length at runtime
+///
+/// ```
+/// use cast::reinterpret::{self, ReinterpretError};
+///
+/// # fn main() {
+/// let words = [1u32, 2, 3];
+/// let bytes = reinterpret::slice::<u8, _>(&words);
+///
+/// assert_eq!(reinterpret::try_slice::<u32, _>(bytes), Ok(&words[..]));
+/// assert_eq!(reinterpret::try_slice::<u32, _>(&bytes[..6]), Err(ReinterpretError::Size));
+/// assert_eq!(
+///     reinterpret::try_slice::<u32, _>(&bytes[1..5]),
+///     Err(ReinterpretError::Misaligned)
+/// );
+/// # }
+/// ```
+#[cfg(feature = "bytemuck")]
+#[inline]
+pub fn try_slice<Dst: Pod, Src: Pod>(src: &[Src]) -> Result<&[Dst], ReinterpretError> {
+    const { assert_sized::<Dst>() };
+
+    // an empty slice may start anywhere
+    if src.is_empty() {
+        return Ok(&[]);
+    }
+
+    bytemuck::try_cast_slice(src).map_err(ReinterpretError::from_pod)
+}
+
+/// Mutable version of `try_slice`
+#[cfg(feature = "bytemuck")]
+#[inline]
+pub fn try_slice_mut<Dst: Pod, Src: Pod>(src: &mut [Src]) -> Result<&mut [Dst], ReinterpretError> {
+    const { assert_sized::<Dst>() };
+
+    if src.is_empty() {
+        return Ok(&mut []);
+    }
+
+    bytemuck::try_cast_slice_mut(src).map_err(ReinterpretError::from_pod)
+}
+
+/// The functions of the parent module, for the types that implement
+/// `zerocopy`'s traits
+///
+/// ```
+/// use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+///
+/// #[derive(Clone, Copy, Debug, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+/// #[repr(transparent)]
+/// struct Meters(f32);
+///
+/// # fn main() {
+/// let distances = [1.5f32, 2.];
+/// assert_eq!(
+///     cast::reinterpret::zerocopy::slice::<Meters, _>(&distances),
+///     [Meters(1.5), Meters(2.)]
+/// );
+/// # }
+/// ```
+///
+/// *This module requires the `zerocopy` Cargo feature*
+#[cfg(feature = "zerocopy")]
+pub mod zerocopy {
+    use core::convert::Infallible;
+
+    use zerocopy::{ConvertError, FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    use super::{assert_always, assert_sized, ReinterpretError};
+
+    fn error<A, S>(e: ConvertError<A, S, Infallible>) -> ReinterpretError {
+        match e {
+            ConvertError::Alignment(_) => ReinterpretError::Misaligned,
+            ConvertError::Size(_) => ReinterpretError::Size,
+            ConvertError::Validity(e) => match e {},
+        }
+    }
+
+    /// `super::slice`, for types whose every bit pattern is valid
+    #[inline]
+    pub fn slice<Dst, Src>(src: &[Src]) -> &[Dst]
+    where
+        Dst: FromBytes + Immutable + KnownLayout,
+        Src: IntoBytes + Immutable,
+    {
+        const { assert_always::<Dst, Src>() };
+
+        match <[Dst]>::ref_from_bytes(src.as_bytes()) {
+            Ok(dst) => dst,
+            Err(_) => unreachable!("checked at compile time"),
+        }
+    }
+
+    /// `super::slice_mut`, for types whose every bit pattern is valid
+    #[inline]
+    pub fn slice_mut<Dst, Src>(src: &mut [Src]) -> &mut [Dst]
+    where
+        Dst: FromBytes + IntoBytes + KnownLayout,
+        Src: FromBytes + IntoBytes,
+    {
+        const { assert_always::<Dst, Src>() };
+
+        match <[Dst]>::mut_from_bytes(src.as_mut_bytes()) {
+            Ok(dst) => dst,
+            Err(_) => unreachable!("checked at compile time"),
+        }
+    }
+
+    /// `super::try_slice`, for types whose every bit pattern is valid
+    #[inline]
+    pub fn try_slice<Dst, Src>(src: &[Src]) -> Result<&[Dst], ReinterpretError>
+    where
+        Dst: FromBytes + Immutable + KnownLayout,
+        Src: IntoBytes + Immutable,
+    {
+        const { assert_sized::<Dst>() };
+
+        // an empty slice may start anywhere
+        if src.is_empty() {
+            return Ok(&[]);
+        }
+
+        <[Dst]>::ref_from_bytes(src.as_bytes()).map_err(error)
+    }
+
+    /// `super::try_slice_mut`, for types whose every bit pattern is valid
+    #[inline]
+    pub fn try_slice_mut<Dst, Src>(src: &mut [Src]) -> Result<&mut [Dst], ReinterpretError>
+    where
+        Dst: FromBytes + IntoBytes + KnownLayout,
+        Src: FromBytes + IntoBytes,
+    {
+        const { assert_sized::<Dst>() };
+
+        if src.is_empty() {
+            return Ok(&mut []);
+        }
+
+        <[Dst]>::mut_from_bytes(src.as_mut_bytes()).map_err(error)
+    }
+}
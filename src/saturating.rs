@@ -0,0 +1,51 @@
+//! Casts that clamp instead of failing
+
+use core::cmp::Ordering;
+
+use crate::{auto, cmp, Compare, Error, From, IntoResult, Primitive};
+
+/// Cast that clamps out of range values to the bounds of `Dst`
+///
+/// Values that are too large become `Dst::MAX`, including positive infinity,
+/// and values that are too small become `Dst::MIN`, including negative
+/// infinity. NaN becomes `0`, like with the `as` operator. Everything else
+/// casts as with the checked casts.
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::saturating::<i16, _>(40_000u32), i16::MAX);
+/// assert_eq!(cast::saturating::<u8, _>(-3i64), 0);
+/// assert_eq!(cast::saturating::<u8, _>(f32::INFINITY), u8::MAX);
+/// assert_eq!(cast::saturating::<u8, _>(f32::NAN), 0);
+/// assert_eq!(cast::saturating::<u8, _>(2.5f64), 2);
+/// # }
+/// ```
+#[inline]
+pub fn saturating<Dst, Src>(src: Src) -> Dst
+where
+    Src: Compare,
+    Dst: From<Src> + Primitive + Default,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    <Dst as From<Src>>::saturating(src)
+}
+
+/// `saturating` in terms of the checked cast
+#[inline]
+pub(crate) fn clamp<Dst, Src>(src: Src) -> Dst
+where
+    Src: Compare,
+    Dst: From<Src> + Primitive + Default,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    match auto(src) {
+        Ok(dst) => dst,
+        Err(Error::Overflow) => Dst::MAX,
+        Err(Error::NegOverflow) => Dst::MIN,
+        Err(_) => match cmp(src, 0u8) {
+            Some(Ordering::Greater) => Dst::MAX,
+            Some(Ordering::Less) => Dst::MIN,
+            _ => Dst::default(),
+        },
+    }
+}
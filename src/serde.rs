@@ -0,0 +1,142 @@
+//! Narrowing helpers for `#[serde(deserialize_with = "...")]`
+//!
+//! Each `checked_*` function deserializes a number of any width, signedness
+//! or kind, and casts it into its destination type with this crate's checks.
+//! A value that doesn't fit is a deserialization error, instead of a silently
+//! truncated field or a confusing "invalid type" message.
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Pixel {
+//!     #[serde(deserialize_with = "cast::serde::checked_u8")]
+//!     red: u8,
+//!     #[serde(deserialize_with = "cast::serde::checked_i16")]
+//!     offset: i16,
+//! }
+//!
+//! # fn main() {
+//! let p: Pixel = serde_json::from_str(r#"{ "red": 255, "offset": -3.5 }"#).unwrap();
+//! assert_eq!((p.red, p.offset), (255, -3));
+//!
+//! let e = serde_json::from_str::<Pixel>(r#"{ "red": 256, "offset": 0 }"#).unwrap_err();
+//! assert_eq!(
+//!     e.to_string(),
+//!     "256 doesn't fit in `u8`: Overflow during numeric conversion at line 1 column 12",
+//! );
+//! # }
+//! ```
+//!
+//! The number is deserialized with `deserialize_any`, so these only work with
+//! self-describing formats, like JSON, TOML or YAML.
+//!
+//! *This module requires the `serde` Cargo feature*
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Visitor};
+
+use crate::{From, IntoResult};
+
+/// Visits any number and casts it into `Dst`
+#[allow(dead_code)]
+struct Checked<Dst>(PhantomData<fn() -> Dst>);
+
+#[allow(dead_code)]
+impl<Dst> Checked<Dst> {
+    fn narrow<Src, E>(x: Src) -> Result<Dst, E>
+    where
+        Src: fmt::Display + Copy,
+        Dst: From<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+        E: de::Error,
+    {
+        Dst::cast(x).into_result().map_err(|error| {
+            E::custom(format_args!(
+                "{} doesn't fit in `{}`: {}",
+                x,
+                core::any::type_name::<Dst>(),
+                error
+            ))
+        })
+    }
+}
+
+impl<'de, Dst> Visitor<'de> for Checked<Dst>
+where
+    Dst: From<i64> + From<u64> + From<i128> + From<u128> + From<f64>,
+    <Dst as From<i64>>::Output: IntoResult<Dst>,
+    <Dst as From<u64>>::Output: IntoResult<Dst>,
+    <Dst as From<i128>>::Output: IntoResult<Dst>,
+    <Dst as From<u128>>::Output: IntoResult<Dst>,
+    <Dst as From<f64>>::Output: IntoResult<Dst>,
+{
+    type Value = Dst;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a number that fits in `{}`",
+            core::any::type_name::<Dst>()
+        )
+    }
+
+    // the narrower types are forwarded to these by the default impls
+
+    fn visit_i64<E: de::Error>(self, x: i64) -> Result<Dst, E> {
+        Self::narrow(x)
+    }
+
+    fn visit_u64<E: de::Error>(self, x: u64) -> Result<Dst, E> {
+        Self::narrow(x)
+    }
+
+    fn visit_i128<E: de::Error>(self, x: i128) -> Result<Dst, E> {
+        Self::narrow(x)
+    }
+
+    fn visit_u128<E: de::Error>(self, x: u128) -> Result<Dst, E> {
+        Self::narrow(x)
+    }
+
+    fn visit_f64<E: de::Error>(self, x: f64) -> Result<Dst, E> {
+        Self::narrow(x)
+    }
+}
+
+macro_rules! checked {
+    ($($f:ident: $dst:ident,)+) => {
+        $(
+            dst! {
+                $dst,
+
+                #[doc = concat!("Deserializes any number and casts it into `", stringify!($dst), "`")]
+                pub fn $f<'de, D>(deserializer: D) -> Result<$dst, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    deserializer.deserialize_any(Checked(PhantomData))
+                }
+            }
+        )+
+    }
+}
+
+checked! {
+    checked_f32: f32,
+    checked_f64: f64,
+    checked_i8: i8,
+    checked_i16: i16,
+    checked_i32: i32,
+    checked_i64: i64,
+    checked_i128: i128,
+    checked_isize: isize,
+    checked_u8: u8,
+    checked_u16: u16,
+    checked_u32: u32,
+    checked_u64: u64,
+    checked_u128: u128,
+    checked_usize: usize,
+}
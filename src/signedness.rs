@@ -0,0 +1,164 @@
+//! Conversions between the signed and unsigned integers of the same width
+
+use crate::{fail, Error, Primitive};
+
+/// Integers and their same-width signed and unsigned counterparts
+///
+/// See the `SignedOf` and `UnsignedOf` type aliases, and the `to_signed`,
+/// `to_unsigned`, `to_signed_bits` and `to_unsigned_bits` functions.
+pub trait Signedness: Primitive {
+    /// The signed integer of the same width (`i8` for `u8` and `i8`)
+    type Signed: Signedness<Signed = Self::Signed, Unsigned = Self::Unsigned>;
+    /// The unsigned integer of the same width (`u8` for `u8` and `i8`)
+    type Unsigned: Signedness<Signed = Self::Signed, Unsigned = Self::Unsigned>;
+
+    /// Checked cast into the signed counterpart
+    fn to_signed(self) -> Result<Self::Signed, Error>;
+
+    /// Checked cast into the unsigned counterpart
+    fn to_unsigned(self) -> Result<Self::Unsigned, Error>;
+
+    /// Reinterprets the bits of `self` as the signed counterpart
+    fn to_signed_bits(self) -> Self::Signed;
+
+    /// Reinterprets the bits of `self` as the unsigned counterpart
+    fn to_unsigned_bits(self) -> Self::Unsigned;
+}
+
+/// The signed integer of the same width as `T`
+pub type SignedOf<T> = <T as Signedness>::Signed;
+
+/// The unsigned integer of the same width as `T`
+pub type UnsignedOf<T> = <T as Signedness>::Unsigned;
+
+macro_rules! signedness {
+    ($($unsigned:ident, $signed:ident;)+) => {
+        $(
+            impl Signedness for $unsigned {
+                type Signed = $signed;
+                type Unsigned = $unsigned;
+
+                #[inline]
+                fn to_signed(self) -> Result<$signed, Error> {
+                    if self > $signed::MAX as $unsigned {
+                        Err(fail::<$unsigned, $signed, _>(Error::Overflow))
+                    } else {
+                        Ok(self as $signed)
+                    }
+                }
+
+                #[inline]
+                fn to_unsigned(self) -> Result<$unsigned, Error> {
+                    Ok(self)
+                }
+
+                #[inline]
+                fn to_signed_bits(self) -> $signed {
+                    self as $signed
+                }
+
+                #[inline]
+                fn to_unsigned_bits(self) -> $unsigned {
+                    self
+                }
+            }
+
+            impl Signedness for $signed {
+                type Signed = $signed;
+                type Unsigned = $unsigned;
+
+                #[inline]
+                fn to_signed(self) -> Result<$signed, Error> {
+                    Ok(self)
+                }
+
+                #[inline]
+                fn to_unsigned(self) -> Result<$unsigned, Error> {
+                    if self < 0 {
+                        Err(fail::<$signed, $unsigned, _>(Error::NegOverflow))
+                    } else {
+                        Ok(self as $unsigned)
+                    }
+                }
+
+                #[inline]
+                fn to_signed_bits(self) -> $signed {
+                    self
+                }
+
+                #[inline]
+                fn to_unsigned_bits(self) -> $unsigned {
+                    self as $unsigned
+                }
+            }
+        )+
+    }
+}
+
+signedness! {
+    u8, i8;
+    u16, i16;
+    u32, i32;
+    u64, i64;
+    u128, i128;
+    usize, isize;
+}
+
+/// Checked cast into the signed integer of the same width
+///
+/// ```
+/// use cast::Error;
+///
+/// # fn main() {
+/// assert_eq!(cast::to_signed(127u8), Ok(127i8));
+/// assert_eq!(cast::to_signed(128u8), Err(Error::Overflow));
+/// assert_eq!(cast::to_signed(-1i8), Ok(-1i8));
+/// # }
+/// ```
+#[inline]
+pub fn to_signed<T: Signedness>(x: T) -> Result<SignedOf<T>, Error> {
+    x.to_signed()
+}
+
+/// Checked cast into the unsigned integer of the same width
+///
+/// ```
+/// use cast::Error;
+///
+/// # fn main() {
+/// assert_eq!(cast::to_unsigned(1i32), Ok(1u32));
+/// assert_eq!(cast::to_unsigned(-1i32), Err(Error::NegOverflow));
+/// # }
+/// ```
+#[inline]
+pub fn to_unsigned<T: Signedness>(x: T) -> Result<UnsignedOf<T>, Error> {
+    x.to_unsigned()
+}
+
+/// Reinterprets the bits of `x` as the signed integer of the same width
+///
+/// This is `x as SignedOf<T>`, which wraps around for large values.
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::to_signed_bits(255u8), -1i8);
+/// # }
+/// ```
+#[inline]
+pub fn to_signed_bits<T: Signedness>(x: T) -> SignedOf<T> {
+    x.to_signed_bits()
+}
+
+/// Reinterprets the bits of `x` as the unsigned integer of the same width
+///
+/// This is `x as UnsignedOf<T>`, which wraps around for negative values.
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::to_unsigned_bits(-1i8), 255u8);
+/// # }
+/// ```
+#[inline]
+pub fn to_unsigned_bits<T: Signedness>(x: T) -> UnsignedOf<T> {
+    x.to_unsigned_bits()
+}
@@ -0,0 +1,258 @@
+//! Bulk cast kernels written so that the compiler vectorizes them
+//!
+//! These have the same semantics as the `slice` casts and `saturating`, but
+//! process the input in fixed-size chunks whose range checks don't branch per
+//! element, which lets LLVM turn them into SIMD code on every target that has
+//! it. The conversions themselves are done with `as`, which vectorizes where
+//! the target has saturating vector conversions, like ARM and AArch64, and the
+//! widening ones everywhere. When a chunk contains an element that fails to be
+//! casted, the chunk is re-processed element by element to report the first
+//! failure exactly.
+//!
+//! The checked and widening casts of the `slice` module and `cast::vec` run
+//! these kernels for the types they cover, so most code gets them by enabling
+//! the feature.
+//!
+//! ```
+//! use cast::slice::SliceError;
+//!
+//! # fn main() {
+//! let samples = [0.5f32, 254.9, -0.9, 17.];
+//! let mut pixels = [0u8; 4];
+//!
+//! assert_eq!(cast::simd::u8_from_f32(&samples, &mut pixels), Ok(&mut [0, 254, 0, 17][..]));
+//!
+//! match cast::simd::u8_from_f32(&[1., f32::NAN], &mut pixels) {
+//!     Err(SliceError::Element(e)) => assert_eq!(e.index(), 1),
+//!     _ => unreachable!(),
+//! }
+//! # }
+//! ```
+//!
+//! With the `std` feature enabled, on x86 and x86_64 these also detect at
+//! runtime whether the CPU supports AVX2 and, if so, run a copy of the kernel
+//! compiled for it, so binaries built for the baseline target still get the
+//! wider vectors. AArch64 targets have NEON in their baseline, so there the
+//! kernels always use it.
+//!
+//! *This module requires the `simd` Cargo feature*
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::slice::{self, SliceError};
+use crate::ElementError;
+
+// enough elements to fill a 512-bit register with `f32`s
+#[allow(dead_code)]
+const LANES: usize = 16;
+
+/// Checked cast of a slice, starting at `offset` in the original input, with
+/// the error index adjusted accordingly
+#[allow(dead_code)]
+fn scalar<'a, Src, Dst>(
+    src: &[Src],
+    dst: &'a mut [Dst],
+    offset: usize,
+) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: crate::From<Src>,
+    <Dst as crate::From<Src>>::Output: crate::IntoResult<Dst>,
+{
+    slice::scalar(src, dst).map_err(|e| match e {
+        SliceError::Element(e) => {
+            SliceError::Element(ElementError::new(offset + e.index(), e.value(), e.error()))
+        }
+        e => e,
+    })
+}
+
+/// One of the public functions of this module
+#[cfg(feature = "alloc")]
+type Kernel<Src, Dst> = for<'a> fn(&[Src], &'a mut [Dst]) -> Result<&'a mut [Dst], SliceError<Src>>;
+
+/// Checked cast of a vector with `kernel`; see `From::vec`
+#[cfg(feature = "alloc")]
+#[allow(dead_code)]
+pub(crate) fn vec<Src, Dst>(
+    v: Vec<Src>,
+    kernel: Kernel<Src, Dst>,
+) -> Result<Vec<Dst>, ElementError<Src>>
+where
+    Dst: Copy + Default,
+{
+    let mut dst = alloc::vec![Dst::default(); v.len()];
+    match kernel(&v, &mut dst) {
+        Ok(_) => Ok(dst),
+        Err(SliceError::Element(e)) => Err(e),
+        Err(SliceError::BufferTooSmall) => unreachable!("`dst` is as long as `v`"),
+    }
+}
+
+/// Defines a public cast of a slice whose body is `$body`
+///
+/// The body gets inlined into two functions: one compiled for the target's
+/// baseline and, when runtime detection is available, one compiled for AVX2.
+/// The public function picks between them.
+#[allow(unused_macros)]
+macro_rules! kernel {
+    (
+        $(#[$attr:meta])*
+        fn $name:ident($x:ident: &[$src:ident], $y:ident: &mut [$dst:ident]) $body:block
+    ) => {
+        $(#[$attr])*
+        pub fn $name<'a>(
+            $x: &[$src],
+            $y: &'a mut [$dst],
+        ) -> Result<&'a mut [$dst], SliceError<$src>> {
+            #[inline(always)]
+            fn kernel<'a>(
+                $x: &[$src],
+                $y: &'a mut [$dst],
+            ) -> Result<&'a mut [$dst], SliceError<$src>> $body
+
+            #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+            {
+                // only sound to call after checking that the CPU supports AVX2
+                #[allow(unsafe_code)]
+                #[target_feature(enable = "avx2")]
+                unsafe fn avx2<'a>(
+                    $x: &[$src],
+                    $y: &'a mut [$dst],
+                ) -> Result<&'a mut [$dst], SliceError<$src>> {
+                    kernel($x, $y)
+                }
+
+                if std::is_x86_feature_detected!("avx2") {
+                    // SAFETY: the CPU supports AVX2
+                    #[allow(unsafe_code)]
+                    return unsafe { avx2($x, $y) };
+                }
+            }
+
+            kernel($x, $y)
+        }
+    };
+}
+
+macro_rules! checked {
+    ($($(#[$attr:meta])* $name:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            dst! {
+                $dst,
+
+                kernel! {
+                    $(#[$attr])*
+                    fn $name(src: &[$src], dst: &mut [$dst]) {
+                        let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+                        let range = <$dst as crate::SourceRange<$src>>::source_range();
+                        let (min, max) = (*range.start(), *range.end());
+
+                        let mut xs = src.chunks_exact(LANES);
+                        let mut ys = dst.chunks_exact_mut(LANES);
+                        for (i, (xs, ys)) in (&mut xs).zip(&mut ys).enumerate() {
+                            // NaN fails both comparisons
+                            let ok = xs.iter().fold(true, |ok, &x| ok & (x >= min) & (x <= max));
+
+                            if ok {
+                                for (y, &x) in ys.iter_mut().zip(xs) {
+                                    *y = x as $dst;
+                                }
+                            } else {
+                                scalar(xs, ys, i * LANES)?;
+                            }
+                        }
+                        scalar(xs.remainder(), ys.into_remainder(), src.len() / LANES * LANES)?;
+
+                        Ok(dst)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! saturating {
+    ($($(#[$attr:meta])* $name:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            dst! {
+                $dst,
+
+                kernel! {
+                    $(#[$attr])*
+                    fn $name(src: &[$src], dst: &mut [$dst]) {
+                        let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+                        // `as` saturates and maps NaN to zero, like `saturating`;
+                        // on ARM and AArch64 it compiles to vector conversions and
+                        // narrowings that do the same
+                        for (y, &x) in dst.iter_mut().zip(src) {
+                            *y = x as $dst;
+                        }
+
+                        Ok(dst)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! infallible {
+    ($($(#[$attr:meta])* $name:ident: $src:ident => $dst:ident;)+) => {
+        $(
+            dst! {
+                $dst,
+
+                kernel! {
+                    $(#[$attr])*
+                    fn $name(src: &[$src], dst: &mut [$dst]) {
+                        let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+                        for (y, &x) in dst.iter_mut().zip(src) {
+                            *y = x as $dst;
+                        }
+
+                        Ok(dst)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+checked! {
+    /// Checked cast of a slice of `f32`s into `u8`s; see `slice::u8`
+    u8_from_f32: f32 => u8;
+    /// Checked cast of a slice of `f32`s into `i16`s; see `slice::i16`
+    i16_from_f32: f32 => i16;
+}
+
+saturating! {
+    /// Saturating cast of a slice of `f32`s into `u8`s; see `cast::saturating`
+    ///
+    /// Only fails if `dst` is too small.
+    u8_from_f32_saturating: f32 => u8;
+    /// Saturating cast of a slice of `f32`s into `i16`s; see
+    /// `cast::saturating`
+    ///
+    /// Only fails if `dst` is too small.
+    i16_from_f32_saturating: f32 => i16;
+}
+
+infallible! {
+    /// Lossless cast of a slice of `u8`s into `f32`s
+    ///
+    /// Only fails if `dst` is too small.
+    f32_from_u8: u8 => f32;
+    /// Lossless cast of a slice of `i16`s into `f32`s
+    ///
+    /// Only fails if `dst` is too small.
+    f32_from_i16: i16 => f32;
+    /// Lossless cast of a slice of `u8`s into `i16`s
+    ///
+    /// Only fails if `dst` is too small.
+    i16_from_u8: u8 => i16;
+}
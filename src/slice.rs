@@ -0,0 +1,251 @@
+//! Element-wise casts of whole slices into caller provided buffers
+//!
+//! These don't allocate, so they are available in `no_std` code. The output
+//! buffer is an initialized `&mut [Dst]` slice because writing into
+//! uninitialized memory would need `unsafe` code; for numeric primitives
+//! zeroing it is cheap.
+//!
+//! ```
+//! use cast::slice::SliceError;
+//!
+//! # fn main() {
+//! let mut buf = [0u8; 4];
+//!
+//! assert_eq!(cast::slice::u8(&[1u16, 2, 3], &mut buf), Ok(&mut [1, 2, 3][..]));
+//! assert_eq!(cast::slice::u8(&[0i8; 5], &mut buf), Err(SliceError::BufferTooSmall));
+//!
+//! match cast::slice::u8(&[1u16, 256], &mut buf) {
+//!     Err(SliceError::Element(e)) => assert_eq!((e.index(), e.value()), (1, 256)),
+//!     _ => unreachable!(),
+//! }
+//! # }
+//! ```
+
+use core::fmt;
+
+use crate::{ElementError, From, IntoResult};
+
+/// The error of the slice casts
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SliceError<Src> {
+    /// The output buffer is shorter than the input slice
+    BufferTooSmall,
+    /// The first element that failed to be casted
+    Element(ElementError<Src>),
+}
+
+impl<Src: fmt::Display> fmt::Display for SliceError<Src> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SliceError::BufferTooSmall => f.write_str("Output buffer too small"),
+            SliceError::Element(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<Src: fmt::Debug + fmt::Display> core::error::Error for SliceError<Src> {}
+
+/// Checked cast of every element of `src` into the first `src.len()` elements
+/// of `dst`, whose type is inferred like with `cast::auto`
+///
+/// Returns the written part of `dst`. On error, the contents of `dst` are
+/// unspecified.
+///
+/// With the `simd` feature enabled, the casts that the `simd` module has
+/// kernels for, like `f32` into `u8`, run those.
+#[inline]
+pub fn auto<'a, Dst, Src>(src: &[Src], dst: &'a mut [Dst]) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    <Dst as From<Src>>::slice(src, dst)
+}
+
+/// `auto`, one element at a time
+#[inline]
+pub(crate) fn scalar<'a, Dst, Src>(
+    src: &[Src],
+    dst: &'a mut [Dst],
+) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let dst = dst.get_mut(..src.len()).ok_or(SliceError::BufferTooSmall)?;
+
+    for (index, (y, &x)) in dst.iter_mut().zip(src).enumerate() {
+        *y = match Dst::cast(x).into_result() {
+            Ok(y) => y,
+            Err(error) => return Err(SliceError::Element(ElementError::new(index, x, error))),
+        };
+    }
+
+    Ok(dst)
+}
+
+/// Casts `x`, the element at `index` of the input
+#[inline]
+fn element<Dst, Src>(index: usize, x: Src) -> Result<Dst, SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    Dst::cast(x)
+        .into_result()
+        .map_err(|error| SliceError::Element(ElementError::new(index, x, error)))
+}
+
+/// Checked cast of every `step`-th element of `src`, starting with the first
+/// one, into the first elements of `dst`
+///
+/// Use this to extract one channel of interleaved data: `&src[channel..]`
+/// with a `step` of the number of channels. The index of the error is the
+/// position of the element in `src`.
+///
+/// ```
+/// # fn main() {
+/// // left and right samples of a stereo signal
+/// let stereo = [1i32, -1, 2, -2, 3, -3];
+/// let mut right = [0i16; 3];
+///
+/// assert_eq!(cast::slice::step_by(&stereo[1..], 2, &mut right), Ok(&mut [-1, -2, -3][..]));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// This panics if `step` is `0`.
+#[inline]
+pub fn step_by<'a, Dst, Src>(
+    src: &[Src],
+    step: usize,
+    dst: &'a mut [Dst],
+) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    assert!(step != 0, "`step` must not be zero");
+
+    let dst = dst
+        .get_mut(..src.len().div_ceil(step))
+        .ok_or(SliceError::BufferTooSmall)?;
+
+    for (i, (y, &x)) in dst.iter_mut().zip(src.iter().step_by(step)).enumerate() {
+        *y = element(i * step, x)?;
+    }
+
+    Ok(dst)
+}
+
+/// Checked cast of interleaved data, e.g. the left and right samples of a
+/// stereo signal, into one buffer per channel
+///
+/// `src` consists of frames of `N` elements; the `n`-th element of every
+/// frame is casted into the `n`-th plane. The elements of an incomplete last
+/// frame are ignored. Returns the written part of each plane. The index of the
+/// error is the position of the element in `src`.
+///
+/// ```
+/// # fn main() {
+/// let rgb = [255u16, 0, 10, 128, 64, 20];
+/// let (mut r, mut g, mut b) = ([0u8; 2], [0u8; 2], [0u8; 2]);
+///
+/// let [r, g, b] = cast::slice::deinterleave(&rgb, [&mut r, &mut g, &mut b]).unwrap();
+/// assert_eq!((&r[..], &g[..], &b[..]), (&[255, 128][..], &[0, 64][..], &[10, 20][..]));
+/// # }
+/// ```
+pub fn deinterleave<'a, Dst, Src, const N: usize>(
+    src: &[Src],
+    planes: [&'a mut [Dst]; N],
+) -> Result<[&'a mut [Dst]; N], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let frames = src.len().checked_div(N).unwrap_or(0);
+    if planes.iter().any(|plane| plane.len() < frames) {
+        return Err(SliceError::BufferTooSmall);
+    }
+
+    let mut planes = planes.map(|plane| &mut plane[..frames]);
+    for (frame, xs) in src.chunks_exact(N.max(1)).take(frames).enumerate() {
+        for (channel, (plane, &x)) in planes.iter_mut().zip(xs).enumerate() {
+            plane[frame] = element(frame * N + channel, x)?;
+        }
+    }
+
+    Ok(planes)
+}
+
+/// Checked cast of one buffer per channel into interleaved data; the inverse
+/// of `deinterleave`
+///
+/// The `n`-th element of every frame of `dst` is casted from the `n`-th plane.
+/// If the planes have different lengths, only as many frames as the shortest
+/// plane has elements are written. Returns the written part of `dst`. The
+/// index of the error is the position in `dst` the element would have been
+/// written to.
+///
+/// ```
+/// # fn main() {
+/// let (left, right) = ([0.5f32, 1.], [-0.5f32, -1.]);
+/// let mut stereo = [0f64; 4];
+///
+/// assert_eq!(
+///     cast::slice::interleave([&left, &right], &mut stereo),
+///     Ok(&mut [0.5, -0.5, 1., -1.][..])
+/// );
+/// # }
+/// ```
+pub fn interleave<'a, Dst, Src, const N: usize>(
+    planes: [&[Src]; N],
+    dst: &'a mut [Dst],
+) -> Result<&'a mut [Dst], SliceError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let frames = planes.iter().map(|plane| plane.len()).min().unwrap_or(0);
+    let dst = dst
+        .get_mut(..frames * N)
+        .ok_or(SliceError::BufferTooSmall)?;
+
+    for (frame, ys) in dst.chunks_exact_mut(N.max(1)).enumerate() {
+        for (channel, (y, plane)) in ys.iter_mut().zip(&planes).enumerate() {
+            *y = element(frame * N + channel, plane[frame])?;
+        }
+    }
+
+    Ok(dst)
+}
+
+macro_rules! fns {
+    ($($ty:ident),+) => {
+        $(
+            dst! {
+                $ty,
+
+                /// Checked cast of a slice into a buffer; see `auto`
+                #[inline]
+                pub fn $ty<'a, Src>(src: &[Src], dst: &'a mut [$ty]) -> Result<&'a mut [$ty], SliceError<Src>>
+                where
+                    Src: Copy,
+                    $ty: From<Src>,
+                    <$ty as From<Src>>::Output: IntoResult<$ty>,
+                {
+                    auto(src, dst)
+                }
+            }
+        )+
+    }
+}
+
+fns!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,61 @@
+//! The out of line parts of the casts, for the `small-code` feature
+//!
+//! By default, every cast has its range check, and the reporting of its
+//! failures, compiled into it. With `small-code`, the casts into narrower
+//! integers check their range with the two functions of this module, on `i128`
+//! and `u128`, and failures are reported by a single function that takes the
+//! names of the types as arguments, instead of one copy of it per pair of
+//! types.
+//!
+//! Whether this makes a binary smaller depends on the target and on what it
+//! casts, so measure it. Sharing the reporting saves the most: with `tracing`,
+//! every pair of types otherwise gets its own copy of the event. The range
+//! checks are only a comparison or two, about as large as the call that
+//! replaces them, so sharing them pays off for targets where comparing wide
+//! integers takes many instructions. The casts from signed into unsigned
+//! integers of the same width or wider only check the sign of the source, so
+//! they still do it inline.
+
+use crate::Error;
+
+/// The error of casting `x`, of a signed type, into a `bits`-bit integer,
+/// signed if `signed`
+// unused if the casts into none of the narrower integers are enabled
+#[allow(dead_code)]
+#[inline(never)]
+pub fn from_signed(x: i128, bits: u32, signed: bool) -> Option<Error> {
+    let (min, max) = if signed {
+        (-1 << (bits - 1), (1 << (bits - 1)) - 1)
+    } else {
+        (0, (1 << bits) - 1)
+    };
+
+    if x < min {
+        Some(Error::NegOverflow)
+    } else if x > max {
+        Some(Error::Overflow)
+    } else {
+        None
+    }
+}
+
+/// Whether `x`, of an unsigned type, is at most `max`
+// see `from_signed`
+#[allow(dead_code)]
+#[inline(never)]
+pub fn from_unsigned(x: u128, max: u128) -> bool {
+    x <= max
+}
+
+/// `report`, with the names of the types instead of type parameters
+#[cfg(any(feature = "failure-hook", feature = "tracing"))]
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+#[cold]
+#[inline(never)]
+pub fn report(src: &'static str, dst: &'static str, error: Error) {
+    #[cfg(feature = "failure-hook")]
+    crate::hook::call(&error);
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(src, dst, error = ?error, "cast failed");
+}
@@ -0,0 +1,45 @@
+//! The range of source values that a cast accepts
+
+use core::ops::RangeInclusive;
+
+use crate::From;
+
+/// Reports which values of `Src` can be casted into `Self`
+///
+/// The range is computed with the exact same bounds the cast checks, so a
+/// value `x` of `Src` converts successfully if, and only if,
+/// `source_range().contains(&x)`, with one exception: casts between floats
+/// also accept the infinities and NaN.
+///
+/// For float sources the bounds are the extreme float values that convert
+/// successfully, which aren't always the integer bounds of `Self`: for
+/// example, because casts truncate, `-0.99` can be casted into `u8` and, as
+/// `f32` can't represent `i64::MAX`, the largest `f32` that can be casted into
+/// `i64` is a bit smaller than that.
+pub trait SourceRange<Src>: From<Src> {
+    /// The range of values of `Src` that can be casted into `Self`
+    fn source_range() -> RangeInclusive<Src>;
+}
+
+/// Returns the range of values of `Src` that can be casted into `Dst`
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::source_range::<u8, i32>(), 0..=255);
+/// assert_eq!(cast::source_range::<i8, u16>(), 0..=127);
+///
+/// // casts truncate towards zero, but only below zero
+/// let range = cast::source_range::<u32, f64>();
+/// assert!(range.contains(&-0.5));
+/// assert!(!range.contains(&-1.0));
+/// assert!(range.contains(&4_294_967_295.0));
+/// assert!(!range.contains(&4_294_967_295.5));
+/// # }
+/// ```
+#[inline]
+pub fn source_range<Dst, Src>() -> RangeInclusive<Src>
+where
+    Dst: SourceRange<Src>,
+{
+    Dst::source_range()
+}
@@ -0,0 +1,128 @@
+//! Splitting integers into, and joining them from, their high and low halves
+
+use crate::pointer::{Isize, Usize};
+use crate::{Error, From, IntoResult, Promote, Signedness, UnsignedOf};
+
+/// The type of twice the width of `T`, e.g. `DoubleWidth<u32>` is `u64`
+///
+/// See `Promote` for the full mapping.
+pub type DoubleWidth<T> = <T as Promote>::Output;
+
+/// The integer type of half the width of `T`, e.g. `HalfWidth<u64>` is `u32`
+pub type HalfWidth<T> = <T as Split>::Half;
+
+/// Integers that can be split into two integers of half their width
+///
+/// The high half has the signedness of `Self` and carries its sign; the low
+/// half is always unsigned. `u8` and `i8` have no narrower counterpart, and
+/// `usize` and `isize` split into halves of half the pointer width.
+pub trait Split: Copy {
+    /// The integer type of half the width, with the same signedness
+    type Half: Signedness;
+
+    /// Splits `self` into its high and low halves
+    fn split(self) -> (Self::Half, UnsignedOf<Self::Half>);
+
+    /// Joins the high and low halves back together; the inverse of `split`
+    fn join(hi: Self::Half, lo: UnsignedOf<Self::Half>) -> Self;
+}
+
+macro_rules! split {
+    ($($ty:ident => $half:ident;)+) => {
+        $(
+            impl Split for $ty {
+                type Half = $half;
+
+                #[inline]
+                fn split(self) -> ($half, UnsignedOf<$half>) {
+                    (
+                        (self >> $half::BITS) as $half,
+                        self as UnsignedOf<$half>,
+                    )
+                }
+
+                #[inline]
+                fn join(hi: $half, lo: UnsignedOf<$half>) -> $ty {
+                    (hi as $ty) << $half::BITS | lo as $ty
+                }
+            }
+        )+
+    }
+}
+
+split! {
+    u16 => u8;
+    u32 => u16;
+    u64 => u32;
+    u128 => u64;
+    i16 => i8;
+    i32 => i16;
+    i64 => i32;
+    i128 => i64;
+}
+
+// `usize` and `isize` split like the fixed-size integer of the same width
+macro_rules! pointer {
+    ($($ty:ident: $fixed:ident),+) => {
+        $(
+            impl Split for $ty {
+                type Half = <$fixed as Split>::Half;
+
+                #[inline]
+                fn split(self) -> (Self::Half, UnsignedOf<Self::Half>) {
+                    (self as $fixed).split()
+                }
+
+                #[inline]
+                fn join(hi: Self::Half, lo: UnsignedOf<Self::Half>) -> $ty {
+                    <$fixed as Split>::join(hi, lo) as $ty
+                }
+            }
+        )+
+    }
+}
+
+pointer!(usize: Usize, isize: Isize);
+
+/// Splits `x` into its high and low halves
+///
+/// ```
+/// # fn main() {
+/// assert_eq!(cast::split(0x1234_5678u32), (0x1234u16, 0x5678u16));
+/// assert_eq!(cast::split(-2i64), (-1i32, 0xffff_fffeu32));
+/// # }
+/// ```
+#[inline]
+pub fn split<T: Split>(x: T) -> (HalfWidth<T>, UnsignedOf<HalfWidth<T>>) {
+    x.split()
+}
+
+/// Checked join of a high and a low half into an integer of type `Dst`
+///
+/// `hi` and `lo` can be of any type; each is casted into its half first, so
+/// the join fails if either doesn't fit. This is handy when the halves come out
+/// of wider intermediate computations, like the partial products of a wide
+/// multiplication.
+///
+/// ```
+/// use cast::Error;
+///
+/// # fn main() {
+/// assert_eq!(cast::join::<u64, _, _>(1u32, 2u32), Ok(0x1_0000_0002));
+/// assert_eq!(cast::join::<u64, _, _>(1u64 << 32, 0u64), Err(Error::Overflow));
+/// assert_eq!(cast::join::<i32, _, _>(-1i32, -1i32), Err(Error::NegOverflow));
+/// # }
+/// ```
+#[inline]
+pub fn join<Dst, Hi, Lo>(hi: Hi, lo: Lo) -> Result<Dst, Error>
+where
+    Dst: Split,
+    HalfWidth<Dst>: From<Hi>,
+    UnsignedOf<HalfWidth<Dst>>: From<Lo>,
+    <HalfWidth<Dst> as From<Hi>>::Output: IntoResult<HalfWidth<Dst>>,
+    <UnsignedOf<HalfWidth<Dst>> as From<Lo>>::Output: IntoResult<UnsignedOf<HalfWidth<Dst>>>,
+{
+    let hi = <HalfWidth<Dst> as From<Hi>>::cast(hi).into_result()?;
+    let lo = <UnsignedOf<HalfWidth<Dst>> as From<Lo>>::cast(lo).into_result()?;
+    Ok(Dst::join(hi, lo))
+}
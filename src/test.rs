@@ -6,12 +6,18 @@ macro_rules! promote_and_back {
             $(
                 mod $src {
                     mod from {
-                        use crate::From;
+                        use crate::{From, IntoResult};
 
                         $(
                             quickcheck! {
+                                // with `portable`, the casts from and into
+                                // `usize` and `isize` return a `Result`, which
+                                // must be `Ok` for a promotion
                                 fn $dst(src: $src) -> bool {
-                                    $src::cast($dst::cast(src)).is_ok()
+                                    match $dst::cast(src).into_result() {
+                                        Ok(dst) => $src::cast(dst).is_ok(),
+                                        Err(_) => false,
+                                    }
                                 }
                             }
                          )+
@@ -22,6 +28,20 @@ macro_rules! promote_and_back {
     }
 }
 
+#[cfg(target_pointer_width = "16")]
+promote_and_back! {
+    i8    => f32, f64,     i16, isize, i32, i64, i128                            ;
+    i16   => f32, f64,                 i32, i64, i128                            ;
+    isize => f32, f64,                 i32, i64, i128                            ;
+    i32   =>      f64,                      i64, i128                            ;
+    i64   =>                                     i128                            ;
+    u8    => f32, f64,     i16, isize, i32, i64, i128, u16, usize, u32, u64, u128;
+    u16   => f32, f64,                 i32, i64, i128,             u32, u64, u128;
+    usize => f32, f64,                 i32, i64, i128,             u32, u64, u128;
+    u32   =>      f64,                      i64, i128,                  u64, u128;
+    u64   =>                                     i128,                       u128;
+}
+
 #[cfg(target_pointer_width = "32")]
 promote_and_back! {
     i8    => f32, f64,     i16, i32, isize, i64, i128                            ;
@@ -81,6 +101,15 @@ macro_rules! symmetric_cast_between {
     }
 }
 
+#[cfg(target_pointer_width = "16")]
+symmetric_cast_between! {
+    u8    =>           i8                      ;
+    u16   =>           i8, i16                 ;
+    usize =>           i8, i16                 ;
+    u32   =>           i8, i16, isize, i32     ;
+    u64   =>           i8, i16, isize, i32, i64;
+}
+
 #[cfg(target_pointer_width = "32")]
 symmetric_cast_between! {
     u8    =>           i8                      ;
@@ -206,3 +235,3178 @@ fn gh23_lossless_integer_max_min_to_float() {
     assert_eq!(Ok(i16::MIN), super::i16(-32_768f64));
     assert_eq!(Ok(i32::MIN), super::i32(-2_147_483_648f64));
 }
+
+mod checked {
+    use crate::{Checked, Error};
+
+    quickcheck! {
+        fn add_u8_to_u32(acc: u32, x: u8) -> bool {
+            (Checked::new(acc) + x).get() == acc.checked_add(u32::from(x)).ok_or(Error::Overflow)
+        }
+
+        fn sub_i8_from_u16(acc: u16, x: i8) -> bool {
+            let expected = if x < 0 {
+                Err(Error::NegOverflow)
+            } else {
+                acc.checked_sub(x as u16).ok_or(Error::NegOverflow)
+            };
+
+            (Checked::new(acc) - x).get() == expected
+        }
+    }
+
+    #[test]
+    fn errors_are_sticky() {
+        let mut x = Checked::new(0i8);
+        x -= 100u8;
+        x -= 100u8;
+        x += 127u8;
+        assert_eq!(x.get(), Err(Error::NegOverflow));
+    }
+
+    #[test]
+    fn signed_mul() {
+        assert_eq!((Checked::new(-100i8) * 2u8).get(), Err(Error::NegOverflow));
+        assert_eq!((Checked::new(-100i8) * -2i8).get(), Err(Error::Overflow));
+        assert_eq!((Checked::new(-64i8) * 2u8).get(), Ok(-128));
+    }
+}
+
+#[test]
+fn fallback() {
+    use core::num::NonZeroU8;
+
+    use crate::{Error, Fallback, From, StdCompat};
+
+    assert_eq!(
+        Fallback::cast(1u8).map(Fallback::into_inner),
+        Ok(NonZeroU8::new(1).unwrap())
+    );
+    // the kind isn't meaningful: `TryFromIntError` doesn't say what went wrong
+    assert!(Fallback::<NonZeroU8>::cast(0u8).is_err());
+
+    // errors of this crate's kind go through as they are
+    assert_eq!(
+        Fallback::<StdCompat<u8>>::cast(-1i16),
+        Err(Error::NegOverflow)
+    );
+}
+
+#[test]
+fn display() {
+    use crate::Error;
+
+    assert_eq!(
+        format!("{}", Error::NegOverflow),
+        "Negative overflow during numeric conversion"
+    );
+    assert_eq!(format!("{:>8.3}", Error::NaN), "     Can");
+}
+
+#[test]
+fn std_error() {
+    use std::boxed::Box;
+    use std::error::Error;
+
+    fn parse(s: &str) -> Result<u8, Box<dyn Error>> {
+        Ok(crate::u8(s.parse::<i32>()?)?)
+    }
+
+    assert_eq!(parse("255").ok(), Some(255));
+    assert_eq!(
+        format!("{}", parse("-1").unwrap_err()),
+        "Negative overflow during numeric conversion"
+    );
+}
+
+#[cfg(feature = "ufmt")]
+#[test]
+fn ufmt() {
+    use std::string::String;
+
+    use crate::Error;
+
+    struct Buf(String);
+
+    impl ufmt::uWrite for Buf {
+        type Error = core::convert::Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    let mut buf = Buf(String::new());
+    ufmt::uwrite!(buf, "{} / {:?}", Error::Overflow, Error::NaN).unwrap();
+    assert_eq!(buf.0, "Overflow during numeric conversion / NaN");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use crate::Error;
+
+    for &e in [
+        Error::Infinite,
+        Error::NaN,
+        Error::Overflow,
+        Error::NegOverflow,
+    ]
+    .iter()
+    {
+        let json = serde_json::to_string(&e).unwrap();
+        assert_eq!(serde_json::from_str::<Error>(&json).unwrap(), e);
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Error::Overflow).unwrap(),
+        "\"Overflow\""
+    );
+    // data serialized before the rename
+    assert_eq!(
+        serde_json::from_str::<Error>("\"Underflow\"").unwrap(),
+        Error::NegOverflow
+    );
+}
+
+#[test]
+fn predicates() {
+    use crate::Error;
+
+    let all = [
+        Error::Infinite,
+        Error::NaN,
+        Error::Overflow,
+        Error::NegOverflow,
+    ];
+    for &e in all.iter() {
+        assert_eq!(e.is_infinite(), e == Error::Infinite);
+        assert_eq!(e.is_nan(), e == Error::NaN);
+        assert_eq!(e.is_overflow(), e == Error::Overflow);
+        assert_eq!(e.is_neg_overflow(), e == Error::NegOverflow);
+    }
+
+    let set: std::collections::BTreeSet<_> = all.iter().chain(all.iter()).cloned().collect();
+    assert_eq!(set.len(), all.len());
+}
+
+#[cfg(feature = "error-value")]
+#[test]
+fn error_value() {
+    use crate::{with_value, Error, Value};
+
+    let e = with_value::<i8, _>(-200i32).unwrap_err();
+    assert_eq!(e.error(), Error::NegOverflow);
+    assert_eq!(e.value(), Value::Int(-200));
+    assert_eq!(format!("{}", e), "got -200, expected ≥ -128");
+
+    let e = with_value::<u64, _>(f32::NAN).unwrap_err();
+    assert_eq!(format!("{}", e), "got NaN, expected a finite number");
+
+    assert_eq!(with_value::<u8, _>(255.0f64), Ok(255));
+}
+
+#[test]
+fn typed() {
+    use crate::{typed, CastError, Error};
+
+    assert_eq!(
+        typed::<u8, _>(-1i8),
+        Err(CastError::new(Error::NegOverflow))
+    );
+    assert_eq!(
+        format!("{}", typed::<i32, _>(f64::INFINITY).unwrap_err()),
+        "cannot cast f64 → i32: infinite value"
+    );
+    assert_eq!(typed::<u32, _>(1u8), Ok(1));
+}
+
+#[test]
+fn try_from_int_error() {
+    use core::convert::TryFrom;
+    use core::num::{IntErrorKind, TryFromIntError};
+
+    use crate::Error;
+
+    fn narrow(x: i64) -> Result<u8, TryFromIntError> {
+        let x = crate::u32(x)?;
+        u8::try_from(x)
+    }
+
+    assert_eq!(narrow(255), Ok(255));
+    assert_eq!(
+        format!("{}", narrow(-1).unwrap_err()),
+        "out of range integral type conversion attempted"
+    );
+    assert!(narrow(256).is_err());
+
+    assert_eq!(
+        Error::Overflow.int_error_kind(),
+        Some(IntErrorKind::PosOverflow)
+    );
+    assert_eq!(
+        Error::NegOverflow.int_error_kind(),
+        Some(IntErrorKind::NegOverflow)
+    );
+    assert_eq!(Error::Infinite.int_error_kind(), None);
+    assert_eq!(Error::NaN.int_error_kind(), None);
+    for e in [Error::Overflow, Error::NegOverflow] {
+        let kind = e.int_error_kind().unwrap();
+        assert_eq!(Error::from_int_error_kind(&kind), Some(e));
+    }
+    assert_eq!(Error::from_int_error_kind(&IntErrorKind::Empty), None);
+}
+
+#[test]
+fn infallible() {
+    use core::convert::TryFrom;
+
+    use crate::{Error, IntoResult};
+
+    fn widen<T>(x: T) -> Result<u32, Error>
+    where
+        u32: TryFrom<T>,
+        Error: From<<u32 as TryFrom<T>>::Error>,
+    {
+        Ok(u32::try_from(x)?)
+    }
+
+    assert_eq!(widen(1u8), Ok(1));
+    assert_eq!(Ok::<u8, core::convert::Infallible>(1).into_result(), Ok(1));
+}
+
+#[cfg(feature = "track-location")]
+#[test]
+fn track_location() {
+    let line = line!() + 1;
+    let e = crate::typed::<u8, _>(256u16).unwrap_err();
+
+    assert_eq!(e.location().file(), file!());
+    assert_eq!(e.location().line(), line);
+}
+
+#[test]
+fn code() {
+    use crate::Error;
+
+    let all = [
+        Error::Infinite,
+        Error::NaN,
+        Error::Overflow,
+        Error::NegOverflow,
+    ];
+    for &e in all.iter() {
+        assert_ne!(e.code(), 0);
+        assert_eq!(Error::from_code(e.code()), Some(e));
+    }
+
+    assert_eq!(Error::from_code(0), None);
+    assert_eq!(Error::from_code(u8::MAX), None);
+}
+
+#[test]
+fn narrow() {
+    use crate::{Error, From, NegOverflowError, OverflowError};
+
+    // the error type alone tells which way these casts can fail
+    let e: OverflowError = <u8 as From<u16>>::cast(256).unwrap_err();
+    assert_eq!(Error::from(e), Error::Overflow);
+    let e: NegOverflowError = <u8 as From<i8>>::cast(-1).unwrap_err();
+    assert_eq!(Error::from(e), Error::NegOverflow);
+
+    assert_eq!(format!("{}", OverflowError), format!("{}", Error::Overflow));
+    assert_eq!(crate::auto::<u8, _>(-1i8), Err(Error::NegOverflow));
+}
+
+#[test]
+#[allow(deprecated)]
+fn underflow_alias() {
+    use crate::Error;
+
+    assert_eq!(Error::Underflow, Error::NegOverflow);
+    assert!(matches!(crate::u8(-1i16), Err(Error::Underflow)));
+    assert!(Error::NegOverflow.is_underflow());
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn miette() {
+    use miette::Diagnostic;
+
+    use crate::{typed, Error, OverflowError};
+
+    let code = |d: &dyn Diagnostic| d.code().map(|c| format!("{}", c));
+    let help = |d: &dyn Diagnostic| d.help().map(|h| format!("{}", h));
+
+    assert_eq!(code(&Error::NaN).as_deref(), Some("cast::nan"));
+    assert_eq!(code(&OverflowError), code(&Error::Overflow));
+    assert_eq!(
+        help(&typed::<u8, _>(256i32).unwrap_err()).as_deref(),
+        Some("consider clamping the value first, or using a type wider than `u8`")
+    );
+
+    #[cfg(feature = "error-value")]
+    assert_eq!(
+        help(&crate::with_value::<i8, _>(-200i32).unwrap_err()).as_deref(),
+        Some("the value must be between -128 and 127")
+    );
+}
+
+#[test]
+fn help() {
+    use crate::Error;
+
+    let all = [
+        Error::Infinite,
+        Error::NaN,
+        Error::Overflow,
+        Error::NegOverflow,
+    ];
+    for (i, a) in all.iter().enumerate() {
+        assert!(a.help().starts_with("consider"));
+        for b in &all[i + 1..] {
+            assert_ne!(a.help(), b.help());
+        }
+    }
+}
+
+#[test]
+fn boundary_values() {
+    use core::convert::TryFrom;
+
+    use crate::BoundaryValues;
+
+    // the casts agree with `core` on every boundary value
+    for x in i128::boundary_values() {
+        assert_eq!(crate::u8(x).is_ok(), u8::try_from(x).is_ok());
+        assert_eq!(crate::i64(x).is_ok(), i64::try_from(x).is_ok());
+    }
+
+    for x in f64::boundary_values() {
+        let fits = x.is_finite() && x.trunc() >= i32::MIN as f64 && x.trunc() <= i32::MAX as f64;
+        if !fits {
+            assert!(crate::i32(x).is_err(), "{:e}", x);
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{arbitrary_boundary, BoundaryValues, Error};
+
+    let bytes = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    let mut u = Unstructured::new(&bytes);
+    for _ in 0..4 {
+        Error::arbitrary(&mut u).unwrap();
+    }
+
+    // odd bytes pick a boundary value
+    let mut u = Unstructured::new(&[1, 6]);
+    let x = arbitrary_boundary::<u16>(&mut u).unwrap();
+    assert!(u16::boundary_values().any(|y| y == x));
+}
+
+#[test]
+fn lossless() {
+    use crate::{BoundaryValues, From, IntoResult, Lossless};
+
+    fn roundtrips<S, D>()
+    where
+        S: BoundaryValues + From<D> + PartialEq + core::fmt::Debug,
+        D: Lossless<S>,
+        <S as From<D>>::Output: IntoResult<S>,
+    {
+        for x in S::boundary_values() {
+            assert_eq!(S::cast(D::cast(x)).into_result(), Ok(x));
+        }
+    }
+
+    roundtrips::<i8, f32>();
+    roundtrips::<i32, f64>();
+    roundtrips::<u16, f32>();
+    roundtrips::<u32, i64>();
+    roundtrips::<u64, i128>();
+
+    #[cfg(not(feature = "portable"))]
+    {
+        roundtrips::<usize, u64>();
+        roundtrips::<isize, i64>();
+        roundtrips::<usize, u128>();
+    }
+}
+
+#[test]
+fn promote() {
+    use crate::Promote;
+
+    fn u64(x: u64) -> bool {
+        x.promote() == u128::from(x)
+    }
+
+    fn i8(x: i8) -> bool {
+        x.promote() == i16::from(x)
+    }
+
+    quickcheck::quickcheck(u64 as fn(u64) -> bool);
+    quickcheck::quickcheck(i8 as fn(i8) -> bool);
+    assert_eq!(usize::MAX.promote().count_ones(), usize::BITS);
+    assert_eq!(isize::MIN.promote().count_zeros(), isize::BITS - 1);
+}
+
+#[test]
+fn widest() {
+    use crate::{widest, BoundaryValues};
+
+    // comparing in the widest type agrees with comparing the exact values
+    for a in i64::boundary_values() {
+        for b in u32::boundary_values() {
+            let (x, y) = widest(a, b);
+            assert_eq!(x < y, i128::from(a) < i128::from(b));
+        }
+    }
+
+    let (x, y) = widest(-1i8, 255u8);
+    assert_eq!((x, y), (-1i16, 255i16));
+    let (x, y) = widest(16_777_217u32, 0.5f32);
+    assert_eq!((x, y), (16_777_217f64, 0.5f64));
+}
+
+crate::assert_lossless!(u8 => u16, i32 => f64, u64 => i128);
+
+#[cfg(all(target_pointer_width = "64", not(feature = "portable")))]
+crate::assert_lossless!(u32 => usize, isize => i64);
+
+#[test]
+fn roundtrip() {
+    use crate::{BoundaryValues, Roundtrip};
+
+    fn check<A, B>()
+    where
+        A: BoundaryValues + Roundtrip<B> + PartialEq + core::fmt::Debug,
+    {
+        for x in A::boundary_values() {
+            assert_eq!(A::cast_back(x.cast_forth()), Ok(x));
+        }
+    }
+
+    check::<u8, i16>();
+    check::<i32, f64>();
+    check::<u64, u128>();
+    #[cfg(not(feature = "portable"))]
+    check::<isize, i128>();
+}
+
+#[test]
+fn try_cast() {
+    use crate::{Error, TryCast};
+
+    fn all<T>(xs: &[&dyn TryCast<T>]) -> Result<std::vec::Vec<T>, Error> {
+        xs.iter().map(|&x| x.try_cast()).collect()
+    }
+
+    assert_eq!(
+        all::<i64>(&[&1u8, &-1i32, &2.5f64]),
+        Ok(std::vec![1, -1, 2])
+    );
+    assert_eq!(all::<i64>(&[&1u8, &u64::MAX]), Err(Error::Overflow));
+    assert_eq!(TryCast::<f64>::try_cast(&1u8), Ok(1.));
+}
+
+#[test]
+fn source_range() {
+    use crate::{BoundaryValues, From, IntoResult, SourceRange};
+
+    fn check<Src, Dst>()
+    where
+        Src: BoundaryValues + PartialOrd + core::fmt::Debug,
+        Dst: SourceRange<Src>,
+        <Dst as From<Src>>::Output: IntoResult<Dst>,
+    {
+        let range = Dst::source_range();
+        for x in Src::boundary_values() {
+            let ok = Dst::cast(x).into_result().is_ok();
+            assert_eq!(range.contains(&x), ok, "{:?}", x);
+        }
+    }
+
+    macro_rules! check {
+        ($($src:ident => $($dst:ident),+;)+) => {
+            $($(check::<$src, $dst>();)+)+
+        }
+    }
+
+    check! {
+        f32 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+        f64 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+        i8 => f32, i8, i16, i128, u8, u16, u128;
+        i32 => f64, i8, i32, u8, u32, u64;
+        i64 => f32, i8, i32, i64, isize, u8, u32, u64, usize;
+        i128 => f64, i8, i64, i128, u8, u64, u128;
+        u8 => f32, i8, u8, i128;
+        u32 => f64, i8, i32, u8, u64;
+        u64 => f32, i8, i64, isize, u32, usize;
+        u128 => f32, f64, i8, i128, u64, usize;
+        usize => i32, u32, u64;
+        isize => i8, i64, u64;
+    }
+
+    // casts between floats also accept the infinities and NaN
+    assert_eq!(
+        crate::source_range::<f32, f64>(),
+        f32::MIN as f64..=f32::MAX as f64
+    );
+    assert_eq!(crate::source_range::<f64, f32>(), f32::MIN..=f32::MAX);
+}
+
+#[test]
+fn primitive() {
+    use crate::Primitive;
+
+    assert_eq!(<u8 as Primitive>::BITS, u8::BITS);
+    assert_eq!(<isize as Primitive>::BITS, isize::BITS);
+    assert_eq!(<f32 as Primitive>::BITS, 32);
+    assert_eq!(
+        (<f64 as Primitive>::SIGNED, <usize as Primitive>::SIGNED),
+        (true, false)
+    );
+    assert_eq!(<i128 as Primitive>::MIN, i128::MIN);
+    assert_eq!(<f64 as Primitive>::MAX, f64::MAX);
+}
+
+#[test]
+fn infallibly_fallibly() {
+    use crate::{Error, Fallibly, Infallibly, NegOverflowError};
+
+    fn widen<T: Infallibly<i64>>(x: T) -> i64 {
+        x.cast_into()
+    }
+
+    fn narrow<T: Fallibly<u8>>(x: T) -> Result<u8, T::Error> {
+        x.try_cast_into()
+    }
+
+    assert_eq!(widen(-1i8), -1);
+    assert_eq!(widen(u32::MAX), 4_294_967_295);
+    assert_eq!(narrow(-1i8), Err(NegOverflowError));
+    assert_eq!(narrow(1e3f32), Err(Error::Overflow));
+}
+
+#[test]
+fn signedness() {
+    use crate::{to_signed, to_signed_bits, to_unsigned, to_unsigned_bits, BoundaryValues};
+    use std::convert::TryFrom;
+
+    // the checked casts agree with `TryFrom` and the bitwise ones round-trip
+    for x in i64::boundary_values() {
+        assert_eq!(to_unsigned(x).ok(), u64::try_from(x).ok());
+        assert_eq!(to_signed_bits(to_unsigned_bits(x)), x);
+    }
+    for x in u16::boundary_values() {
+        assert_eq!(to_signed(x).ok(), i16::try_from(x).ok());
+        assert_eq!(to_unsigned_bits(to_signed_bits(x)), x);
+    }
+}
+
+#[test]
+fn split_join() {
+    use crate::{join, split, BoundaryValues, DoubleWidth, HalfWidth};
+
+    for x in i64::boundary_values() {
+        let (hi, lo) = split(x);
+        assert_eq!(join::<i64, _, _>(hi, lo), Ok(x));
+        assert_eq!(join::<i64, _, _>(i64::from(hi), u64::from(lo)), Ok(x));
+    }
+    for x in u128::boundary_values() {
+        let (hi, lo) = split(x);
+        assert_eq!(join::<u128, _, _>(hi, lo), Ok(x));
+    }
+
+    let _: DoubleWidth<u32> = 0u64;
+    let _: HalfWidth<i128> = 0i64;
+}
+
+#[test]
+fn tuple() {
+    use crate::{Error, From};
+
+    let (x, y, z) = <(u32, f64, i128)>::cast((1u8, 0.5f32, -1i64));
+    assert_eq!((x, y, z), (1, 0.5, -1));
+
+    const { assert!(!<(u32, f64) as From<(u8, f32)>>::FALLIBLE) };
+    const { assert!(<(u32, u8) as From<(u8, f32)>>::FALLIBLE) };
+
+    // the first failure wins
+    assert_eq!(
+        <(u8, u8, u8)>::cast((0u16, -1i16, f32::NAN)),
+        Err(Error::NegOverflow)
+    );
+    assert_eq!(<(u8,)>::cast((f32::NAN,)), Err(Error::NaN));
+
+    // the infallible casts work with the generic APIs too
+    assert_eq!(crate::auto::<(u16, f64), _>((1u8, 0.5f32)), Ok((1, 0.5)));
+    assert_eq!(
+        crate::slice::auto::<(u16,), _>(&[(1u8,), (2,)], &mut [(0,); 2]),
+        Ok(&mut [(1,), (2,)][..])
+    );
+}
+
+#[test]
+fn array() {
+    use crate::{Error, From};
+
+    const { assert!(!<[u32; 4] as From<[u8; 4]>>::FALLIBLE) };
+    const { assert!(<[u8; 4] as From<[u32; 4]>>::FALLIBLE) };
+
+    assert_eq!(
+        <[i64; 2]>::cast([i32::MIN, i32::MAX]),
+        [-2_147_483_648, 2_147_483_647]
+    );
+    assert_eq!(<[u8; 0]>::cast([0u32; 0]), Ok([]));
+
+    // the first failure wins
+    assert_eq!(<[u8; 3]>::cast([0i32, -1, 256]), Err(Error::NegOverflow));
+    assert_eq!(<[u8; 3]>::cast([0i32, 256, -1]), Err(Error::Overflow));
+    // the infallible casts work with the generic APIs too
+    assert_eq!(crate::auto::<[u16; 2], _>([1u8, 2]), Ok([1, 2]));
+    assert_eq!(
+        crate::slice::auto::<[u16; 2], _>(&[[1u8, 2]], &mut [[0; 2]]),
+        Ok(&mut [[1, 2]][..])
+    );
+
+    // and the elements after it aren't casted
+    #[cfg(feature = "failure-hook")]
+    assert_eq!(
+        failures(|| assert!(<[u8; 3]>::cast([-1i32, 256, -1]).is_err())),
+        [Error::NegOverflow]
+    );
+}
+
+#[test]
+fn option() {
+    use crate::{opt, CastOption, Error, From};
+
+    assert_eq!(<Option<u32>>::cast(Some(1u8)), Some(1));
+    assert_eq!(<Option<u8>>::cast(Some(-1i8)), Err(Error::NegOverflow));
+    assert_eq!(<Option<u8>>::cast(None::<i8>), Ok(None));
+
+    assert_eq!(opt::<i8, _>(Some(f64::NAN)), Err(Error::NaN));
+    assert_eq!(Some(1.5f64).cast_opt::<i8>(), Ok(Some(1)));
+    assert_eq!(None::<f64>.cast_opt::<i8>(), Ok(None));
+
+    // the infallible casts work with the generic APIs too
+    assert_eq!(crate::auto::<Option<u16>, _>(Some(1u8)), Ok(Some(1)));
+    assert_eq!(
+        crate::slice::auto::<Option<u16>, _>(&[Some(1u8), None], &mut [None; 2]),
+        Ok(&mut [Some(1), None][..])
+    );
+}
+
+#[test]
+fn transparent() {
+    use crate::{Error, From, NegOverflowError, Transparent};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Celsius(i8);
+
+    impl Transparent for Celsius {
+        type Inner = i8;
+
+        fn from_inner(inner: i8) -> Celsius {
+            Celsius(inner)
+        }
+
+        fn into_inner(self) -> i8 {
+            self.0
+        }
+    }
+
+    assert_eq!(Celsius::cast(-40i64), Ok(Celsius(-40)));
+    assert_eq!(Celsius::cast(f32::NAN), Err(Error::NaN));
+    assert_eq!(Celsius::cast(Celsius(1)), Celsius(1));
+    assert_eq!(crate::u8(Celsius(-1)), Err(NegOverflowError));
+    assert_eq!(crate::i16(Celsius(-1)), -1);
+    assert_eq!(<[Celsius; 2]>::cast([1u8, 2]), Ok([Celsius(1), Celsius(2)]));
+}
+
+#[test]
+fn proof() {
+    use crate::{Error, InRange, NonNegative, OverflowError};
+
+    let x = NonNegative::new(i8::MAX).unwrap();
+    assert_eq!(crate::u8(x), 127);
+    assert_eq!(crate::i8(x), Ok(127));
+    assert_eq!(crate::f32(x), 127.);
+    assert_eq!(NonNegative::new(0u128).map(NonNegative::get), Ok(0));
+    assert_eq!(NonNegative::new(i128::MIN), Err(crate::NegOverflowError));
+    assert_eq!(NonNegative::new(u8::MAX).map(NonNegative::get), Ok(255));
+    // reported once
+    #[cfg(feature = "failure-hook")]
+    assert_eq!(
+        failures(|| assert!(NonNegative::new(-1i32).is_err())),
+        [Error::NegOverflow]
+    );
+
+    let y = NonNegative::new(i64::MAX).unwrap();
+    assert_eq!(crate::u32(y), Err(OverflowError));
+
+    type Byte = InRange<u128, 0, 255>;
+    assert_eq!(Byte::new(u128::MAX), Err(Error::Overflow));
+    assert_eq!(Byte::new(256), Err(Error::Overflow));
+    assert_eq!(crate::u8(Byte::new(255).unwrap()), 255);
+    assert_eq!(crate::i16(Byte::new(255).unwrap()), 255);
+
+    type Offset = InRange<i64, -128, 127>;
+    assert_eq!(Offset::new(-129), Err(Error::NegOverflow));
+    assert_eq!(crate::i8(Offset::new(-128).unwrap()), -128);
+    assert_eq!(crate::f64(Offset::new(-128).unwrap()), -128.);
+}
+
+#[test]
+fn finite() {
+    use crate::{BoundaryValues, Error, Finite, RangeError};
+
+    assert_eq!(Finite::new(f64::NAN), Err(Error::NaN));
+    assert_eq!(Finite::new(f32::NEG_INFINITY), Err(Error::Infinite));
+
+    // agrees with the cast from the unwrapped float
+    for x in f64::boundary_values().filter(|x| x.is_finite()) {
+        let y = Finite::new(x).unwrap();
+        assert_eq!(crate::u32(y).map_err(Error::from), crate::u32(x));
+        assert_eq!(crate::i64(y).map_err(Error::from), crate::i64(x));
+    }
+    for x in f32::boundary_values().filter(|x| x.is_finite()) {
+        let y = Finite::new(x).unwrap();
+        assert_eq!(crate::u128(y).map_err(Error::from), crate::u128(x));
+        assert_eq!(crate::i8(y).map_err(Error::from), crate::i8(x));
+    }
+
+    assert_eq!(RangeError::from(crate::OverflowError), Error::Overflow);
+}
+
+#[test]
+fn classify() {
+    use crate::{auto, classify, BoundaryValues, Error, Fallibility, From, IntoResult};
+
+    // every error that a cast reports is allowed by its classification
+    fn check<S, D>()
+    where
+        S: BoundaryValues,
+        D: From<S>,
+        D::Output: IntoResult<D>,
+    {
+        let class = classify::<S, D>();
+        for x in S::boundary_values() {
+            let expected = match auto::<D, S>(x) {
+                Ok(_) => continue,
+                Err(Error::Overflow) => Fallibility::Overflow,
+                Err(Error::NegOverflow) => Fallibility::NegOverflow,
+                Err(_) => Fallibility::Fallible,
+            };
+            assert_eq!(class.or(expected), class);
+        }
+    }
+
+    macro_rules! pairs {
+        ($($src:ident),+) => {
+            $(
+                pairs!(@ $src: f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+            )+
+        };
+        (@ $src:ident: $($dst:ident),+) => {
+            $(
+                check::<$src, $dst>();
+            )+
+        };
+    }
+
+    pairs!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+    assert_eq!(classify::<(u8, i8), (u16, u16)>(), Fallibility::NegOverflow);
+    assert_eq!(classify::<(u16, i8), (u8, u16)>(), Fallibility::Fallible);
+    assert_eq!(classify::<[u16; 2], [u8; 2]>(), Fallibility::Overflow);
+}
+
+#[test]
+fn fit() {
+    use crate::{bits_required, smallest_fitting_type, BoundaryValues, IntType};
+    use std::convert::TryFrom;
+
+    // agrees with the fallible conversions into each type
+    for x in i128::boundary_values() {
+        let expected = if i8::try_from(x).is_ok() {
+            IntType::I8
+        } else if i16::try_from(x).is_ok() {
+            IntType::I16
+        } else if i32::try_from(x).is_ok() {
+            IntType::I32
+        } else if i64::try_from(x).is_ok() {
+            IntType::I64
+        } else {
+            IntType::I128
+        };
+        assert_eq!(smallest_fitting_type(x), expected);
+    }
+    for x in u128::boundary_values() {
+        let expected = if u8::try_from(x).is_ok() {
+            IntType::U8
+        } else if u16::try_from(x).is_ok() {
+            IntType::U16
+        } else if u32::try_from(x).is_ok() {
+            IntType::U32
+        } else if u64::try_from(x).is_ok() {
+            IntType::U64
+        } else {
+            IntType::U128
+        };
+        assert_eq!(smallest_fitting_type(x), expected);
+    }
+
+    assert_eq!(bits_required(i128::MIN), 128);
+    assert_eq!(bits_required(-1i8), 1);
+    assert_eq!(bits_required(u64::MAX), 64);
+}
+
+#[test]
+fn compare() {
+    use core::cmp::Ordering;
+
+    use crate::{cmp, eq, max, min, BoundaryValues, Error};
+
+    fn ints(a: i64, b: u64) -> bool {
+        cmp(a, b) == Some(i128::from(a).cmp(&i128::from(b)))
+            && cmp(b, a) == Some(i128::from(b).cmp(&i128::from(a)))
+    }
+    quickcheck::quickcheck(ints as fn(i64, u64) -> bool);
+
+    // exact where the integer is exactly representable as a float
+    fn floats(a: f64, b: i32) -> bool {
+        cmp(a, b) == a.partial_cmp(&f64::from(b)) && cmp(b, a) == f64::from(b).partial_cmp(&a)
+    }
+    quickcheck::quickcheck(floats as fn(f64, i32) -> bool);
+
+    for x in f64::boundary_values() {
+        assert_eq!(cmp(x, u128::MAX).is_none(), x.is_nan());
+        assert_eq!(cmp(x, i128::MIN).is_none(), x.is_nan());
+    }
+
+    // `u128::MAX as f32` rounds up to 2^128
+    assert_eq!(cmp(u128::MAX, u128::MAX as f32), Some(Ordering::Less));
+    assert_eq!(cmp(i128::MIN, i128::MIN as f64), Some(Ordering::Equal));
+    assert_eq!(cmp(i64::MAX, i64::MAX as f64), Some(Ordering::Less));
+    assert_eq!(cmp(-0.0f32, 0u8), Some(Ordering::Equal));
+    assert_eq!(cmp(0.5f64, 0i8), Some(Ordering::Greater));
+    assert_eq!(cmp(-0.5f64, 0i8), Some(Ordering::Less));
+    assert!(eq(-1i8, -1.0f64));
+    assert!(!eq(f64::NAN, f64::NAN));
+
+    assert_eq!(min::<i8, _, _>(f32::NAN, -3i64), Ok(-3));
+    assert_eq!(max::<i8, _, _>(-3i64, f32::NAN), Ok(-3));
+    assert_eq!(max::<i8, _, _>(u64::MAX, 0i8), Err(Error::Overflow));
+}
+
+#[test]
+fn slice() {
+    use crate::slice::{self, SliceError};
+    use std::vec::Vec;
+
+    fn agrees(xs: Vec<i32>) -> bool {
+        let mut buf = std::vec![0i16; xs.len() + 1];
+        let expected = xs.iter().position(|&x| crate::i16(x).is_err());
+
+        match slice::i16(&xs, &mut buf) {
+            Ok(ys) => {
+                expected.is_none()
+                    && ys.len() == xs.len()
+                    && ys.iter().zip(&xs).all(|(&y, &x)| i32::from(y) == x)
+            }
+            Err(SliceError::Element(e)) => {
+                Some(e.index()) == expected
+                    && e.value() == xs[e.index()]
+                    && crate::i16(e.value()) == Err(e.error())
+            }
+            Err(SliceError::BufferTooSmall) => false,
+        }
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<i32>) -> bool);
+
+    let mut buf = [0.; 2];
+    assert_eq!(slice::f32(&[1u8, 2], &mut buf), Ok(&mut [1., 2.][..]));
+    assert_eq!(
+        slice::auto(&[0u8; 3], &mut buf),
+        Err(SliceError::BufferTooSmall)
+    );
+    assert_eq!(
+        format!("{}", slice::u8(&[f32::NAN], &mut [0]).unwrap_err()),
+        "Element 0 (NaN): Cannot store NaN in type which does not support it"
+    );
+    // the message already includes the error of the element
+    assert!(std::error::Error::source(&slice::u8(&[f32::NAN], &mut [0]).unwrap_err()).is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec() {
+    use std::vec::Vec;
+
+    fn agrees(xs: Vec<i64>) -> bool {
+        let expected: Result<Vec<u32>, _> = xs.iter().map(|&x| crate::u32(x)).collect();
+        match crate::vec::<u32, _>(xs.clone()) {
+            Ok(ys) => expected == Ok(ys),
+            Err(e) => {
+                xs[..e.index()].iter().all(|&x| crate::u32(x).is_ok())
+                    && e.value() == xs[e.index()]
+                    && crate::u32(e.value()) == Err(e.error())
+            }
+        }
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<i64>) -> bool);
+
+    // same layout: the allocation is reused
+    let xs = std::vec![0i32, 1, 2];
+    let ptr = xs.as_ptr() as usize;
+    let ys = crate::vec::<u32, _>(xs).unwrap();
+    assert_eq!(ys.as_ptr() as usize, ptr);
+
+    // different layouts: the output is allocated once, with the exact capacity
+    let ys = crate::vec::<f32, _>((0..1000u16).map(|x| x as u8).collect()).unwrap();
+    assert_eq!(ys.capacity(), ys.len());
+    let ys = crate::vec::<u8, _>((0..1000u16).map(|x| x % 256).collect()).unwrap();
+    assert_eq!(ys.capacity(), ys.len());
+}
+
+#[test]
+fn cast_checked() {
+    use crate::{CastIterator, Error};
+
+    let mut it = [1.5f64, -1., f64::NAN].iter().copied().cast_checked::<u8>();
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.next_back(), Some(Err(Error::NaN)));
+    assert_eq!(it.next(), Some(Ok(1)));
+    assert_eq!(it.next(), Some(Err(Error::NegOverflow)));
+    assert_eq!(it.next(), None);
+
+    // infallible casts also yield `Result`s
+    let sum: Result<u32, Error> = (0u8..=255).cast_checked::<u32>().sum();
+    assert_eq!(sum, Ok(32_640));
+}
+
+#[test]
+fn saturating() {
+    use crate::{saturating, BoundaryValues, CastIterator};
+
+    fn clamp(x: f64) -> i128 {
+        if x.is_nan() {
+            0
+        } else if x >= 127. {
+            127
+        } else if x <= -128. {
+            -128
+        } else {
+            x as i128
+        }
+    }
+
+    for x in f64::boundary_values() {
+        assert_eq!(i128::from(saturating::<i8, _>(x)), clamp(x));
+    }
+    for x in i128::boundary_values() {
+        assert_eq!(
+            i128::from(saturating::<i8, _>(x)),
+            x.clamp(i8::MIN.into(), i8::MAX.into())
+        );
+    }
+
+    let xs: std::vec::Vec<u16> = [-1i32, 70_000, 7]
+        .iter()
+        .copied()
+        .cast_saturating()
+        .rev()
+        .collect();
+    assert_eq!(xs, [7, u16::MAX, 0]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_partition() {
+    use std::vec::Vec;
+
+    fn agrees(xs: Vec<f64>) -> bool {
+        let (ok, failed) = crate::vec_partition::<i16, _>(xs.clone());
+
+        ok.len() + failed.len() == xs.len()
+            && failed.iter().all(|e| {
+                xs[e.index()].to_bits() == e.value().to_bits()
+                    && crate::i16(e.value()) == Err(e.error())
+            })
+            && ok
+                == xs
+                    .iter()
+                    .filter_map(|&x| crate::i16(x).ok())
+                    .collect::<Vec<_>>()
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<f64>) -> bool);
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn simd() {
+    use std::vec::Vec;
+
+    use crate::{saturating, simd, slice, BoundaryValues};
+
+    fn agrees(xs: Vec<f32>) -> bool {
+        let n = xs.len();
+        let (mut a, mut b) = (std::vec![0u8; n], std::vec![0u8; n]);
+        let (mut c, mut d) = (std::vec![0i16; n], std::vec![0i16; n]);
+        let u8s = format!("{:?}", slice::scalar(&xs, &mut b));
+        let i16s = format!("{:?}", slice::scalar(&xs, &mut d));
+
+        // compared as text because the reported value may be NaN
+        format!("{:?}", simd::u8_from_f32(&xs, &mut a)) == u8s
+            && format!("{:?}", simd::i16_from_f32(&xs, &mut c)) == i16s
+            // these run the kernels too
+            && format!("{:?}", slice::u8(&xs, &mut a)) == u8s
+            && format!("{:?}", slice::i16(&xs, &mut c)) == i16s
+            && simd::u8_from_f32_saturating(&xs, &mut a).unwrap()
+                == xs
+                    .iter()
+                    .map(|&x| saturating::<u8, _>(x))
+                    .collect::<Vec<_>>()
+            && simd::i16_from_f32_saturating(&xs, &mut c).unwrap()
+                == xs
+                    .iter()
+                    .map(|&x| saturating::<i16, _>(x))
+                    .collect::<Vec<_>>()
+    }
+
+    // the boundary values repeated, so that they land in every lane of the
+    // vectorized chunks and in the remainder
+    let mut xs: Vec<f32> = f32::boundary_values()
+        .chain(f32::boundary_values().map(|x| x / 3.))
+        .collect();
+    for i in 0..xs.len() {
+        assert!(agrees(xs[i..].to_vec()));
+        assert!(agrees(std::vec![xs[i]; 37]));
+    }
+    xs.retain(|x| (0. ..256.).contains(x));
+    assert!(agrees(xs.clone()));
+
+    quickcheck::quickcheck(agrees as fn(Vec<f32>) -> bool);
+
+    // the first failure is reported, even past the first chunk
+    let mut xs = std::vec![1f32; 40];
+    xs[21] = -1.;
+    xs[30] = f32::NAN;
+    let mut ys = [0u8; 40];
+    match simd::u8_from_f32(&xs, &mut ys) {
+        Err(slice::SliceError::Element(e)) => assert_eq!(e.index(), 21),
+        _ => unreachable!(),
+    }
+
+    let (mut a, mut b, mut c) = ([0.; 5], [0; 2], [0.; 2]);
+    assert_eq!(
+        simd::f32_from_i16(&[i16::MIN, -1, 0, i16::MAX], &mut a),
+        Ok(&mut [-32768., -1., 0., 32767.][..])
+    );
+    assert_eq!(simd::i16_from_u8(&[0, 255], &mut b), Ok(&mut [0, 255][..]));
+    assert_eq!(
+        simd::f32_from_u8(&[0, 255], &mut c),
+        Ok(&mut [0., 255.][..])
+    );
+    assert_eq!(
+        simd::u8_from_f32(&[0.; 3], &mut [0; 2]),
+        Err(slice::SliceError::BufferTooSmall)
+    );
+    assert_eq!(
+        slice::f32(&[i16::MIN, 1], &mut a),
+        Ok(&mut [-32768., 1.][..])
+    );
+}
+
+#[cfg(all(feature = "simd", feature = "alloc"))]
+#[test]
+fn simd_vec() {
+    use std::vec::Vec;
+
+    use crate::slice::{self, SliceError};
+    use crate::BoundaryValues;
+
+    // `vec` runs the kernels of the `simd` module
+    fn agrees(xs: Vec<f32>) -> bool {
+        let (mut a, mut b) = (std::vec![0u8; xs.len()], std::vec![0i16; xs.len()]);
+
+        // compared as text because the reported value may be NaN
+        format!(
+            "{:?}",
+            crate::vec::<u8, _>(xs.clone()).map_err(SliceError::Element)
+        ) == format!("{:?}", slice::scalar(&xs, &mut a))
+            && format!(
+                "{:?}",
+                crate::vec::<i16, _>(xs.clone()).map_err(SliceError::Element)
+            ) == format!("{:?}", slice::scalar(&xs, &mut b))
+    }
+
+    for x in f32::boundary_values() {
+        assert!(agrees(std::vec![x; 37]));
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<f32>) -> bool);
+
+    assert_eq!(
+        crate::vec::<f32, _>(std::vec![0u8, 255]),
+        Ok(std::vec![0., 255.])
+    );
+    assert_eq!(
+        crate::vec::<i16, _>(std::vec![0u8, 255]),
+        Ok(std::vec![0, 255])
+    );
+    assert_eq!(
+        crate::vec::<f32, _>(std::vec![i16::MIN]),
+        Ok(std::vec![-32768.])
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par() {
+    use std::vec::Vec;
+
+    use crate::{par, slice, vec, vec_partition};
+
+    fn agrees(xs: Vec<i64>) -> bool {
+        let (mut a, mut b) = (std::vec![0u8; xs.len()], std::vec![0u8; xs.len()]);
+
+        par::slice(&xs, &mut a) == slice::u8(&xs, &mut b)
+            && par::vec::<u8, _>(xs.clone()) == vec::<u8, _>(xs.clone())
+            && par::vec_partition::<u8, _>(xs.clone()) == vec_partition::<u8, _>(xs)
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<i64>) -> bool);
+
+    // failures spread over several chunks and threads; the first one wins
+    let mut xs: Vec<i64> = (0..100_000).map(|x| x % 200).collect();
+    for &i in &[99_999, 70_000, 4096, 4095, 12] {
+        xs[i] = -1;
+        assert!(agrees(xs.clone()));
+        assert_eq!(par::vec::<u8, _>(xs.clone()).unwrap_err().index(), i);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn reinterpret() {
+    use crate::reinterpret::{self, ReinterpretError};
+
+    let mut xs = [1i16, -1, i16::MIN];
+    assert_eq!(reinterpret::slice::<u16, _>(&xs), [1, u16::MAX, 0x8000]);
+    assert_eq!(reinterpret::slice::<i8, _>(&xs).len(), 6);
+
+    reinterpret::slice_mut::<u16, _>(&mut xs)[1] = 2;
+    assert_eq!(xs, [1, 2, i16::MIN]);
+
+    let floats = [1.5f64, -0., f64::NAN];
+    for (bits, x) in reinterpret::slice::<u64, _>(&floats).iter().zip(&floats) {
+        assert_eq!(*bits, x.to_bits());
+    }
+
+    let mut words = [0u32; 4];
+    let bytes = reinterpret::slice_mut::<u8, _>(&mut words);
+    bytes[4..8].copy_from_slice(&7u32.to_ne_bytes());
+    assert_eq!(
+        reinterpret::try_slice::<u32, _>(&bytes[4..]),
+        Ok(&[7, 0, 0][..])
+    );
+    assert_eq!(
+        reinterpret::try_slice_mut::<u32, _>(&mut bytes[4..7]),
+        Err(ReinterpretError::Size)
+    );
+    assert_eq!(
+        reinterpret::try_slice::<u32, _>(&bytes[2..6]),
+        Err(ReinterpretError::Misaligned)
+    );
+    assert_eq!(reinterpret::try_slice::<u32, _>(&bytes[3..3]), Ok(&[][..]));
+    assert_eq!(
+        format!("{}", ReinterpretError::Misaligned),
+        "Slice is not aligned for the destination type"
+    );
+}
+
+#[cfg(all(feature = "heapless", feature = "arrayvec"))]
+#[test]
+fn fixed_capacity_vecs() {
+    use std::vec::Vec;
+
+    use arrayvec::ArrayVec;
+
+    use crate::{array_vec, heapless_vec, vec};
+
+    fn agrees(xs: Vec<i32>) -> bool {
+        let xs = &xs[..xs.len().min(16)];
+        let h = heapless::Vec::<i32, 16>::from_slice(xs).unwrap();
+        let a: ArrayVec<i32, 16> = xs.iter().copied().collect();
+        let expected = vec::<i8, _>(xs.to_vec());
+
+        heapless_vec::<i8, _, 16>(h).map(|v| v.to_vec()) == expected
+            && array_vec::<i8, _, 16>(a).map(|v| v.to_vec()) == expected
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<i32>) -> bool);
+
+    let h = heapless_vec::<u64, _, 3>(heapless::Vec::<u8, 3>::new()).unwrap();
+    assert_eq!((h.len(), h.capacity()), (0, 3));
+    let a = array_vec::<u64, _, 5>(ArrayVec::<u8, 5>::from([1, 2, 3, 4, 5])).unwrap();
+    assert_eq!((&a[..], a.capacity()), (&[1, 2, 3, 4, 5][..], 5));
+}
+
+#[test]
+fn bytes() {
+    use crate::bytes::{self, LengthError};
+
+    fn roundtrip(x: i64, y: u16, z: f64) -> bool {
+        bytes::i64_le(bytes::le(x)) == x
+            && bytes::i64_be(&bytes::be(x)) == x
+            && bytes::u16_ne(&bytes::ne(y)[..]) == Ok(y)
+            && bytes::f64_be(bytes::be(z)).to_bits() == z.to_bits()
+    }
+    quickcheck::quickcheck(roundtrip as fn(i64, u16, f64) -> bool);
+
+    assert_eq!(
+        bytes::u128_be(&[0; 15][..]),
+        Err(LengthError {
+            expected: 16,
+            found: 15
+        })
+    );
+    assert_eq!(
+        bytes::f32_le(&[][..]),
+        Err(LengthError {
+            expected: 4,
+            found: 0
+        })
+    );
+    assert_eq!(bytes::le(1.5f32), 1.5f32.to_le_bytes());
+    assert_eq!(
+        format!(
+            "{}",
+            LengthError {
+                expected: 2,
+                found: 3
+            }
+        ),
+        "Expected 2 bytes, found 3"
+    );
+}
+
+#[test]
+fn converter() {
+    use std::vec::Vec;
+
+    use crate::slice::{self, SliceError};
+    use crate::Converter;
+
+    // feeding a stream in chunks of any size gives the same frames as casting
+    // it at once
+    fn agrees(xs: Vec<i32>, sizes: Vec<u8>) -> bool {
+        let mut stereo = Converter::<i32, u8, 2>::new();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 258];
+        let mut rest = &xs[..];
+        let mut first_error = None;
+        for size in sizes
+            .iter()
+            .map(|&n| usize::from(n))
+            .chain(Some(usize::MAX))
+        {
+            let (chunk, tail) = rest.split_at(size.min(rest.len()));
+            rest = tail;
+            match stereo.convert(chunk, &mut buf) {
+                Ok(ys) => out.extend_from_slice(ys),
+                Err(e) => {
+                    first_error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        let even = xs.len() / 2 * 2;
+        let mut expected = std::vec![0u8; even];
+        match slice::auto(&xs[..even], &mut expected) {
+            Ok(_) => first_error.is_none() && out == expected && stereo.pending() == &xs[even..],
+            Err(e) => first_error == Some(e),
+        }
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<i32>, Vec<u8>) -> bool);
+
+    // an error leaves the converter as it was
+    let mut mono = Converter::<f64, i8>::default();
+    let mut buf = [0; 4];
+    assert_eq!(mono.convert(&[1., 2.], &mut buf), Ok(&mut [1, 2][..]));
+    assert!(mono.convert(&[3., f64::NAN], &mut buf).is_err());
+    assert_eq!(mono.position(), 2);
+    assert_eq!(
+        mono.convert(&[0.; 5], &mut buf),
+        Err(SliceError::BufferTooSmall)
+    );
+    mono.reset();
+    assert_eq!(mono.position(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io() {
+    use std::io::{ErrorKind, Read};
+    use std::vec::Vec;
+
+    use crate::bytes::ByteOrder;
+    use crate::io::{Reader, Writer};
+    use crate::{ElementError, Error};
+
+    /// Returns at most `n` bytes per read
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.1.min(buf.len()).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn roundtrip(xs: Vec<i32>, n: u8, big: bool) -> bool {
+        let order = if big {
+            ByteOrder::BigEndian
+        } else {
+            ByteOrder::LittleEndian
+        };
+        let mut writer = Writer::<_, i32, i64>::new(Vec::new(), order);
+        writer.write_samples(&xs).unwrap();
+        let bytes = writer.into_inner();
+
+        let mut reader = Reader::<_, i64, i32>::new(Trickle(&bytes, usize::from(n) + 1), order);
+        let mut ys = Vec::new();
+        let mut buf = [0; 3];
+        loop {
+            match reader.read_samples(&mut buf).unwrap() {
+                0 => break,
+                k => ys.extend_from_slice(&buf[..k]),
+            }
+        }
+
+        ys == xs && reader.position() == xs.len()
+    }
+    quickcheck::quickcheck(roundtrip as fn(Vec<i32>, u8, bool) -> bool);
+
+    let bytes = [1, 0, 0, 1, 0, 2, 0, 3, 0];
+    let mut reader = Reader::<_, u16, u8>::new(&bytes[..], ByteOrder::LittleEndian);
+    let mut buf = [0; 8];
+    let e = reader.read_samples(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+    let e = e.into_inner().unwrap();
+    let e = e.downcast_ref::<ElementError<u16>>().unwrap();
+    assert_eq!((e.index(), e.value()), (1, 256));
+    assert_eq!(
+        reader.read_samples(&mut buf).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    let mut writer = Writer::<_, i16, u8>::new(Vec::new(), ByteOrder::NATIVE);
+    writer.write_samples(&[1, 2]).unwrap();
+    let e = writer.write_samples(&[3, -4]).unwrap_err();
+    let e = e.into_inner().unwrap();
+    assert_eq!(
+        e.downcast_ref::<ElementError<i16>>().map(|e| e.index()),
+        Some(3)
+    );
+    assert_eq!(writer.into_inner(), [1, 2]);
+
+    // normalized PCM
+    fn normalized(xs: Vec<i16>, ys: Vec<u8>) -> bool {
+        let order = ByteOrder::LittleEndian;
+
+        let pcm: Vec<u8> = xs.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let mut floats = std::vec![0f32; xs.len()];
+        let mut reader = Reader::<_, i16, f32>::new(&pcm[..], order);
+        let n = reader.read_normalized(&mut floats).unwrap();
+        let mut writer = Writer::<_, f32, i16>::new(Vec::new(), order);
+        writer.write_normalized(&floats[..n]).unwrap();
+
+        let mut doubles = std::vec![0f64; ys.len()];
+        let mut reader = Reader::<_, u8, f64>::new(&ys[..], order);
+        let m = reader.read_normalized(&mut doubles).unwrap();
+        let mut bytes = Writer::<_, f64, u8>::new(Vec::new(), order);
+        bytes.write_normalized(&doubles[..m]).unwrap();
+
+        floats[..n].iter().all(|x| (-1. ..1.).contains(x))
+            && writer.into_inner() == pcm
+            && bytes.into_inner() == ys
+    }
+    quickcheck::quickcheck(normalized as fn(Vec<i16>, Vec<u8>) -> bool);
+
+    let mut reader = Reader::<_, u8, f32>::new(&[0u8, 128, 255][..], ByteOrder::NATIVE);
+    let mut buf = [0.; 3];
+    assert_eq!(reader.read_normalized(&mut buf).unwrap(), 3);
+    assert_eq!(buf, [-1., 0., 127. / 128.]);
+
+    let mut writer = Writer::<_, f32, i16>::new(Vec::new(), ByteOrder::NATIVE);
+    for (x, error) in [
+        (1., Error::Overflow),
+        (-1.5, Error::NegOverflow),
+        (f32::NAN, Error::NaN),
+    ] {
+        let e = writer
+            .write_normalized(&[0.5, x])
+            .unwrap_err()
+            .into_inner()
+            .unwrap();
+        let e = e.downcast_ref::<ElementError<f32>>().unwrap();
+        assert_eq!((e.index(), e.error()), (1, error));
+    }
+    assert_eq!(writer.position(), 0);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn ndarray() {
+    use std::vec::Vec;
+
+    use ndarray::{Array, Array3, Axis, IxDyn};
+
+    use crate::{ndarray, ndarray_saturating, saturating, vec, ElementError};
+
+    fn agrees(xs: Vec<f64>) -> bool {
+        let n = xs.len();
+        let a = Array::from_shape_vec((1, n), xs.clone()).unwrap();
+        let b = ndarray::<i8, _, _>(&a);
+
+        let checked = match vec::<i8, _>(xs.clone()) {
+            Ok(ys) => b.map(|b| b.into_raw_vec_and_offset().0) == Ok(ys),
+            Err(e) => b.map_err(|e| (e.index(), e.error())) == Err(((0, e.index()), e.error())),
+        };
+
+        checked
+            && ndarray_saturating::<i8, _, _>(&a)
+                .into_iter()
+                .eq(xs.iter().map(|&x| saturating::<i8, _>(x)))
+    }
+    quickcheck::quickcheck(agrees as fn(Vec<f64>) -> bool);
+
+    // coordinates are logical, also for views that aren't in the standard
+    // layout
+    let mut a = Array3::<i32>::zeros((2, 3, 4));
+    a[(1, 0, 2)] = -1;
+    a[(0, 2, 3)] = -2;
+    assert_eq!(ndarray::<u8, _, _>(&a).unwrap_err().index(), (0, 2, 3));
+    let mut t = a.view();
+    t.swap_axes(0, 2);
+    assert_eq!(
+        ndarray::<u8, _, _>(&t).map_err(|e| (e.index(), e.value())),
+        Err(((2, 0, 1), -1))
+    );
+    assert_eq!(
+        ndarray::<i64, _, _>(&t.index_axis(Axis(1), 1))
+            .unwrap()
+            .shape(),
+        [4, 2]
+    );
+
+    let d = a.into_dyn();
+    let e: ElementError<i32, IxDyn> = ndarray::<u16, _, _>(&d).unwrap_err();
+    assert_eq!(e.index(), IxDyn(&[0, 2, 3]));
+    assert_eq!(
+        format!("{}", ElementError::new((1, 2), 300, crate::Error::Overflow)),
+        "Element (1, 2) (300): Overflow during numeric conversion"
+    );
+}
+
+#[test]
+fn range() {
+    use core::ops::{Range, RangeInclusive};
+
+    use crate::{auto, Error, Fallibility, From};
+
+    fn agrees(start: i64, end: i64) -> bool {
+        let expected = crate::i16(start).and_then(|s| Ok(s..crate::i16(end)?));
+
+        auto::<Range<i16>, _>(start..end) == expected
+            && auto::<RangeInclusive<i16>, _>(start..=end) == expected.map(|r| r.start..=r.end)
+    }
+    quickcheck::quickcheck(agrees as fn(i64, i64) -> bool);
+
+    // infallible casts stay infallible
+    let r: Range<u64> = <Range<u64>>::cast(1u8..2);
+    assert_eq!(r, 1..2);
+    const {
+        assert!(matches!(
+            <RangeInclusive<i8> as From<RangeInclusive<u8>>>::FALLIBILITY,
+            Fallibility::Overflow
+        ))
+    };
+    assert_eq!(auto::<Range<u32>, _>(-1i64..0), Err(Error::NegOverflow));
+
+    // the cast doesn't keep the exhaustion of a `RangeInclusive`
+    let mut exhausted = 0..=0u8;
+    assert_eq!(exhausted.next(), Some(0));
+    assert!(exhausted.is_empty());
+    let r = <RangeInclusive<u16>>::cast(exhausted);
+    assert_eq!((r.is_empty(), r.count()), (false, 1));
+}
+
+#[test]
+fn interleaved() {
+    use std::vec::Vec;
+
+    use crate::slice::{self, SliceError};
+
+    fn roundtrip(xs: Vec<i32>) -> bool {
+        let frames = xs.len() / 3;
+        let xs = &xs[..frames * 3];
+        let first_error = xs.iter().position(|&x| crate::i16(x).is_err());
+
+        let mut planes = [
+            std::vec![0i16; frames],
+            std::vec![0i16; frames],
+            std::vec![0i16; frames],
+        ];
+        let [a, b, c] = &mut planes;
+        let result = slice::deinterleave(xs, [&mut a[..], &mut b[..], &mut c[..]]);
+        match (result, first_error) {
+            (Err(SliceError::Element(e)), Some(i)) => return e.index() == i,
+            (Ok(_), None) => {}
+            _ => return false,
+        }
+
+        // each plane is one channel ...
+        let mut channel = std::vec![0i16; frames];
+        for (i, plane) in planes.iter().enumerate() {
+            if slice::step_by(xs.get(i..).unwrap_or(&[]), 3, &mut channel).map(|ys| &*ys == plane)
+                != Ok(true)
+            {
+                return false;
+            }
+        }
+
+        // ... and interleaving them gives back the input
+        let mut ys = std::vec![0i32; frames * 3];
+        let [a, b, c] = &planes;
+        slice::interleave([&a[..], &b[..], &c[..]], &mut ys).map(|ys| &*ys == xs) == Ok(true)
+    }
+    quickcheck::quickcheck(roundtrip as fn(Vec<i32>) -> bool);
+
+    // errors point into the interleaved data
+    let mut ys = [0u8; 4];
+    assert_eq!(
+        slice::interleave([&[1, 2][..], &[3, -4]], &mut ys).map_err(|e| match e {
+            SliceError::Element(e) => (e.index(), e.value()),
+            _ => unreachable!(),
+        }),
+        Err((3, -4))
+    );
+    assert_eq!(
+        slice::step_by(&[1u16, 2, 3, 4, 5], 2, &mut [0u8; 2]),
+        Err(SliceError::BufferTooSmall)
+    );
+    assert_eq!(
+        slice::step_by(&[1u16, 2, 3, 4, 5], 2, &mut [0u8; 3]),
+        Ok(&mut [1, 3, 5][..])
+    );
+    let mut short = [0u8; 1];
+    assert_eq!(
+        slice::deinterleave(&[1u16, 2, 3, 4], [&mut [0u8; 2][..], &mut short]).err(),
+        Some(SliceError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn dither() {
+    use std::vec::Vec;
+
+    use crate::dither;
+    use crate::slice::SliceError;
+
+    // a horizontal gradient that straight rounding turns into bands
+    let width = 64;
+    let src = (0..width * 16)
+        .map(|i| 100. + (i % width) as f32 / width as f32)
+        .collect::<Vec<_>>();
+    let mean = |ys: &[u8]| ys.iter().map(|&y| f64::from(y)).sum::<f64>() / ys.len() as f64;
+    let expected = src.iter().map(|&x| f64::from(x)).sum::<f64>() / src.len() as f64;
+
+    let mut ys = std::vec![0u8; src.len()];
+    let ys = dither::serpentine(&src, width, &mut ys).unwrap();
+    assert!(ys.iter().all(|&y| y == 100 || y == 101));
+    assert!((mean(ys) - expected).abs() < 0.01);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut zs = std::vec![0u8; src.len()];
+        let zs = dither::floyd_steinberg(&src, width, &mut zs).unwrap();
+        assert!(zs.iter().all(|&z| z == 100 || z == 101));
+        assert!((mean(zs) - expected).abs() < 0.01);
+        // the dark and the bright halves of the gradient keep their intensity
+        for half in 0..2 {
+            let (xs, zs): (Vec<_>, Vec<_>) = src
+                .chunks(width / 2)
+                .zip(zs.chunks(width / 2))
+                .skip(half)
+                .step_by(2)
+                .flat_map(|(xs, zs)| xs.iter().zip(zs))
+                .map(|(&x, &z)| (f64::from(x), z))
+                .unzip();
+            let expected = xs.iter().sum::<f64>() / xs.len() as f64;
+            assert!((mean(&zs) - expected).abs() < 0.05);
+        }
+    }
+
+    // integers and the extremes go through unchanged
+    let src = [0., 1., 128., 255., 255., 0.];
+    let mut ys = [0u8; 6];
+    assert_eq!(
+        dither::serpentine(&src, 4, &mut ys),
+        Ok(&mut [0, 1, 128, 255, 255, 0][..])
+    );
+
+    // invalid inputs are reported with their index
+    let mut ys = [0u8; 4];
+    assert_eq!(
+        dither::serpentine(&[1., 2., f32::NAN, 3.], 2, &mut ys).map_err(|e| match e {
+            SliceError::Element(e) => e.index(),
+            _ => unreachable!(),
+        }),
+        Err(2)
+    );
+    assert_eq!(
+        dither::serpentine(&[1., 2., 3.], 2, &mut [0u8; 2]),
+        Err(SliceError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn tpdf() {
+    use std::vec::Vec;
+
+    use crate::dither::Tpdf;
+    use crate::slice::SliceError;
+
+    let mut dither = Tpdf::new();
+    let mut ys = std::vec![0i16; 1 << 16];
+
+    // silence turns into noise of at most one step
+    let zs = dither.i16(&std::vec![0.; 1 << 16], &mut ys).unwrap();
+    assert!(zs.iter().all(|&z| (-1..=1).contains(&z)));
+    let ones = zs.iter().filter(|&&z| z != 0).count() as f64 / zs.len() as f64;
+    // the probability of a triangular noise in `-1..1` being rounded away
+    // from zero is 1/4
+    assert!((ones - 0.25).abs() < 0.01);
+
+    // the average of the output follows the input
+    let src = (0..1 << 16)
+        .map(|i| (i % 1000) as f32 / 100. - 5.)
+        .collect::<Vec<_>>();
+    let zs = dither.i16(&src, &mut ys).unwrap();
+    let error = src
+        .iter()
+        .zip(zs.iter())
+        .map(|(&x, &z)| f64::from(z) - f64::from(x))
+        .sum::<f64>()
+        / src.len() as f64;
+    assert!(error.abs() < 0.01);
+
+    // the extremes saturate
+    let zs = dither.i16(&[32767., -32768.], &mut ys).unwrap();
+    assert!(zs[0] >= 32766 && zs[1] <= -32767);
+
+    // the same seed gives the same noise, and the state carries over
+    let src = [0.5; 64];
+    let (mut a, mut b) = ([0i16; 64], [0i16; 64]);
+    let mut dither = Tpdf::with_seed(7);
+    dither.i16(&src[..10], &mut a).unwrap();
+    dither.i16(&src[10..], &mut a[10..]).unwrap();
+    Tpdf::with_seed(7).i16(&src, &mut b).unwrap();
+    assert_eq!(a, b);
+    assert_ne!(Tpdf::with_seed(0), Tpdf::with_seed(7));
+    assert_eq!(Tpdf::with_seed(0), Tpdf::default());
+
+    assert_eq!(
+        dither.i16(&[0., f32::NAN], &mut ys).map_err(|e| match e {
+            SliceError::Element(e) => e.index(),
+            _ => unreachable!(),
+        }),
+        Err(1)
+    );
+    assert_eq!(
+        dither.i16(&[0.; 2], &mut [0; 1]),
+        Err(SliceError::BufferTooSmall)
+    );
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow() {
+    use arrow_array::types::{Float32Type, Int16Type, Int64Type, UInt16Type};
+    use arrow_array::{Array, Float32Array, Int64Array};
+
+    // the values behind nulls are not casted
+    let column = Int64Array::new(
+        std::vec![1, i64::MAX, -3].into(),
+        Some(std::vec![true, false, true].into()),
+    );
+    let ys = crate::arrow::<Int16Type, Int64Type>(&column).unwrap();
+    assert_eq!(ys.len(), 3);
+    assert_eq!((ys.value(0), ys.value(2)), (1, -3));
+    assert_eq!(ys.null_count(), 1);
+    assert!(ys.is_null(1));
+
+    let e = crate::arrow::<UInt16Type, Int64Type>(&column).unwrap_err();
+    assert_eq!((e.index(), e.value()), (2, -3));
+
+    let column = Float32Array::from(std::vec![Some(0.5), Some(f32::NAN)]);
+    let e = crate::arrow::<Int16Type, Float32Type>(&column).unwrap_err();
+    assert_eq!(e.index(), 1);
+
+    // arrays without nulls, and slices of arrays
+    let column = Int64Array::from(std::vec![1, 2, 3, 70_000]);
+    let ys = crate::arrow::<Float32Type, Int64Type>(&column.slice(1, 3)).unwrap();
+    assert_eq!(ys.values()[..], [2., 3., 70_000.]);
+    assert_eq!(ys.nulls(), None);
+}
+
+#[cfg(feature = "num-traits")]
+#[test]
+fn num_traits() {
+    use num_traits::{FromPrimitive, NumCast, ToPrimitive};
+
+    use crate::num::{self, Num};
+    use crate::Error;
+
+    // `ToPrimitive` agrees with the crate's casts
+    fn agrees(x: i64, y: f64) -> bool {
+        Num(x).to_u8() == crate::u8(x).ok()
+            && Num(x).to_i32() == crate::i32(x).ok()
+            && Num(x).to_f32() == Some(crate::f32(x))
+            && Num(y).to_i16() == crate::i16(y).ok()
+            && Num(y).to_u64() == crate::u64(y).ok()
+            && <Num<u16>>::from_i64(x) == crate::u16(x).ok().map(Num)
+            && <Num<i8> as NumCast>::from(y) == crate::i8(y).ok().map(Num)
+            && num::cast::<u32, _>(&x) == crate::u32(x)
+            && num::cast::<i64, _>(&y) == crate::i64(y)
+    }
+    quickcheck::quickcheck(agrees as fn(i64, f64) -> bool);
+
+    // the edge cases where `num-traits` and the crate disagree
+    assert_eq!(255.5f64.to_u8(), Some(255));
+    assert_eq!(Num(255.5f64).to_u8(), None);
+    assert_eq!(f64::MAX.to_f32(), Some(f32::INFINITY));
+    assert_eq!(Num(f64::MAX).to_f32(), None);
+
+    assert_eq!(num::cast::<u8, _>(&u128::MAX), Err(Error::Overflow));
+    assert_eq!(num::cast::<i8, _>(&-1e300), Err(Error::NegOverflow));
+    assert_eq!(
+        num::cast::<u64, _>(&f32::NEG_INFINITY),
+        Err(Error::Infinite)
+    );
+    assert_eq!(num::cast::<f32, _>(&1e300), Err(Error::Overflow));
+    assert_eq!(num::cast::<f64, _>(&u128::MAX), Ok(u128::MAX as f64));
+
+    // `Num` casts like the primitive it wraps
+    assert_eq!(crate::u8(Num(2.5f32)), Ok(2));
+    assert_eq!(
+        <Num<i8> as crate::From<_>>::cast(300u16),
+        Err(Error::Overflow)
+    );
+}
+
+#[cfg(feature = "az")]
+#[test]
+fn az() {
+    use az::{CheckedAs, SaturatingAs, UnwrappedAs};
+
+    use crate::{Error, Finite, InRange, NonNegative};
+
+    // the newtypes cast like the primitives they wrap
+    let x = Finite::new(-2.5f64).unwrap();
+    assert_eq!(x.checked_as::<u8>(), None);
+    assert_eq!(x.saturating_as::<i8>(), -2);
+    assert_eq!(az::cast::<_, f32>(x), -2.5);
+    let n = NonNegative::new(300i32).unwrap();
+    assert_eq!(n.saturating_as::<u8>(), 255);
+    let r = InRange::<u32, 0, 100>::new(42).unwrap();
+    assert_eq!(r.unwrapped_as::<i8>(), 42);
+
+    // `checked` agrees with `az` on success, and explains its failures
+    fn agrees(x: i64, y: f32) -> bool {
+        crate::az::checked::<u16, _>(x).ok() == x.checked_as::<u16>()
+            && crate::az::checked::<i8, _>(y).ok() == y.checked_as::<i8>()
+    }
+    quickcheck::quickcheck(agrees as fn(i64, f32) -> bool);
+
+    assert_eq!(crate::az::checked::<u8, _>(256u16), Err(Error::Overflow));
+    assert_eq!(crate::az::checked::<u64, _>(-0.5f32), Ok(0));
+    assert_eq!(
+        crate::az::checked::<u64, _>(-1.5f32),
+        Err(Error::NegOverflow)
+    );
+    assert_eq!(crate::az::checked::<i32, _>(f64::NAN), Err(Error::NaN));
+    assert_eq!(
+        crate::az::checked::<u128, _>(f32::NEG_INFINITY),
+        Err(Error::Infinite)
+    );
+    assert_eq!(
+        crate::az::checked::<f32, _>(u128::MAX),
+        Ok(u128::MAX as f32)
+    );
+}
+
+#[cfg(feature = "num_enum")]
+#[test]
+fn num_enum() {
+    use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+    use crate::{EnumError, Error, Fallback, From as _0, NumEnum};
+
+    #[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+    #[repr(i16)]
+    enum Level {
+        Low = -300,
+        Mid = 0,
+        High = 300,
+    }
+
+    assert_eq!(NumEnum::cast(-300i64), Ok(NumEnum(Level::Low)));
+    assert_eq!(NumEnum::cast(300.5f32), Ok(NumEnum(Level::High)));
+    assert_eq!(
+        NumEnum::cast(0u128).map(NumEnum::into_inner),
+        Ok(Level::Mid)
+    );
+    assert_eq!(
+        NumEnum::<Level>::cast(1u8),
+        Err(EnumError::UnknownDiscriminant(1))
+    );
+    assert_eq!(
+        NumEnum::<Level>::cast(-40_000i32),
+        Err(EnumError::Cast(Error::NegOverflow))
+    );
+    assert_eq!(
+        NumEnum::<Level>::cast(f64::NAN),
+        Err(EnumError::Cast(Error::NaN))
+    );
+
+    assert_eq!(crate::i64(NumEnum(Level::Low)), -300);
+    assert_eq!(crate::u16(NumEnum(Level::High)), Ok(300));
+    assert_eq!(
+        crate::u16(NumEnum(Level::Low)).map_err(Error::from),
+        Err(Error::NegOverflow)
+    );
+    assert_eq!(crate::f32(NumEnum(Level::Low)), -300.);
+
+    // `Fallback` goes through `TryFrom<i16>`
+    assert_eq!(Fallback::<Level>::cast(300i16), Ok(Fallback(Level::High)));
+    assert_eq!(Fallback::<Level>::cast(301i16), Err(Error::Overflow));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json() {
+    use serde_json::Number;
+
+    use crate::json::{self, NumberError};
+    use crate::Error;
+
+    // integers are exact exactly when the cast succeeds
+    fn ints(x: i64) -> bool {
+        let n = Number::from(x);
+        json::from_number::<i16>(&n) == crate::i16(x).map_err(NumberError::from)
+            && json::from_number::<u32>(&n) == crate::u32(x).map_err(NumberError::from)
+            && json::to_number(x) == Ok(n)
+    }
+    quickcheck::quickcheck(ints as fn(i64) -> bool);
+
+    // floats must survive the round trip
+    fn exact(x: f64, cast: Result<f64, Error>) -> Result<f64, NumberError> {
+        match cast {
+            Ok(y) if y == x => Ok(y),
+            Ok(_) => Err(NumberError::Imprecise),
+            Err(e) => Err(NumberError::Cast(e)),
+        }
+    }
+    fn floats(x: f64) -> bool {
+        let n = match Number::from_f64(x) {
+            Some(n) => n,
+            None => return json::to_number(x).is_err(),
+        };
+        json::from_number::<f32>(&n).map(f64::from) == exact(x, crate::f32(x).map(f64::from))
+            && json::from_number::<i32>(&n).map(f64::from) == exact(x, crate::i32(x).map(f64::from))
+    }
+    quickcheck::quickcheck(floats as fn(f64) -> bool);
+
+    let n = Number::from(u64::MAX);
+    assert_eq!(json::from_number::<u64>(&n), Ok(u64::MAX));
+    assert_eq!(
+        json::from_number::<i64>(&n),
+        Err(NumberError::Cast(Error::Overflow))
+    );
+    assert_eq!(json::from_number::<f64>(&n), Err(NumberError::Imprecise));
+    assert_eq!(json::from_number::<u128>(&n), Ok(u128::from(u64::MAX)));
+    let n = Number::from((1i64 << 53) + 2);
+    assert_eq!(json::from_number::<f64>(&n), Ok(9007199254740994.));
+
+    let n = Number::from_f64(-0.).unwrap();
+    assert_eq!(json::from_number::<u8>(&n), Ok(0));
+    let n = Number::from_f64(1e300).unwrap();
+    assert_eq!(
+        json::from_number::<f32>(&n),
+        Err(NumberError::Cast(Error::Overflow))
+    );
+    assert_eq!(json::from_number::<f64>(&n), Ok(1e300));
+
+    assert_eq!(json::to_number(f32::NEG_INFINITY), Err(Error::Infinite));
+    assert_eq!(json::to_number(-2i8), Ok(Number::from(-2)));
+
+    assert_eq!(
+        format!("{}", NumberError::Imprecise),
+        "Loss of precision during numeric conversion"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_narrowing() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Row {
+        #[serde(deserialize_with = "crate::serde::checked_u16")]
+        a: u16,
+        #[serde(deserialize_with = "crate::serde::checked_i8")]
+        b: i8,
+        #[serde(deserialize_with = "crate::serde::checked_f32")]
+        c: f32,
+    }
+
+    let row = |text: &str| serde_json::from_str::<Row>(text).map_err(|e| e.to_string());
+
+    assert_eq!(
+        row(r#"{ "a": 65535, "b": -128, "c": 1e10 }"#),
+        Ok(Row {
+            a: 65535,
+            b: -128,
+            c: 1e10
+        })
+    );
+    assert_eq!(
+        row(r#"{ "a": 2.9, "b": 1e3, "c": 0 }"#),
+        Err(std::string::String::from(
+            "1000 doesn't fit in `i8`: Overflow during numeric conversion at line 1 column 20"
+        ))
+    );
+    assert_eq!(
+        row(r#"{ "a": -1, "b": 0, "c": 0 }"#),
+        Err(std::string::String::from(
+            "-1 doesn't fit in `u16`: Negative overflow during numeric conversion at line 1 column 9"
+        ))
+    );
+    assert_eq!(
+        row(r#"{ "a": "1", "b": 0, "c": 0 }"#),
+        Err(std::string::String::from(
+            "invalid type: string \"1\", expected a number that fits in `u16` at line 1 column 10"
+        ))
+    );
+    assert!(row(r#"{ "a": 0, "b": 0, "c": 1e300 }"#).is_err());
+}
+
+#[test]
+fn js() {
+    use crate::js::{self, MAX_SAFE_INTEGER, MIN_SAFE_INTEGER};
+    use crate::Error;
+
+    // the safe integers are those that round trip through an `f64` together
+    // with their neighbors
+    fn safe(x: i64) -> bool {
+        let exact = |x: i64| x as f64 as i64 == x;
+        let expected = (x.saturating_sub(1)..=x.saturating_add(1)).all(exact);
+        js::f64(x).is_ok() == expected && js::f64(x).and_then(js::i64) == js::f64(x).map(|_| x)
+    }
+    quickcheck::quickcheck(safe as fn(i64) -> bool);
+    for x in [
+        MIN_SAFE_INTEGER - 1,
+        MIN_SAFE_INTEGER,
+        MAX_SAFE_INTEGER,
+        MAX_SAFE_INTEGER + 1,
+    ] {
+        assert!(safe(x));
+    }
+
+    assert_eq!(js::f64(MAX_SAFE_INTEGER), Ok(9007199254740991.));
+    assert_eq!(js::f64(MIN_SAFE_INTEGER - 1), Err(Error::NegOverflow));
+    assert_eq!(js::f64(i64::MIN), Err(Error::NegOverflow));
+
+    assert_eq!(js::i64(9007199254740991.), Ok(MAX_SAFE_INTEGER));
+    assert_eq!(js::i64(-4503599627370495.5), Ok(-4503599627370495));
+    assert_eq!(js::i64(-9007199254740992.), Err(Error::NegOverflow));
+    assert_eq!(js::i64(-0.5), Ok(0));
+    assert_eq!(js::i64(f64::NEG_INFINITY), Err(Error::Infinite));
+    assert_eq!(js::i64(1e300), Err(Error::Overflow));
+}
+
+// `BigInt`s can only be created on WASM targets, so only the impls are checked
+#[cfg(feature = "wasm-bindgen")]
+#[test]
+fn bigint() {
+    use js_sys::BigInt;
+
+    use crate::{classify, Fallibility, From};
+
+    const { assert!(!<BigInt as From<i64>>::FALLIBLE) };
+    const { assert!(!<BigInt as From<u128>>::FALLIBLE) };
+    const { assert!(<u64 as From<BigInt>>::FALLIBLE) };
+    const { assert!(<i128 as From<BigInt>>::FALLIBLE) };
+    assert_eq!(classify::<BigInt, i64>(), Fallibility::Fallible);
+}
+
+#[cfg(feature = "pyo3")]
+#[test]
+fn py() {
+    use pyo3::exceptions::{PyOverflowError, PyTypeError, PyValueError};
+    use pyo3::prelude::*;
+    use pyo3::PyTypeInfo;
+
+    use crate::{py, Error};
+
+    fn raises<T: PyTypeInfo>(py: Python<'_>, e: PyErr) -> bool {
+        e.is_instance_of::<T>(py)
+    }
+
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let eval = |code: &str| {
+            let code = std::ffi::CString::new(code).unwrap();
+            py.eval(&code, None, None).unwrap()
+        };
+
+        assert_eq!(py::extract::<u8>(&eval("255")).unwrap(), 255);
+        assert_eq!(py::extract::<i8>(&eval("True")).unwrap(), 1);
+        assert_eq!(py::extract::<i16>(&eval("-2.5")).unwrap(), -2);
+        assert_eq!(
+            py::extract::<u128>(&eval("2 ** 128 - 1")).unwrap(),
+            u128::MAX
+        );
+        assert_eq!(py::extract::<i128>(&eval("-2 ** 127")).unwrap(), i128::MIN);
+        assert_eq!(
+            py::extract::<f64>(&eval("2 ** 1000")).unwrap(),
+            2f64.powi(1000)
+        );
+        assert!(py::extract::<f32>(&eval("-2 ** 129")).is_err());
+
+        for (code, error) in [
+            ("256", Error::Overflow),
+            ("-1", Error::NegOverflow),
+            ("2 ** 128", Error::Overflow),
+            ("-2 ** 2000", Error::NegOverflow),
+            ("10 ** 400", Error::Overflow),
+            ("float('nan')", Error::NaN),
+            ("float('-inf')", Error::Infinite),
+        ] {
+            let e = py::extract::<u8>(&eval(code)).unwrap_err();
+            assert_eq!(e.value(py).to_string(), error.to_string());
+            match error {
+                Error::Overflow | Error::NegOverflow => assert!(raises::<PyOverflowError>(py, e)),
+                Error::NaN | Error::Infinite => assert!(raises::<PyValueError>(py, e)),
+            }
+        }
+
+        let e = py::extract::<u8>(&eval("'1'")).unwrap_err();
+        assert!(raises::<PyTypeError>(py, e));
+        assert!(raises::<PyOverflowError>(py, PyErr::from(Error::Overflow)));
+    });
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image() {
+    use ::image::{ImageBuffer, Luma, LumaA, Rgba};
+
+    use crate::image::{buffer, channel, pixel};
+    use crate::Error;
+
+    // every level survives a round trip through a wider type
+    for x in 0..=u8::MAX {
+        assert_eq!(channel::<u8, _>(channel::<u16, _>(x).unwrap()), Ok(x));
+        assert_eq!(channel::<u8, _>(channel::<f32, _>(x).unwrap()), Ok(x));
+        assert_eq!(channel::<u16, _>(x), Ok(u16::from(x) * 257));
+    }
+    assert_eq!(channel::<u8, _>(32896u16), Ok(128));
+    assert_eq!(channel::<u8, _>(33024u16), Ok(128));
+    assert_eq!(channel::<u8, _>(33025u16), Ok(129));
+    assert_eq!(channel::<f32, _>(u16::MAX), Ok(1.));
+    assert_eq!(channel::<f32, _>(0.25f32), Ok(0.25));
+
+    assert_eq!(channel::<u16, _>(f32::NAN), Err(Error::NaN));
+    assert_eq!(channel::<u16, _>(f32::INFINITY), Err(Error::Infinite));
+    assert_eq!(channel::<f32, _>(1.0001f32), Err(Error::Overflow));
+    assert_eq!(channel::<u8, _>(-0f32), Ok(0));
+    assert_eq!(channel::<u8, _>(-1e-9f32), Err(Error::NegOverflow));
+
+    assert_eq!(pixel::<f32, _>(Luma([255u8])), Ok(Luma([1.])));
+    assert_eq!(pixel::<u8, _>(LumaA([0u16, 65535])), Ok(LumaA([0, 255])));
+    assert_eq!(
+        pixel::<u8, _>(Rgba([0f32, 2., 0., f32::NAN])),
+        Err(Error::Overflow)
+    );
+
+    let gray = ImageBuffer::from_fn(3, 2, |x, y| Luma([(x + 3 * y) as u16 * 13107]));
+    let bytes = buffer::<u8, _, _>(&gray).unwrap();
+    assert_eq!(bytes.dimensions(), (3, 2));
+    assert_eq!(bytes.into_raw(), [0, 51, 102, 153, 204, 255]);
+
+    let mut hdr = ImageBuffer::<Rgba<f32>, _>::new(4, 3);
+    assert!(buffer::<u16, _, _>(&hdr).is_ok());
+    hdr.put_pixel(3, 1, Rgba([0., 0., 1.5, 2.]));
+    let e = buffer::<u16, _, _>(&hdr).unwrap_err();
+    assert_eq!((e.index(), e.value()), ((3, 1, 2), 1.5));
+    assert_eq!(e.error(), Error::Overflow);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn reinterpret_pod() {
+    use bytemuck::{Pod, Zeroable};
+
+    use crate::reinterpret::{self, ReinterpretError};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    #[repr(transparent)]
+    struct Sample(i16);
+
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    #[repr(C)]
+    struct Stereo {
+        left: Sample,
+        right: Sample,
+    }
+
+    let mut frames = [
+        Stereo {
+            left: Sample(1),
+            right: Sample(-1),
+        },
+        Stereo {
+            left: Sample(i16::MIN),
+            right: Sample(0),
+        },
+    ];
+    assert_eq!(reinterpret::slice::<i16, _>(&frames), [1, -1, i16::MIN, 0]);
+    assert_eq!(reinterpret::slice::<Sample, _>(&frames)[1], Sample(-1));
+
+    reinterpret::slice_mut::<Sample, _>(&mut frames)[3] = Sample(7);
+    assert_eq!(frames[1].right, Sample(7));
+
+    let samples = reinterpret::slice::<i16, _>(&frames);
+    assert_eq!(
+        reinterpret::try_slice::<Stereo, _>(&samples[2..]),
+        Ok(&frames[1..])
+    );
+    assert_eq!(
+        reinterpret::try_slice::<Stereo, _>(&samples[1..]),
+        Err(ReinterpretError::Size)
+    );
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn reinterpret_zerocopy() {
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    use crate::reinterpret::{zerocopy as reinterpret, ReinterpretError};
+
+    #[derive(Clone, Copy, Debug, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+    #[repr(transparent)]
+    struct Meters(u32);
+
+    let mut distances = [Meters(1), Meters(u32::MAX)];
+    assert_eq!(reinterpret::slice::<i32, _>(&distances), [1, -1]);
+    reinterpret::slice_mut::<u16, _>(&mut distances)[2..].fill(0);
+    assert_eq!(distances, [Meters(1), Meters(0)]);
+
+    let words = [0u32, 2, 3];
+    let bytes = reinterpret::slice::<u8, _>(&words);
+    assert_eq!(
+        reinterpret::try_slice::<Meters, _>(&bytes[4..]),
+        Ok(&[Meters(2), Meters(3)][..])
+    );
+    assert_eq!(
+        reinterpret::try_slice::<Meters, _>(&bytes[1..5]),
+        Err(ReinterpretError::Misaligned)
+    );
+    let mut words = words;
+    assert_eq!(
+        reinterpret::try_slice_mut::<Meters, _>(reinterpret::slice_mut::<u8, _>(&mut words)),
+        Ok(&mut [Meters(0), Meters(2), Meters(3)][..])
+    );
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn buf() {
+    use bytes::{Buf, BytesMut};
+
+    use crate::buf::{BufExt, BufMutExt};
+    use crate::Error;
+
+    let mut frame = BytesMut::new();
+    frame.put_len_u8(255).unwrap();
+    frame.put_len_u16_le(0x1234).unwrap();
+    frame.put_len_u32(70_000).unwrap();
+    frame.put_len_u64_le(usize::MAX).unwrap();
+    assert_eq!(frame.put_len_u8(256), Err(Error::Overflow));
+    assert_eq!(frame.put_len_u16(65_536), Err(Error::Overflow));
+    assert_eq!(frame.len(), 1 + 2 + 4 + 8);
+    assert_eq!(frame[1..3], [0x34, 0x12]);
+
+    let mut frame = frame.freeze();
+    assert_eq!(frame.get_len_u8(), Ok(255));
+    assert_eq!(frame.get_len_u16_le(), Ok(0x1234));
+    assert_eq!(frame.get_len_u32(), Ok(70_000));
+    assert_eq!(frame.get_len_u64_le(), Ok(usize::MAX));
+    assert!(!frame.has_remaining());
+
+    let mut wide = &u64::MAX.to_be_bytes()[..];
+    let expected = if cfg!(target_pointer_width = "64") {
+        Ok(usize::MAX)
+    } else {
+        Err(Error::Overflow)
+    };
+    assert_eq!(wide.get_len_u64(), expected);
+}
+
+#[test]
+fn proto() {
+    use std::string::ToString;
+
+    use crate::proto;
+    use crate::Error;
+
+    assert_eq!(proto::field::<u8, _>("level", 255i32), Ok(255));
+    assert_eq!(
+        proto::field::<Option<u16>, _>("port", None::<u32>),
+        Ok(None)
+    );
+    assert_eq!(
+        proto::field::<Option<u16>, _>("port", Some(65_535u32)),
+        Ok(Some(65_535))
+    );
+
+    let e = proto::field::<u8, _>("level", 256i64).unwrap_err();
+    assert_eq!(
+        (e.field(), e.index(), e.error()),
+        ("level", None, Error::Overflow)
+    );
+    let e = proto::field::<i8, _>("delta", f64::NAN).unwrap_err();
+    assert_eq!(e.to_string(), format!("Field `delta`: {}", Error::NaN));
+
+    // the error is already in the message
+    assert!(std::error::Error::source(&e).is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn proto_repeated() {
+    use crate::proto;
+    use crate::Error;
+
+    assert_eq!(
+        proto::repeated::<u16, _>("ports", std::vec![80u32, 443]),
+        Ok(std::vec![80, 443])
+    );
+    assert_eq!(
+        proto::repeated::<u16, u32>("ports", std::vec![]),
+        Ok(std::vec![])
+    );
+
+    let e = proto::repeated::<u16, _>("ports", std::vec![80i64, -1, 70_000]).unwrap_err();
+    assert_eq!(
+        (e.field(), e.index(), e.error()),
+        ("ports", Some(1), Error::NegOverflow)
+    );
+}
+
+#[test]
+fn cli() {
+    use std::string::ToString;
+
+    use crate::cli::parse;
+    use crate::Error;
+
+    assert_eq!(parse::<i8>("-128"), Ok(-128));
+    assert_eq!(parse::<u16>("+8080"), Ok(8080));
+    assert_eq!(
+        parse::<u128>("340282366920938463463374607431768211455"),
+        Ok(u128::MAX)
+    );
+    assert_eq!(parse::<f64>("1e3"), Ok(1000.));
+
+    for (s, error) in [
+        ("128", Error::Overflow),
+        ("-129", Error::NegOverflow),
+        ("340282366920938463463374607431768211456", Error::Overflow),
+        (
+            "-170141183460469231731687303715884105729",
+            Error::NegOverflow,
+        ),
+    ] {
+        assert_eq!(parse::<i8>(s).unwrap_err().error(), Some(error));
+    }
+    assert_eq!(
+        parse::<i8>("128").unwrap_err().to_string(),
+        "expected ≤ 127"
+    );
+    assert_eq!(parse::<u32>("-1").unwrap_err().to_string(), "expected ≥ 0");
+
+    // not integers
+    for s in ["", "2.5", "1e3", " 1", "0x10"] {
+        assert_eq!(parse::<u32>(s).unwrap_err().error(), None);
+    }
+    assert_eq!(parse::<f32>("x").unwrap_err().error(), None);
+    assert_eq!(
+        parse::<f32>("1e39").unwrap_err().error(),
+        Some(Error::Overflow)
+    );
+}
+
+#[cfg(feature = "bitflags")]
+#[test]
+fn bitflags() {
+    use std::string::ToString;
+
+    use crate::{classify, BitFlags, Error, Fallibility, FlagsError, From};
+
+    bitflags::bitflags! {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        struct Status: u16 {
+            const READY = 1;
+            const ERROR = 1 << 8;
+            const BUSY = 1 << 15;
+        }
+    }
+
+    assert_eq!(
+        BitFlags::<Status>::cast(0x8101u32),
+        Ok(BitFlags(Status::all()))
+    );
+    assert_eq!(BitFlags::<Status>::cast(0i8), Ok(BitFlags(Status::empty())));
+    assert_eq!(
+        BitFlags::<Status>::cast(-1i32),
+        Err(FlagsError::Cast(Error::NegOverflow))
+    );
+
+    let e = BitFlags::<Status>::cast(0x8103u64).unwrap_err();
+    assert_eq!(e, FlagsError::UnknownBits(0x2));
+    assert_eq!(e.to_string(), "Unknown flag bits set: 0x2");
+    assert_eq!(Error::from(e), Error::Overflow);
+    assert_eq!(
+        crate::auto::<BitFlags<Status>, _>(0x10000u32).map(BitFlags::into_inner),
+        Err(Error::Overflow)
+    );
+
+    assert_eq!(crate::u8(BitFlags(Status::READY)), Ok(1));
+    assert!(crate::u8(BitFlags(Status::BUSY)).is_err());
+    assert_eq!(crate::f32(BitFlags(Status::BUSY)), 32768.);
+    assert_eq!(classify::<BitFlags<Status>, u32>(), Fallibility::Infallible);
+}
+
+#[cfg(feature = "modular-bitfield")]
+#[test]
+// for the code generated by `#[bitfield]`
+#[allow(dead_code, unused_parens)]
+fn bitfield() {
+    use modular_bitfield::prelude::*;
+
+    use crate::bitfield::{pack, unpack};
+    use crate::{EnumError, Error};
+
+    #[derive(BitfieldSpecifier, Clone, Copy, Debug, PartialEq)]
+    #[bits = 3]
+    enum Level {
+        Trace,
+        Debug,
+        Info,
+        Warn,
+        Error,
+    }
+
+    #[bitfield]
+    #[derive(BitfieldSpecifier, Clone, Copy, Debug, PartialEq)]
+    struct Entry {
+        level: Level,
+        line: B12,
+        #[skip]
+        __: B1,
+    }
+
+    let entry = unpack::<Entry, _>(0x0fffi32 << 3 | 4).unwrap();
+    assert_eq!((entry.level(), entry.line()), (Level::Error, 0xfff));
+    assert_eq!(pack::<u16, Entry>(entry), Ok(0x7ffc));
+    assert_eq!(pack::<u8, Entry>(entry), Err(Error::Overflow));
+    assert_eq!(
+        unpack::<Entry, _>(-1i32),
+        Err(EnumError::Cast(Error::NegOverflow))
+    );
+    assert_eq!(
+        unpack::<Entry, _>(0x10000u32),
+        Err(EnumError::Cast(Error::Overflow))
+    );
+
+    for (bits, level) in [(0, Level::Trace), (1, Level::Debug), (3, Level::Warn)] {
+        assert_eq!(unpack::<Level, _>(bits as u64), Ok(level));
+        assert_eq!(pack::<i8, Level>(level), Ok(bits));
+    }
+    for bits in 5..8u8 {
+        assert_eq!(
+            unpack::<Level, _>(bits),
+            Err(EnumError::UnknownDiscriminant(bits))
+        );
+    }
+    assert_eq!(
+        unpack::<Level, _>(8u8),
+        Err(EnumError::Cast(Error::Overflow))
+    );
+
+    assert_eq!(unpack::<B12, _>(4095u16), Ok(4095));
+    assert_eq!(
+        unpack::<B12, _>(4096u16),
+        Err(EnumError::Cast(Error::Overflow))
+    );
+    assert_eq!(pack::<u32, B12>(4096), Err(Error::Overflow));
+    assert_eq!(unpack::<bool, _>(1.5f32), Ok(true));
+    assert_eq!(
+        unpack::<bool, _>(2u8),
+        Err(EnumError::Cast(Error::Overflow))
+    );
+}
+
+#[cfg(feature = "uom")]
+#[test]
+fn uom() {
+    use uom::si::length::{kilometer, meter};
+    use uom::si::{f32, f64};
+
+    use crate::uom::value;
+    use crate::{Error, Fallibility, From};
+
+    // the storage of the quantity determines the checks
+    assert_eq!(
+        <f64::Length as From<f32::Length>>::FALLIBILITY,
+        Fallibility::Infallible
+    );
+    assert_eq!(
+        <f32::Length as From<f64::Length>>::FALLIBILITY,
+        <f32 as From<::std::primitive::f64>>::FALLIBILITY
+    );
+
+    let widened: f64::Length = crate::auto(f32::Length::new::<kilometer>(1.5)).unwrap();
+    assert_eq!(widened.get::<meter>(), 1500.);
+
+    let narrowed = <f32::Length as From<f64::Length>>::cast(f64::Length::new::<meter>(0.25));
+    assert_eq!(narrowed.map(|l| l.get::<meter>()), Ok(0.25));
+    assert_eq!(
+        <f32::Length as From<f64::Length>>::cast(f64::Length::new::<meter>(1e300)).map(|_| ()),
+        Err(Error::Overflow)
+    );
+    let nan = <f32::Length as From<f64::Length>>::cast(f64::Length::new::<meter>(
+        ::std::primitive::f64::NAN,
+    ));
+    assert!(nan.unwrap().value.is_nan());
+
+    // in base units, not in the units the quantity was created with
+    let length = f32::Length::new::<kilometer>(0.3);
+    assert_eq!(value::<u16, _, _, _>(&length), Ok(300));
+    assert_eq!(value::<u8, _, _, _>(&length), Err(Error::Overflow));
+    assert_eq!(
+        value::<i8, _, _, _>(&f64::Length::new::<meter>(-129.)),
+        Err(Error::NegOverflow)
+    );
+}
+
+#[test]
+fn bindgen() {
+    use crate::{EnumError, Error, FlagsError, From};
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct errno(i16);
+
+    impl errno {
+        const EPERM: errno = errno(-1);
+        const ENOENT: errno = errno(-2);
+        const EOK: errno = errno(0);
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct open_flags(u32);
+
+    impl open_flags {
+        const O_READ: open_flags = open_flags(1 << 0);
+        const O_WRITE: open_flags = open_flags(1 << 1);
+        const O_APPEND: open_flags = open_flags(1 << 10);
+    }
+
+    // the expansion doesn't pick this up
+    #[allow(dead_code)]
+    type Result<T> = core::result::Result<T, ()>;
+
+    crate::bindgen! {
+        enum errno: i16 { EPERM, ENOENT, EOK, }
+        flags open_flags: u32 { O_READ, O_WRITE, O_APPEND }
+    }
+
+    assert_eq!(errno::cast(-2i64), Ok(errno::ENOENT));
+    assert_eq!(errno::cast(0u8), Ok(errno::EOK));
+    assert_eq!(errno::cast(-1.), Ok(errno::EPERM));
+    assert_eq!(errno::cast(1i32), Err(EnumError::UnknownDiscriminant(1)));
+    assert_eq!(errno::cast(-3i32), Err(EnumError::UnknownDiscriminant(-3)));
+    assert_eq!(
+        errno::cast(-32769i32),
+        Err(EnumError::Cast(Error::NegOverflow))
+    );
+    assert_eq!(errno::cast(f32::NAN), Err(EnumError::Cast(Error::NaN)));
+
+    assert_eq!(open_flags::cast(0u8), Ok(open_flags(0)));
+    assert_eq!(open_flags::cast(0x403u16), Ok(open_flags(0x403)));
+    assert_eq!(
+        open_flags::cast(open_flags::O_APPEND.0),
+        Ok(open_flags::O_APPEND)
+    );
+    assert_eq!(open_flags::cast(0x407u64), Err(FlagsError::UnknownBits(4)));
+    assert_eq!(
+        open_flags::cast(1u64 << 32),
+        Err(FlagsError::Cast(Error::Overflow))
+    );
+    assert_eq!(
+        open_flags::cast(-1i32),
+        Err(FlagsError::Cast(Error::NegOverflow))
+    );
+    // like the other casts into enums and flags
+    #[cfg(feature = "failure-hook")]
+    assert_eq!(
+        failures(|| {
+            let _ = errno::cast(1i32);
+            let _ = open_flags::cast(4u8);
+        }),
+        [Error::Overflow, Error::Overflow]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_error() {
+    use std::io::{self, ErrorKind};
+    use std::string::ToString;
+
+    use crate::io::{ContextError, ResultExt};
+    use crate::Error;
+
+    fn parse(x: i64) -> io::Result<u8> {
+        Ok(crate::u8(x)?)
+    }
+
+    assert_eq!(parse(255).unwrap(), 255);
+    let e = parse(-1).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        e.get_ref().unwrap().downcast_ref::<Error>(),
+        Some(&Error::NegOverflow)
+    );
+
+    // narrower error types
+    let e = crate::u8(256u16).context("offset").unwrap_err();
+    assert_eq!(e.to_string(), "offset: Overflow during numeric conversion");
+    let payload = e.get_ref().unwrap();
+    let error = payload.downcast_ref::<ContextError<&str>>().unwrap();
+    assert_eq!(
+        (*error.context(), error.error()),
+        ("offset", Error::Overflow)
+    );
+    assert!(payload.source().is_none());
+
+    let mut calls = 0;
+    let mut field = |i: usize| {
+        calls += 1;
+        std::format!("field #{}", i)
+    };
+    assert_eq!(crate::i8(1.5f32).with_context(|| field(0)).unwrap(), 1);
+    let e = crate::i8(f32::NAN).with_context(|| field(1)).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "field #1: Cannot store NaN in type which does not support it"
+    );
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn adc() {
+    use crate::adc::{from_code, from_code_fixed, to_code, to_code_fixed};
+    use crate::Error;
+
+    assert_eq!(from_code::<1, _>(1u8, 2.), Ok(1.));
+    assert_eq!(from_code::<1, _>(2u8, 2.), Err(Error::Overflow));
+    assert_eq!(from_code::<16, _>(-1i32, 2.), Err(Error::NegOverflow));
+    assert_eq!(from_code::<32, _>(u32::MAX, 1.), Ok(1.));
+    assert_eq!(from_code::<32, _>(1u64 << 32, 1.), Err(Error::Overflow));
+
+    // rounds to the nearest millivolt
+    assert_eq!(from_code_fixed::<10, _, u16>(1023u16, 3300), Ok(3297));
+    assert_eq!(from_code_fixed::<10, _, u16>(1u16, 3300), Ok(3));
+    assert_eq!(
+        from_code_fixed::<32, _, u64>(u32::MAX, u32::MAX),
+        Ok(0xffff_fffe)
+    );
+    assert_eq!(
+        from_code_fixed::<8, _, u8>(255u8, 1000),
+        Err(Error::Overflow)
+    );
+
+    for code in 0..4096u16 {
+        let x = from_code::<12, _>(code, 2.5).unwrap();
+        assert_eq!(to_code::<12, u16>(x, 2.5), Ok(code));
+
+        let mv = from_code_fixed::<12, _, u32>(code, 4096).unwrap();
+        assert_eq!(to_code_fixed::<12, u16>(mv, 4096), Ok(code));
+    }
+
+    // within half a step of the range
+    assert_eq!(to_code::<8, u8>(-0.001, 1.), Ok(0));
+    assert_eq!(to_code::<8, u8>(0.997, 1.), Ok(255));
+    assert_eq!(to_code::<8, u8>(1., 1.), Err(Error::Overflow));
+    assert_eq!(to_code::<8, u8>(f32::NAN, 1.), Err(Error::NaN));
+    assert_eq!(
+        to_code::<8, u8>(f32::NEG_INFINITY, 1.),
+        Err(Error::Infinite)
+    );
+    assert_eq!(to_code::<9, u8>(0.75, 1.), Err(Error::Overflow));
+    assert_eq!(to_code::<32, u32>(0.5, 1.), Ok(1 << 31));
+
+    assert_eq!(to_code_fixed::<8, u8>(1000, 1000), Err(Error::Overflow));
+    assert_eq!(to_code_fixed::<8, u8>(998, 1000), Ok(255));
+    assert_eq!(
+        to_code_fixed::<32, u32>(u32::MAX, u32::MAX),
+        Err(Error::Overflow)
+    );
+    assert_eq!(to_code_fixed::<16, i16>(500, 1000), Err(Error::Overflow));
+}
+
+#[test]
+fn bench_support() {
+    use crate::bench_support::{checked, checked_slice, saturating, try_from};
+    use crate::Error;
+
+    assert_eq!(checked::<i8, _>(-129i64), Err(Error::NegOverflow));
+    assert_eq!(checked::<f32, _>(1u8), Ok(1.));
+    assert_eq!(saturating::<i8, _>(-129i64), -128);
+    assert_eq!(saturating::<u16, _>(f64::INFINITY), u16::MAX);
+    assert_eq!(try_from::<u16, _>(65535u32), Ok(65535));
+    assert!(try_from::<u16, _>(65536u32).is_err());
+
+    let mut dst = [0i8; 4];
+    assert_eq!(checked_slice(&[0.5f32, -1., 127., -128.], &mut dst), Ok(()));
+    assert_eq!(dst, [0, -1, 127, -128]);
+    assert_eq!(
+        checked_slice(&[1u16, 128, 2, 3], &mut dst),
+        Err(Error::Overflow)
+    );
+    assert_eq!(dst[0], 1);
+}
+
+#[cfg(not(feature = "portable"))]
+#[test]
+fn pointer_width() {
+    use core::convert::TryFrom;
+
+    use crate::{classify, Error, NegOverflowError, OverflowError, StdCompat};
+
+    #[cfg(target_pointer_width = "32")]
+    type Usize = u32;
+    #[cfg(target_pointer_width = "32")]
+    type Isize = i32;
+    #[cfg(target_pointer_width = "64")]
+    type Usize = u64;
+    #[cfg(target_pointer_width = "64")]
+    type Isize = i64;
+
+    // `usize` and `isize` cast like the integers of the same width
+    macro_rules! same {
+        ($($ty:ident),+) => {
+            $(
+                assert_eq!(classify::<usize, $ty>(), classify::<Usize, $ty>());
+                assert_eq!(classify::<$ty, usize>(), classify::<$ty, Usize>());
+                assert_eq!(classify::<isize, $ty>(), classify::<Isize, $ty>());
+                assert_eq!(classify::<$ty, isize>(), classify::<$ty, Isize>());
+            )+
+        }
+    }
+
+    same!(f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    assert_eq!(classify::<usize, isize>(), classify::<Usize, Isize>());
+    assert_eq!(classify::<isize, usize>(), classify::<Isize, Usize>());
+    assert_eq!(classify::<usize, usize>(), classify::<Usize, Usize>());
+
+    let overflow: Result<u8, OverflowError> = crate::u8(usize::MAX);
+    assert_eq!(overflow, Err(OverflowError));
+    assert_eq!(crate::usize(-1i8), Err(NegOverflowError));
+    assert_eq!(crate::isize(usize::MAX), Err(OverflowError));
+    assert_eq!(crate::usize(Usize::MAX), usize::MAX);
+    assert_eq!(crate::i128(isize::MIN), i128::from(Isize::MIN));
+
+    // `From` only where no pointer width makes the cast fallible
+    assert_eq!(StdCompat::<usize>::from(u16::MAX), StdCompat(65_535));
+    assert_eq!(
+        StdCompat::<f64>::from(isize::MIN),
+        StdCompat(Isize::MIN as f64)
+    );
+    assert_eq!(
+        StdCompat::<u128>::from(usize::MAX),
+        StdCompat(usize::MAX as u128)
+    );
+    assert_eq!(
+        StdCompat::<u64>::try_from(usize::MAX),
+        Ok(StdCompat(usize::MAX as u64))
+    );
+    assert_eq!(
+        StdCompat::<isize>::try_from(u16::MAX),
+        Ok(StdCompat(65_535))
+    );
+    assert_eq!(StdCompat::<u8>::try_from(256usize), Err(Error::Overflow));
+}
+
+#[cfg(all(
+    feature = "portable",
+    not(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))
+))]
+#[test]
+fn portable() {
+    use core::convert::TryFrom;
+
+    use crate::{Error, From, StdCompat};
+
+    // fallible whatever the pointer width
+    const { assert!(<usize as From<u8>>::FALLIBLE) };
+    let x: Result<usize, Error> = crate::usize(255u8);
+    assert_eq!(x, Ok(255));
+    assert_eq!(crate::u64(usize::MAX), Ok(usize::MAX as u64));
+    assert_eq!(crate::isize(usize::MAX), Err(Error::Overflow));
+    assert_eq!(crate::usize(-1isize), Err(Error::NegOverflow));
+    assert_eq!(crate::usize(f32::NAN), Err(Error::NaN));
+    assert_eq!(crate::f32(isize::MIN), Ok(isize::MIN as f32));
+    assert_eq!(crate::u8(256usize), Err(Error::Overflow));
+
+    assert_eq!(StdCompat::<usize>::from(u16::MAX), StdCompat(65_535));
+    assert_eq!(StdCompat::<u8>::try_from(256usize), Err(Error::Overflow));
+}
+
+#[cfg(any(feature = "assume-32bit-ptr", feature = "assume-64bit-ptr"))]
+#[test]
+fn assume_ptr() {
+    use crate::{Error, From};
+
+    // infallible for every pointer width assumed
+    let x: usize = crate::usize(u32::MAX);
+    assert_eq!(x, u32::MAX as usize);
+    let x: isize = crate::isize(i32::MIN);
+    assert_eq!(x, i32::MIN as isize);
+    let x: u64 = crate::u64(usize::MAX);
+    assert_eq!(x, usize::MAX as u64);
+    let x: f64 = crate::f64(isize::MIN);
+    assert_eq!(x, isize::MIN as f64);
+    let x: usize = crate::usize(usize::MAX);
+    assert_eq!(x, usize::MAX);
+    const { assert!(!<usize as From<u16>>::FALLIBLE) };
+
+    // fallible for every pointer width
+    let x: Result<u32, Error> = crate::u32(usize::MAX);
+    assert_eq!(x, Err(Error::Overflow));
+    assert_eq!(crate::usize(-1isize), Err(Error::NegOverflow));
+    assert_eq!(crate::isize(usize::MAX), Err(Error::Overflow));
+    assert_eq!(crate::usize(f32::NAN), Err(Error::NaN));
+    assert_eq!(
+        <u32 as From<usize>>::FALLIBILITY,
+        <u32 as From<u64>>::FALLIBILITY
+    );
+
+    // only infallible if pointers are assumed to be 64 bits wide
+    #[cfg(feature = "assume-64bit-ptr")]
+    {
+        let x: usize = crate::usize(u64::MAX);
+        assert_eq!(x, usize::MAX);
+        let x: isize = crate::isize(u32::MAX);
+        assert_eq!(x, u32::MAX as isize);
+    }
+    #[cfg(not(feature = "assume-64bit-ptr"))]
+    {
+        let x: Result<usize, Error> = crate::usize(u64::MAX);
+        assert_eq!(x.is_ok(), cfg!(target_pointer_width = "64"));
+        assert_eq!(
+            crate::isize(u32::MAX).is_ok(),
+            cfg!(target_pointer_width = "64")
+        );
+        assert_eq!(
+            <usize as From<u64>>::FALLIBILITY,
+            crate::Fallibility::Overflow
+        );
+    }
+}
+
+#[test]
+fn float_bit_patterns() {
+    use crate::Error;
+
+    // NaN, whatever its sign and payload
+    assert_eq!(crate::u8(-f32::NAN), Err(Error::NaN));
+    assert_eq!(
+        crate::i32(f64::from_bits(0x7ff0_0000_0000_0001)),
+        Err(Error::NaN)
+    );
+    assert_eq!(crate::i64(f64::from_bits(u64::MAX)), Err(Error::NaN));
+    assert_eq!(crate::u128(f32::from_bits(0xffc0_0001)), Err(Error::NaN));
+    assert_eq!(crate::u128(f32::NEG_INFINITY), Err(Error::Infinite));
+    assert_eq!(crate::i8(f64::NEG_INFINITY), Err(Error::Infinite));
+
+    // zeros and subnormals
+    assert_eq!(crate::u8(-0.0f32), Ok(0));
+    assert_eq!(crate::i8(-0.0f64), Ok(0));
+    assert_eq!(crate::u32(-f64::from_bits(1)), Ok(0));
+    assert_eq!(crate::u16(f32::from_bits(1)), Ok(0));
+
+    // the neighbours of the limits
+    let toward_zero = |x: f32| f32::from_bits(x.to_bits() - 1);
+    let away_from_zero = |x: f32| f32::from_bits(x.to_bits() + 1);
+    assert_eq!(crate::u8(toward_zero(-1.0)), Ok(0));
+    assert_eq!(crate::u8(-1.0f32), Err(Error::NegOverflow));
+    assert_eq!(crate::u8(255.0f32), Ok(255));
+    assert_eq!(crate::u8(away_from_zero(255.0)), Err(Error::Overflow));
+    assert_eq!(crate::u8(256.0f32), Err(Error::Overflow));
+    assert_eq!(crate::i8(-128.0f32), Ok(-128));
+    assert_eq!(crate::i8(away_from_zero(-128.0)), Err(Error::NegOverflow));
+    assert_eq!(crate::i8(-129.0f32), Err(Error::NegOverflow));
+    assert_eq!(crate::i32(toward_zero(2_147_483_648.0)), Ok(2_147_483_520));
+    assert_eq!(crate::i32(2_147_483_648.0f32), Err(Error::Overflow));
+    assert_eq!(crate::i32(-2_147_483_648.0f32), Ok(i32::MIN));
+    assert_eq!(
+        crate::i32(away_from_zero(-2_147_483_648.0)),
+        Err(Error::NegOverflow)
+    );
+    assert_eq!(crate::u64(u64::MAX as f64), Err(Error::Overflow));
+    assert_eq!(
+        crate::u64(f64::from_bits((u64::MAX as f64).to_bits() - 1)),
+        Ok(18_446_744_073_709_549_568)
+    );
+    assert_eq!(crate::u128(f32::MAX), Ok(f32::MAX as u128));
+    assert_eq!(crate::u128(-f32::MAX), Err(Error::NegOverflow));
+}
+
+#[cfg(feature = "small-code")]
+#[test]
+fn small_code() {
+    use crate::small_code::{from_signed, from_unsigned};
+    use crate::Error;
+
+    assert_eq!(from_signed(-128, 8, true), None);
+    assert_eq!(from_signed(127, 8, true), None);
+    assert_eq!(from_signed(-129, 8, true), Some(Error::NegOverflow));
+    assert_eq!(from_signed(128, 8, true), Some(Error::Overflow));
+    assert_eq!(from_signed(-1, 64, false), Some(Error::NegOverflow));
+    assert_eq!(from_signed(u64::MAX.into(), 64, false), None);
+    assert_eq!(
+        from_signed(i128::from(u64::MAX) + 1, 64, false),
+        Some(Error::Overflow)
+    );
+
+    assert!(from_unsigned(255, u8::MAX.into()));
+    assert!(!from_unsigned(256, u8::MAX.into()));
+    assert!(!from_unsigned(u128::MAX, i128::MAX as u128));
+
+    // the casts still check the range of their own types
+    assert_eq!(crate::i8(200i32), Err(Error::Overflow));
+    assert_eq!(crate::u16(-1i64), Err(Error::NegOverflow));
+    assert_eq!(crate::u32(u64::MAX), Err(crate::OverflowError));
+    assert_eq!(crate::i64(u128::MAX), Err(crate::OverflowError));
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn ct() {
+    use crate::ct;
+
+    // `ct::cast` accepts the same values as the checked casts
+    macro_rules! agree {
+        ($($src:ident => $($dst:ident),+: $xs:expr;)+) => {
+            $(
+                for x in $xs {
+                    $(
+                        assert_eq!(
+                            Option::from(ct::cast::<$dst, $src>(x)),
+                            crate::auto::<$dst, $src>(x).ok(),
+                            "{}{} -> {}",
+                            x,
+                            stringify!($src),
+                            stringify!($dst),
+                        );
+                    )+
+                }
+            )+
+        };
+    }
+
+    agree! {
+        i16 => i8, u8, i16, u16, i32, u32: i16::MIN..=i16::MAX;
+        u16 => i8, u8, i16, u16, i32, u64: u16::MIN..=u16::MAX;
+        i64 => i32, u32, u64, i128, usize, isize: [i64::MIN, -1 << 31, -1, 0, 1 << 31, i64::MAX];
+        u64 => i32, u32, i64, u128, usize, isize: [0, 1 << 31, 1 << 32, 1 << 63, u64::MAX];
+        i128 => i64, u64, u128: [i128::MIN, -1, 0, 1 << 64, i128::MAX];
+        u128 => u64, i128: [0, 1 << 64, 1 << 127, u128::MAX];
+    }
+
+    assert_eq!(ct::cast::<u8, _>(300u32).unwrap_or(7), 7);
+    assert!(bool::from(ct::cast::<u32, _>(-1i32).is_none()));
+    assert!(bool::from(ct::cast::<i32, _>(u32::MAX).is_none()));
+}
+
+#[test]
+fn saturating_as() {
+    use crate::saturating::clamp;
+    use crate::{saturating, BoundaryValues};
+
+    // the casts from floats into integers saturate with `as`, which must agree
+    // with the saturation in terms of the checked casts
+    macro_rules! agree {
+        ($($src:ident => $($dst:ident),+;)+) => {
+            $(
+                let xs = $src::boundary_values().flat_map(|x| {
+                    let bits = x.to_bits();
+                    [x, $src::from_bits(bits.wrapping_sub(1)), $src::from_bits(bits.wrapping_add(1))]
+                });
+                for x in xs {
+                    $(
+                        assert_eq!(
+                            saturating::<$dst, _>(x),
+                            clamp::<$dst, _>(x),
+                            "{:?}{} -> {}",
+                            x,
+                            stringify!($src),
+                            stringify!($dst),
+                        );
+                    )+
+                }
+            )+
+        };
+    }
+
+    agree! {
+        f32 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+        f64 => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+    }
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive() {
+    use crate::{EnumError, Error, From as _};
+
+    #[derive(Clone, Copy, Debug, PartialEq, crate::From)]
+    #[repr(i16)]
+    enum Signed {
+        Min = i16::MIN,
+        MinusOne = -1,
+        // implicit discriminants follow the previous one
+        Zero,
+        One,
+        Max = i16::MAX,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, crate::From)]
+    #[repr(align(16), u64)]
+    enum Wide {
+        Big = 1 << 40,
+    }
+
+    assert_eq!(Signed::cast(-32768i64), Ok(Signed::Min));
+    assert_eq!(Signed::cast(-1i8), Ok(Signed::MinusOne));
+    assert_eq!(Signed::cast(0u8), Ok(Signed::Zero));
+    assert_eq!(Signed::cast(1u128), Ok(Signed::One));
+    assert_eq!(Signed::cast(32767u16), Ok(Signed::Max));
+    assert_eq!(Signed::cast(1.5f32), Ok(Signed::One));
+    assert_eq!(Signed::cast(2u8), Err(EnumError::UnknownDiscriminant(2)));
+    assert_eq!(
+        Signed::cast(-32769i32),
+        Err(EnumError::Cast(Error::NegOverflow))
+    );
+    assert_eq!(Signed::cast(f64::NAN), Err(EnumError::Cast(Error::NaN)));
+    assert_eq!(Wide::cast(1u64 << 40), Ok(Wide::Big));
+    assert_eq!(Wide::cast(-1i64), Err(EnumError::Cast(Error::NegOverflow)));
+
+    // the errors convert into `Error`
+    assert_eq!(crate::auto::<Signed, _>(7u8), Err(Error::Overflow));
+    assert_eq!(
+        format!("{}", EnumError::UnknownDiscriminant(7i16)),
+        "Unknown enum discriminant: 7"
+    );
+
+    // into the primitives
+    let x: i16 = crate::i16(Signed::Min);
+    assert_eq!(x, i16::MIN);
+    assert_eq!(crate::i64(Signed::MinusOne), -1);
+    assert_eq!(crate::f32(Signed::Max), 32767.);
+    assert_eq!(crate::u16(Signed::MinusOne), Err(crate::NegOverflowError));
+    assert_eq!(crate::u64(Wide::Big), 1 << 40);
+    assert_eq!(crate::u32(Wide::Big), Err(crate::OverflowError));
+}
+
+#[test]
+fn fallible_default() {
+    use crate::{Error, Fallibility, From};
+
+    // an implementation written before `FALLIBLE` existed
+    struct Percent(u8);
+
+    impl From<u8> for Percent {
+        type Output = Result<Percent, Error>;
+        type Error = Error;
+
+        fn cast(src: u8) -> Self::Output {
+            if src <= 100 {
+                Ok(Percent(src))
+            } else {
+                Err(Error::Overflow)
+            }
+        }
+    }
+
+    const { assert!(<Percent as From<u8>>::FALLIBLE) };
+    assert_eq!(<Percent as From<u8>>::FALLIBILITY, Fallibility::Fallible);
+    assert_eq!(Percent::cast(101).map(|x| x.0), Err(Error::Overflow));
+}
+
+/// Runs `f` with a failure hook registered, and returns the errors it was
+/// called with, in order
+#[cfg(feature = "failure-hook")]
+fn failures(f: impl FnOnce()) -> std::vec::Vec<crate::Error> {
+    use std::cell::RefCell;
+    use std::sync::Mutex;
+    use std::vec::Vec;
+
+    use crate::Error;
+
+    // the hook is global, so the tests that register one take turns; the
+    // errors are recorded per thread, so the other tests' failures don't count
+    static LOCK: Mutex<()> = Mutex::new(());
+    std::thread_local! {
+        static ERRORS: RefCell<Vec<Error>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record(e: &Error) {
+        ERRORS.with(|errors| errors.borrow_mut().push(*e));
+    }
+
+    let _turn = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    ERRORS.with(|errors| errors.borrow_mut().clear());
+
+    crate::set_failure_hook(record);
+    f();
+    crate::clear_failure_hook();
+    // not recorded
+    let _ = crate::u8(-1i8);
+
+    ERRORS.with(|errors| errors.take())
+}
+
+#[cfg(feature = "failure-hook")]
+#[test]
+fn failure_hook() {
+    use crate::Error;
+
+    let errors = failures(|| {
+        assert!(crate::u8(256u16).is_err());
+        assert_eq!(crate::u8(255u16), Ok(255));
+        assert!(crate::i8(f32::NAN).is_err());
+        assert_eq!(crate::u16(1u8), 1);
+    });
+    assert_eq!(errors, [Error::Overflow, Error::NaN]);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing() {
+    use std::string::{String, ToString};
+    use std::sync::{Arc, Mutex};
+    use std::vec::Vec;
+    use std::{fmt, vec};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// The level and the fields of an event
+    type Captured = (Level, Vec<(&'static str, String)>);
+
+    /// Captures the events of the thread it's the default subscriber of
+    struct Capture(Arc<Mutex<Vec<Captured>>>);
+
+    struct Fields(Vec<(&'static str, String)>);
+
+    impl Visit for Fields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name(), value.to_string()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push((field.name(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(Vec::new());
+            event.record(&mut fields);
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), fields.0));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    tracing::subscriber::with_default(Capture(events.clone()), || {
+        assert!(crate::u8(256u16).is_err());
+        assert_eq!(crate::u8(255u16), Ok(255));
+        assert!(crate::i16(f64::NAN).is_err());
+    });
+
+    let fields = |src, dst, error: &str| {
+        vec![
+            ("message", "cast failed".to_string()),
+            ("src", String::from(src)),
+            ("dst", String::from(dst)),
+            ("error", error.to_string()),
+        ]
+    };
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            (Level::DEBUG, fields("u16", "u8", "Overflow")),
+            (Level::DEBUG, fields("f64", "i16", "NaN")),
+        ]
+    );
+}
@@ -0,0 +1,165 @@
+//! Opting user defined newtypes into the crate's casts
+
+use crate::outcome::{Outcome, Wrap};
+use crate::{Error, Fallibility, From, IntoResult, Primitive};
+
+/// Newtypes around a numeric primitive that cast like the primitive
+///
+/// Implementing this trait makes the newtype castable from and into every
+/// type that its inner primitive can be casted from and into, including other
+/// `Transparent` newtypes.
+///
+/// ```
+/// use cast::{Error, From, Transparent};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Meters(f64);
+///
+/// impl Transparent for Meters {
+///     type Inner = f64;
+///
+///     fn from_inner(inner: f64) -> Meters {
+///         Meters(inner)
+///     }
+///
+///     fn into_inner(self) -> f64 {
+///         self.0
+///     }
+/// }
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Steps(u16);
+///
+/// impl Transparent for Steps {
+///     type Inner = u16;
+///
+///     fn from_inner(inner: u16) -> Steps {
+///         Steps(inner)
+///     }
+///
+///     fn into_inner(self) -> u16 {
+///         self.0
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Meters::cast(3u8), Meters(3.));
+/// assert_eq!(cast::u8(Meters(2.5)), Ok(2));
+/// assert_eq!(Steps::cast(Meters(1e6)), Err(Error::Overflow));
+/// # }
+/// ```
+///
+/// Casts into a newtype report this crate's `Error`; casts out of a newtype
+/// have the same output as the cast from its inner primitive.
+pub trait Transparent: Copy {
+    /// The wrapped primitive
+    type Inner: Primitive;
+
+    /// Wraps a primitive
+    fn from_inner(inner: Self::Inner) -> Self;
+
+    /// Unwraps the primitive
+    fn into_inner(self) -> Self::Inner;
+}
+
+impl<Src, Dst> From<Src> for Dst
+where
+    Dst: Transparent,
+    Dst::Inner: From<Src>,
+    <Dst::Inner as From<Src>>::Output: Outcome<Value = Dst::Inner>,
+    <<Dst::Inner as From<Src>>::Output as Outcome>::Fallibility: Wrap<Dst>,
+{
+    type Output =
+        <<<Dst::Inner as From<Src>>::Output as Outcome>::Fallibility as Wrap<Dst>>::Output;
+    type Error = Error;
+    const FALLIBLE: bool = <Dst::Inner as From<Src>>::FALLIBLE;
+    const FALLIBILITY: Fallibility = <Dst::Inner as From<Src>>::FALLIBILITY;
+
+    #[inline]
+    fn cast(src: Src) -> Self::Output {
+        let result = <Dst::Inner as From<Src>>::cast(src)
+            .into_result()
+            .map(Dst::from_inner);
+
+        <<<Dst::Inner as From<Src>>::Output as Outcome>::Fallibility as Wrap<Dst>>::wrap(result)
+    }
+}
+
+/// Types that cast into the primitives like the primitive they're represented
+/// by
+///
+/// Every `Transparent` newtype implements this, as its inner primitive. It's
+/// also what `#[derive(From)]` implements for fieldless enums, as their
+/// discriminant, so that they can be casted into the primitives; an enum can't
+/// be `Transparent` because not every value of its `#[repr]` is a variant.
+///
+/// ```
+/// use cast::{OverflowError, Repr};
+///
+/// #[derive(Clone, Copy)]
+/// enum Level {
+///     Low = 1,
+///     High = 300,
+/// }
+///
+/// impl Repr for Level {
+///     type Primitive = u16;
+///
+///     fn repr(self) -> u16 {
+///         self as u16
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(cast::u32(Level::High), 300);
+/// assert_eq!(cast::u8(Level::Low), Ok(1));
+/// assert_eq!(cast::u8(Level::High), Err(OverflowError));
+/// # }
+/// ```
+pub trait Repr: Copy {
+    /// The primitive this type is represented by
+    type Primitive: Primitive;
+
+    /// Converts this value into its primitive
+    fn repr(self) -> Self::Primitive;
+}
+
+impl<T> Repr for T
+where
+    T: Transparent,
+{
+    type Primitive = T::Inner;
+
+    #[inline]
+    fn repr(self) -> T::Inner {
+        self.into_inner()
+    }
+}
+
+macro_rules! transparent {
+    ($($dst:ident),+) => {
+        $(
+            dst! {
+                $dst,
+
+                impl<Src> From<Src> for $dst
+                where
+                    Src: Repr,
+                    $dst: From<Src::Primitive>,
+                {
+                    type Output = <$dst as From<Src::Primitive>>::Output;
+                    type Error = <$dst as From<Src::Primitive>>::Error;
+                    const FALLIBLE: bool = <$dst as From<Src::Primitive>>::FALLIBLE;
+                    const FALLIBILITY: Fallibility = <$dst as From<Src::Primitive>>::FALLIBILITY;
+
+                    #[inline]
+                    fn cast(src: Src) -> Self::Output {
+                        <$dst as From<Src::Primitive>>::cast(src.repr())
+                    }
+                }
+            }
+        )+
+    }
+}
+
+transparent!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,38 @@
+//! A single, `Result` returning, cast method for every pair of types
+
+use crate::{Error, From, IntoResult};
+
+/// Checked cast into `Dst` that always returns a `Result`
+///
+/// Unlike `From`, whose `Output` is `Self` for infallible casts and a
+/// `Result` otherwise, `try_cast` has the same signature for every pair of
+/// types. That makes the trait object safe:
+///
+/// ```
+/// use cast::{Error, TryCast};
+///
+/// # fn main() {
+/// let values: [&dyn TryCast<u8>; 3] = [&1u8, &-1i16, &300.0f32];
+/// let bytes: Vec<_> = values.iter().map(|&v| v.try_cast()).collect();
+///
+/// assert_eq!(bytes, [Ok(1), Err(Error::NegOverflow), Err(Error::Overflow)]);
+/// # }
+/// ```
+///
+/// It's implemented for every `Src` that `Dst` can be casted from.
+pub trait TryCast<Dst> {
+    /// Checked cast from `self` into `Dst`
+    fn try_cast(&self) -> Result<Dst, Error>;
+}
+
+impl<Src, Dst> TryCast<Dst> for Src
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    #[inline]
+    fn try_cast(&self) -> Result<Dst, Error> {
+        Dst::cast(*self).into_result()
+    }
+}
@@ -0,0 +1,63 @@
+//! Element-wise casts between tuples
+
+use crate::outcome::{Any, Outcome, Wrap};
+use crate::{Error, Fallibility, From, IntoResult};
+
+macro_rules! tuple {
+    ($($src:ident => $dst:ident, $i:tt;)+) => {
+        impl<$($src, $dst),+> From<($($src,)+)> for ($($dst,)+)
+        where
+            $(
+                $dst: From<$src>,
+                <$dst as From<$src>>::Output: Outcome<Value = $dst>,
+            )+
+            ($(<<$dst as From<$src>>::Output as Outcome>::Fallibility,)+): Any,
+            <($(<<$dst as From<$src>>::Output as Outcome>::Fallibility,)+) as Any>::Output:
+                Wrap<($($dst,)+)>,
+        {
+            type Output = <
+                <($(<<$dst as From<$src>>::Output as Outcome>::Fallibility,)+) as Any>::Output
+                    as Wrap<($($dst,)+)>
+            >::Output;
+            type Error = Error;
+            const FALLIBLE: bool = $(<$dst as From<$src>>::FALLIBLE)||+;
+            const FALLIBILITY: Fallibility =
+                Fallibility::Infallible$(.or(<$dst as From<$src>>::FALLIBILITY))+;
+
+            #[inline]
+            fn cast(src: ($($src,)+)) -> Self::Output {
+                let result = (|| {
+                    Ok(($(<$dst as From<$src>>::cast(src.$i).into_result()?,)+))
+                })();
+
+                <
+                    <($(<<$dst as From<$src>>::Output as Outcome>::Fallibility,)+) as Any>::Output
+                        as Wrap<($($dst,)+)>
+                >::wrap(result)
+            }
+        }
+
+        // the output of the infallible casts
+        impl<$($dst),+> IntoResult<($($dst,)+)> for ($($dst,)+) {
+            #[inline]
+            fn into_result(self) -> Result<($($dst,)+), Error> {
+                Ok(self)
+            }
+        }
+    }
+}
+
+tuple!(S0 => D0, 0;);
+tuple!(S0 => D0, 0; S1 => D1, 1;);
+tuple!(S0 => D0, 0; S1 => D1, 1; S2 => D2, 2;);
+tuple!(S0 => D0, 0; S1 => D1, 1; S2 => D2, 2; S3 => D3, 3;);
+tuple!(S0 => D0, 0; S1 => D1, 1; S2 => D2, 2; S3 => D3, 3; S4 => D4, 4;);
+tuple!(S0 => D0, 0; S1 => D1, 1; S2 => D2, 2; S3 => D3, 3; S4 => D4, 4; S5 => D5, 5;);
+tuple!(
+    S0 => D0, 0; S1 => D1, 1; S2 => D2, 2; S3 => D3, 3; S4 => D4, 4; S5 => D5, 5;
+    S6 => D6, 6;
+);
+tuple!(
+    S0 => D0, 0; S1 => D1, 1; S2 => D2, 2; S3 => D3, 3; S4 => D4, 4; S5 => D5, 5;
+    S6 => D6, 6; S7 => D7, 7;
+);
@@ -0,0 +1,142 @@
+//! Errors that know the types involved in the cast
+
+use core::any;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+#[cfg(feature = "track-location")]
+use core::panic::Location;
+
+use crate::{Error, From, IntoResult};
+
+/// A cast error that records the source and destination types of the cast
+///
+/// Its `Display` implementation names both types, e.g. "cannot cast i64 → u8:
+/// overflow".
+pub struct CastError<Src, Dst> {
+    error: Error,
+    #[cfg(feature = "track-location")]
+    location: &'static Location<'static>,
+    _types: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<Src, Dst> CastError<Src, Dst> {
+    /// Creates a new error for a cast from `Src` into `Dst`
+    #[cfg_attr(feature = "track-location", track_caller)]
+    #[inline]
+    pub fn new(error: Error) -> CastError<Src, Dst> {
+        CastError {
+            error,
+            #[cfg(feature = "track-location")]
+            location: Location::caller(),
+            _types: PhantomData,
+        }
+    }
+
+    /// The reason the cast failed
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// The location in the source code where the error was created
+    ///
+    /// For errors returned by `typed`, this is the location of the call to
+    /// `typed`.
+    ///
+    /// *This API requires the `track-location` Cargo feature*
+    #[cfg(feature = "track-location")]
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl<Src, Dst> Clone for CastError<Src, Dst> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Src, Dst> Copy for CastError<Src, Dst> {}
+
+impl<Src, Dst> PartialEq for CastError<Src, Dst> {
+    fn eq(&self, other: &Self) -> bool {
+        self.error == other.error
+    }
+}
+
+impl<Src, Dst> Eq for CastError<Src, Dst> {}
+
+impl<Src, Dst> Hash for CastError<Src, Dst> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.error.hash(state)
+    }
+}
+
+impl<Src, Dst> fmt::Debug for CastError<Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("CastError");
+        s.field("src", &any::type_name::<Src>())
+            .field("dst", &any::type_name::<Dst>())
+            .field("error", &self.error);
+        #[cfg(feature = "track-location")]
+        s.field("location", &self.location);
+        s.finish()
+    }
+}
+
+impl<Src, Dst> fmt::Display for CastError<Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot cast {} → {}: {}",
+            any::type_name::<Src>(),
+            any::type_name::<Dst>(),
+            match self.error {
+                Error::Infinite => "infinite value",
+                Error::NaN => "NaN",
+                Error::Overflow => "overflow",
+                Error::NegOverflow => "negative overflow",
+            }
+        )
+    }
+}
+
+impl<Src, Dst> core::error::Error for CastError<Src, Dst> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<Src, Dst> core::convert::From<CastError<Src, Dst>> for Error {
+    #[inline]
+    fn from(e: CastError<Src, Dst>) -> Error {
+        e.error
+    }
+}
+
+/// Checked cast whose error records the source and destination types
+///
+/// ```
+/// # fn main() {
+/// let e = cast::typed::<u8, _>(300i64).unwrap_err();
+///
+/// assert_eq!(e.error(), cast::Error::Overflow);
+/// assert_eq!(e.to_string(), "cannot cast i64 → u8: overflow");
+/// # }
+/// ```
+#[cfg_attr(feature = "track-location", track_caller)]
+#[inline]
+pub fn typed<Dst, Src>(src: Src) -> Result<Dst, CastError<Src, Dst>>
+where
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    match <Dst as From<Src>>::cast(src).into_result() {
+        Ok(dst) => Ok(dst),
+        // NOTE not using `map_err` because `#[track_caller]` doesn't propagate
+        // into closures
+        Err(e) => Err(CastError::new(e)),
+    }
+}
@@ -0,0 +1,95 @@
+//! Errors of the casts into types that accept only some values of their
+//! integer: enums and flags
+
+use core::fmt;
+
+use crate::Error;
+
+/// The error of the casts into enums: the enums that derive `cast::From`,
+/// `NumEnum`, the enums of `bindgen!` and `bitfield::unpack`
+///
+/// It converts into `Error`, as `Error::Overflow` if the discriminant is
+/// unknown, so these casts work wherever the others do.
+///
+/// ```
+/// use cast::{EnumError, Error};
+///
+/// let error = EnumError::UnknownDiscriminant(0i8);
+/// assert_eq!(error.to_string(), "Unknown enum discriminant: 0");
+/// assert_eq!(Error::from(error), Error::Overflow);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EnumError<D> {
+    /// The value doesn't fit in the integer type of the enum
+    Cast(Error),
+    /// The value fits, but isn't the discriminant of any variant
+    UnknownDiscriminant(D),
+}
+
+impl<D: fmt::Display> fmt::Display for EnumError<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnumError::Cast(error) => error.fmt(f),
+            EnumError::UnknownDiscriminant(d) => write!(f, "Unknown enum discriminant: {}", d),
+        }
+    }
+}
+
+impl<D: fmt::Debug + fmt::Display> core::error::Error for EnumError<D> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EnumError::Cast(error) => Some(error),
+            EnumError::UnknownDiscriminant(_) => None,
+        }
+    }
+}
+
+impl<D> core::convert::From<EnumError<D>> for Error {
+    #[inline]
+    fn from(e: EnumError<D>) -> Error {
+        match e {
+            EnumError::Cast(error) => error,
+            EnumError::UnknownDiscriminant(_) => Error::Overflow,
+        }
+    }
+}
+
+/// The error of the casts into flags: `BitFlags` and the flags of `bindgen!`
+///
+/// It converts into `Error`, as `Error::Overflow` if unknown bits are set, so
+/// these casts work wherever the others do.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlagsError<B> {
+    /// The value doesn't fit in the bits type of the flags
+    Cast(Error),
+    /// The value fits, but these bits don't correspond to any flag
+    UnknownBits(B),
+}
+
+impl<B: fmt::LowerHex> fmt::Display for FlagsError<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagsError::Cast(error) => error.fmt(f),
+            FlagsError::UnknownBits(bits) => write!(f, "Unknown flag bits set: {:#x}", bits),
+        }
+    }
+}
+
+impl<B: fmt::Debug + fmt::LowerHex> core::error::Error for FlagsError<B> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FlagsError::Cast(error) => Some(error),
+            FlagsError::UnknownBits(_) => None,
+        }
+    }
+}
+
+impl<B> core::convert::From<FlagsError<B>> for Error {
+    #[inline]
+    fn from(e: FlagsError<B>) -> Error {
+        match e {
+            FlagsError::Cast(error) => error,
+            FlagsError::UnknownBits(_) => Error::Overflow,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Casts of `uom` quantities
+//!
+//! A quantity of the SI stored as an `f64`, like `uom::si::f64::Length`, can
+//! be casted into the same quantity stored as an `f32`, `uom::si::f32::Length`,
+//! and vice versa, with the checks of the cast between the storage types.
+//! `value` extracts the value of any quantity, in base units, into any
+//! primitive.
+//!
+//! ```
+//! use cast::Error;
+//! use uom::si::length::{kilometer, meter};
+//! use uom::si::{f32, f64};
+//!
+//! # fn main() {
+//! let marathon = f64::Length::new::<kilometer>(42.195);
+//!
+//! let length: f32::Length = cast::auto(marathon).unwrap();
+//! assert_eq!(length.get::<meter>(), 42195.);
+//! assert_eq!(cast::uom::value::<u16, _, _, _>(&marathon), Ok(42195));
+//!
+//! let light_year = f64::Length::new::<meter>(9.46e15);
+//! assert_eq!(cast::uom::value::<u32, _, _, _>(&light_year), Err(Error::Overflow));
+//! # }
+//! ```
+//!
+//! To get the value in other units, cast the result of `Quantity::get`.
+//!
+//! *This module requires the `uom` Cargo feature*
+
+use uom::num::Num;
+use uom::si::{Dimension, Quantity, Units};
+use uom::Conversion;
+
+use crate::{Error, From, IntoResult};
+
+macro_rules! quantity {
+    ($($src:ident => $dst:ident;)+) => {
+        $(
+            dst! {
+                $dst,
+
+                impl<D> From<Quantity<D, uom::si::SI<$src>, $src>> for Quantity<D, uom::si::SI<$dst>, $dst>
+                where
+                    D: Dimension + ?Sized,
+                {
+                    type Output = <<<$dst as From<$src>>::Output as crate::outcome::Outcome>::Fallibility as crate::outcome::Wrap<
+                        Quantity<D, uom::si::SI<$dst>, $dst>,
+                    >>::Output;
+                    type Error = Error;
+                    const FALLIBLE: bool = <$dst as From<$src>>::FALLIBLE;
+                    const FALLIBILITY: crate::Fallibility = <$dst as From<$src>>::FALLIBILITY;
+
+                    #[inline]
+                    fn cast(src: Quantity<D, uom::si::SI<$src>, $src>) -> Self::Output {
+                        // the base units are the same for every storage type
+                        let result = $dst::cast(src.value).into_result().map(|value| Quantity {
+                            dimension: src.dimension,
+                            units: core::marker::PhantomData,
+                            value,
+                        });
+
+                        <<<$dst as From<$src>>::Output as crate::outcome::Outcome>::Fallibility as crate::outcome::Wrap<
+                            Quantity<D, uom::si::SI<$dst>, $dst>,
+                        >>::wrap(result)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+quantity! {
+    f32 => f64;
+    f64 => f32;
+}
+
+// the output of the infallible casts
+impl<D, U, V> IntoResult<Quantity<D, U, V>> for Quantity<D, U, V>
+where
+    D: Dimension + ?Sized,
+    U: Units<V> + ?Sized,
+    V: Num + Conversion<V>,
+{
+    #[inline]
+    fn into_result(self) -> Result<Quantity<D, U, V>, Error> {
+        Ok(self)
+    }
+}
+
+/// Checked cast of the value of `q`, in the base units of its system, into
+/// `Dst`
+#[inline]
+pub fn value<Dst, D, U, V>(q: &Quantity<D, U, V>) -> Result<Dst, Error>
+where
+    D: Dimension + ?Sized,
+    U: Units<V> + ?Sized,
+    V: Num + Conversion<V> + Copy,
+    Dst: From<V>,
+    <Dst as From<V>>::Output: IntoResult<Dst>,
+{
+    Dst::cast(q.value).into_result()
+}
@@ -0,0 +1,172 @@
+//! Errors that carry the value that failed to be casted
+
+use core::fmt;
+#[cfg(feature = "track-location")]
+use core::panic::Location;
+
+use crate::{Error, From, IntoResult};
+
+/// A numeric value in a canonical, type independent, form
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    /// A value of a signed integer type
+    Int(i128),
+    /// A value of an unsigned integer type
+    UInt(u128),
+    /// A value of a floating point type
+    Float(f64),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Value::Int(x) => x.fmt(f),
+            Value::UInt(x) => x.fmt(f),
+            Value::Float(x) => x.fmt(f),
+        }
+    }
+}
+
+/// Numeric primitives that can be losslessly converted into a `Value`
+pub trait ToValue: Copy {
+    /// The smallest value of this type
+    const MIN: Value;
+    /// The largest value of this type
+    const MAX: Value;
+
+    /// Converts this number into a `Value`
+    fn to_value(self) -> Value;
+}
+
+macro_rules! to_value {
+    ($($variant:ident: $($ty:ident),+;)+) => {
+        $(
+            $(
+                impl ToValue for $ty {
+                    const MIN: Value = Value::$variant($ty::MIN as _);
+                    const MAX: Value = Value::$variant($ty::MAX as _);
+
+                    #[inline]
+                    fn to_value(self) -> Value {
+                        Value::$variant(self as _)
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+to_value! {
+    Int: i8, i16, i32, i64, i128, isize;
+    UInt: u8, u16, u32, u64, u128, usize;
+    Float: f32, f64;
+}
+
+/// A cast error that carries the value that failed to be casted, and the range
+/// of the destination type
+///
+/// Its `Display` implementation produces messages like "got 70000, expected ≤
+/// 65535".
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueError {
+    error: Error,
+    value: Value,
+    min: Value,
+    max: Value,
+    #[cfg(feature = "track-location")]
+    location: &'static Location<'static>,
+}
+
+impl ValueError {
+    /// The reason the cast failed
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// The value that failed to be casted
+    #[inline]
+    pub fn value(&self) -> Value {
+        self.value
+    }
+
+    /// The smallest value of the destination type
+    #[inline]
+    pub fn min(&self) -> Value {
+        self.min
+    }
+
+    /// The largest value of the destination type
+    #[inline]
+    pub fn max(&self) -> Value {
+        self.max
+    }
+
+    /// The location in the source code of the call to `with_value` that
+    /// produced this error
+    ///
+    /// *This API requires the `track-location` Cargo feature*
+    #[cfg(feature = "track-location")]
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
+            Error::Overflow => write!(f, "got {}, expected ≤ {}", self.value, self.max),
+            Error::NegOverflow => write!(f, "got {}, expected ≥ {}", self.value, self.min),
+            _ => write!(f, "got {}, expected a finite number", self.value),
+        }
+    }
+}
+
+impl core::error::Error for ValueError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl core::convert::From<ValueError> for Error {
+    #[inline]
+    fn from(e: ValueError) -> Error {
+        e.error
+    }
+}
+
+/// Checked cast that reports the offending value on failure
+///
+/// ```
+/// # fn main() {
+/// let e = cast::with_value::<u16, _>(70_000u32).unwrap_err();
+///
+/// assert_eq!(e.error(), cast::Error::Overflow);
+/// assert_eq!(e.to_string(), "got 70000, expected ≤ 65535");
+/// # }
+/// ```
+///
+/// *This API requires the `error-value` Cargo feature*
+#[cfg_attr(feature = "track-location", track_caller)]
+#[inline]
+pub fn with_value<Dst, Src>(src: Src) -> Result<Dst, ValueError>
+where
+    Src: ToValue,
+    Dst: From<Src> + ToValue,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    match <Dst as From<Src>>::cast(src).into_result() {
+        Ok(dst) => Ok(dst),
+        // NOTE not using `map_err` because `#[track_caller]` doesn't propagate
+        // into closures
+        Err(error) => Err(ValueError {
+            error,
+            value: src.to_value(),
+            min: Dst::MIN,
+            max: Dst::MAX,
+            #[cfg(feature = "track-location")]
+            location: Location::caller(),
+        }),
+    }
+}
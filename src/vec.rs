@@ -0,0 +1,115 @@
+//! Element-wise casts of whole vectors
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::{ElementError, From, IntoResult};
+
+/// Checked cast of every element of a vector
+///
+/// When `Src` and `Dst` have the same size and alignment, like `i8` and `u8`
+/// or `f32` and `u32`, the output is collected from `v` in place, which lets
+/// the standard library reuse its allocation; it does that today, but doesn't
+/// guarantee it. Otherwise the output is allocated once, with a capacity of
+/// `v.len()`.
+///
+/// With the `simd` feature enabled, the casts that the `simd` module has
+/// kernels for, like `f32` into `u8`, run those.
+///
+/// ```
+/// # fn main() {
+/// let samples = vec![-1i8, 0, 1];
+///
+/// let e = cast::vec::<u8, _>(samples).unwrap_err();
+/// assert_eq!(e.index(), 0);
+/// assert_eq!(e.value(), -1);
+/// assert_eq!(e.error(), cast::Error::NegOverflow);
+///
+/// assert_eq!(cast::vec::<f32, _>(vec![1u8, 2]), Ok(vec![1., 2.]));
+/// # }
+/// ```
+///
+/// *This API requires the `alloc` Cargo feature*
+#[inline]
+pub fn vec<Dst, Src>(v: Vec<Src>) -> Result<Vec<Dst>, ElementError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    <Dst as From<Src>>::vec(v)
+}
+
+/// `vec`, one element at a time
+#[inline]
+pub(crate) fn scalar<Dst, Src>(v: Vec<Src>) -> Result<Vec<Dst>, ElementError<Src>>
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let cast = |(index, x): (usize, Src)| match Dst::cast(x).into_result() {
+        Ok(y) => Ok(y),
+        Err(error) => Err(ElementError::new(index, x, error)),
+    };
+
+    if mem::size_of::<Src>() == mem::size_of::<Dst>()
+        && mem::align_of::<Src>() == mem::align_of::<Dst>()
+    {
+        return v.into_iter().enumerate().map(cast).collect();
+    }
+
+    // `collect`ing into a `Result` can't tell how many elements it will get,
+    // so it would grow the output as it goes
+    let mut dst = Vec::with_capacity(v.len());
+    for x in v.into_iter().enumerate() {
+        dst.push(cast(x)?);
+    }
+
+    Ok(dst)
+}
+
+/// Casts every element of a vector that can be casted, and reports the ones
+/// that can't
+///
+/// Returns the casted elements, in order, and the elements that failed to be
+/// casted, with their indices. Use this to set bad elements aside instead of
+/// aborting the whole conversion.
+///
+/// ```
+/// # fn main() {
+/// let rows = vec![20i32, -5, 300, 40];
+///
+/// let (ok, failed) = cast::vec_partition::<u8, _>(rows);
+///
+/// assert_eq!(ok, [20, 40]);
+/// assert_eq!(failed.iter().map(|e| e.index()).collect::<Vec<_>>(), [1, 2]);
+/// assert_eq!(failed[0].value(), -5);
+/// # }
+/// ```
+///
+/// *This API requires the `alloc` Cargo feature*
+#[inline]
+pub fn vec_partition<Dst, Src>(v: Vec<Src>) -> (Vec<Dst>, Vec<ElementError<Src>>)
+where
+    Src: Copy,
+    Dst: From<Src>,
+    <Dst as From<Src>>::Output: IntoResult<Dst>,
+{
+    let mut failed = Vec::new();
+    let ok = v
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, x)| match Dst::cast(x).into_result() {
+            Ok(y) => Some(y),
+            Err(error) => {
+                failed.push(ElementError::new(index, x, error));
+                None
+            }
+        })
+        .collect();
+
+    (ok, failed)
+}
@@ -0,0 +1,166 @@
+//! The narrowest type that can hold the values of two types
+
+use crate::{From, Lossless};
+
+/// Type-level function that pairs `Self` and `B` with a type that can hold
+/// every value of both
+///
+/// `Output` is the narrowest such type: `i32` and `u32` widen into `i64`, `u8`
+/// and `f32` into `f32`, etc. Pairs whose values don't all fit in a single
+/// primitive, like `i128` and `u128` or `u64` and `f64`, don't implement this
+/// trait. Implementations only exist if the Cargo feature of `Output` is
+/// enabled.
+///
+/// See the `Widest` type alias and the `widest` function.
+pub trait Widen<B>: Sized {
+    /// A type that can hold every value of `Self` and `B` exactly
+    type Output: Lossless<Self> + Lossless<B>;
+}
+
+/// The narrowest type that can hold every value of `A` and `B`
+///
+/// ```
+/// use cast::Widest;
+///
+/// # fn main() {
+/// let x: Widest<i32, u32> = -1i64;
+/// let y: Widest<u8, u16> = 65_535u16;
+/// # let _ = (x, y);
+/// # }
+/// ```
+pub type Widest<A, B> = <A as Widen<B>>::Output;
+
+/// Casts `a` and `b` into their widest type, so they can be compared, or
+/// combined, without truncating either
+///
+/// ```
+/// # fn main() {
+/// // `-1i32 < u32::MAX as i32` would be false
+/// let (a, b) = cast::widest(-1i32, u32::MAX);
+/// assert!(a < b);
+/// # }
+/// ```
+#[inline]
+pub fn widest<A, B>(a: A, b: B) -> (Widest<A, B>, Widest<A, B>)
+where
+    A: Widen<B>,
+{
+    (
+        <Widest<A, B> as From<A>>::cast(a),
+        <Widest<A, B> as From<B>>::cast(b),
+    )
+}
+
+macro_rules! widen {
+    ($($a:ident => $($b:ident: $out:ident),+;)+) => {
+        $(
+            $(
+                dst! {
+                    $out,
+
+                    impl Widen<$b> for $a {
+                        type Output = $out;
+                    }
+                }
+            )+
+        )+
+    }
+}
+
+widen! {
+    f32   => f32: f32, f64: f64, i8: f32, i16: f32, i32: f64, u8: f32, u16: f32, u32: f64;
+    f64   => f32: f64, f64: f64, i8: f64, i16: f64, i32: f64, u8: f64, u16: f64, u32: f64;
+    i8    => f32: f32, f64: f64, i8: i8, i16: i16, i32: i32, i64: i64, i128: i128, u8: i16,
+             u16: i32, u32: i64, u64: i128;
+    i16   => f32: f32, f64: f64, i8: i16, i16: i16, i32: i32, i64: i64, i128: i128, u8: i16,
+             u16: i32, u32: i64, u64: i128;
+    i32   => f32: f64, f64: f64, i8: i32, i16: i32, i32: i32, i64: i64, i128: i128, u8: i32,
+             u16: i32, u32: i64, u64: i128;
+    i64   => i8: i64, i16: i64, i32: i64, i64: i64, i128: i128, u8: i64, u16: i64, u32: i64,
+             u64: i128;
+    i128  => i8: i128, i16: i128, i32: i128, i64: i128, i128: i128, u8: i128, u16: i128, u32: i128,
+             u64: i128;
+    u8    => f32: f32, f64: f64, i8: i16, i16: i16, i32: i32, i64: i64, i128: i128, u8: u8,
+             u16: u16, u32: u32, u64: u64, u128: u128;
+    u16   => f32: f32, f64: f64, i8: i32, i16: i32, i32: i32, i64: i64, i128: i128, u8: u16,
+             u16: u16, u32: u32, u64: u64, u128: u128;
+    u32   => f32: f64, f64: f64, i8: i64, i16: i64, i32: i64, i64: i64, i128: i128, u8: u32,
+             u16: u32, u32: u32, u64: u64, u128: u128;
+    u64   => i8: i128, i16: i128, i32: i128, i64: i128, i128: i128, u8: u64, u16: u64, u32: u64,
+             u64: u64, u128: u128;
+    u128  => u8: u128, u16: u128, u32: u128, u64: u128, u128: u128;
+}
+
+/// `Widen` between the pointer-sized `$a` and `$b`, in both directions, or
+/// between `$a` and itself
+///
+/// Each row has the output for 16, 32 and 64-bit pointers, in that order, so
+/// all the widths of a pair are classified in one place. `_` means that the
+/// pair has no output for that width.
+macro_rules! widen_pointer {
+    ($($a:ident $(<=> $b:ident)? => $out16:tt $out32:tt $out64:tt;)+) => {
+        $(
+            widen_pointer!(@width "16", $out16, $a $(, $b)?);
+            widen_pointer!(@width "32", $out32, $a $(, $b)?);
+            widen_pointer!(@width "64", $out64, $a $(, $b)?);
+        )+
+    };
+    (@width $width:tt, _, $($ty:ident),+) => {};
+    (@width $width:tt, $out:ident, $a:ident) => {
+        dst! {
+            $out,
+
+            #[cfg(all(target_pointer_width = $width, not(feature = "portable")))]
+            impl Widen<$a> for $a {
+                type Output = $out;
+            }
+        }
+    };
+    (@width $width:tt, $out:ident, $a:ident, $b:ident) => {
+        dst! {
+            $out,
+
+            #[cfg(all(target_pointer_width = $width, not(feature = "portable")))]
+            impl Widen<$b> for $a {
+                type Output = $out;
+            }
+
+            #[cfg(all(target_pointer_width = $width, not(feature = "portable")))]
+            impl Widen<$a> for $b {
+                type Output = $out;
+            }
+        }
+    };
+}
+
+// With `portable`, `usize` and `isize` don't implement `Lossless`, so they
+// can't be widened either
+widen_pointer! {
+    //                16    32    64
+    isize          => isize isize isize;
+    isize <=> usize => i32   i64   i128;
+    isize <=> f32   => f32   f64   _;
+    isize <=> f64   => f64   f64   _;
+    isize <=> i8    => isize isize isize;
+    isize <=> i16   => i16   isize isize;
+    isize <=> i32   => i32   i32   isize;
+    isize <=> i64   => i64   i64   i64;
+    isize <=> i128  => i128  i128  i128;
+    isize <=> u8    => isize isize isize;
+    isize <=> u16   => i32   isize isize;
+    isize <=> u32   => i64   i64   isize;
+    isize <=> u64   => i128  i128  i128;
+    usize          => usize usize usize;
+    usize <=> f32   => f32   f64   _;
+    usize <=> f64   => f64   f64   _;
+    usize <=> i8    => i32   i64   i128;
+    usize <=> i16   => i32   i64   i128;
+    usize <=> i32   => i32   i64   i128;
+    usize <=> i64   => i64   i64   i128;
+    usize <=> i128  => i128  i128  i128;
+    usize <=> u8    => usize usize usize;
+    usize <=> u16   => u16   usize usize;
+    usize <=> u32   => u32   u32   usize;
+    usize <=> u64   => u64   u64   u64;
+    usize <=> u128  => u128  u128  u128;
+}